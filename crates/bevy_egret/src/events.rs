@@ -9,11 +9,13 @@ impl Plugin for EgretEventsPlugin {
         app.add_plugins((
             EventListenerPlugin::<Clicked>::default(),
             EventListenerPlugin::<ValueChanged<f32>>::default(),
+            EventListenerPlugin::<ValueChanged<bool>>::default(),
             EventListenerPlugin::<MenuEvent>::default(),
             EventListenerPlugin::<SplitterEvent>::default(),
         ))
         .add_event::<Clicked>()
         .add_event::<ValueChanged<f32>>()
+        .add_event::<ValueChanged<bool>>()
         .add_event::<MenuEvent>()
         .add_event::<SplitterEvent>();
     }
@@ -6,6 +6,7 @@ use bevy::{
     ui::{self, Node, Style},
     window::Window,
 };
+use bevy_quill::NodeWindow;
 
 /// Which side of the anchor the floating element should be placed.
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
@@ -61,24 +62,29 @@ impl Clone for Floating {
 }
 
 pub fn position_floating(
-    mut query: Query<(&mut Style, &Floating, &GlobalTransform)>,
+    mut query: Query<(Entity, &mut Style, &Floating, &GlobalTransform)>,
     anchor_query: Query<(&Node, &GlobalTransform), Without<Floating>>,
+    node_window: NodeWindow,
     windows: Query<&Window>,
 ) {
-    let Ok(window) = windows.get_single() else {
-        return;
-    };
-    let ww = window.resolution.physical_width() as f32;
-    let wh = window.resolution.physical_height() as f32;
-    let sf = window.resolution.scale_factor() as f32;
-
-    let window_rect = Rect::new(0., 0., ww / sf, wh / sf).inset(8.);
-
-    for (mut style, floating, floating_transform) in query.iter_mut() {
+    for (entity, mut style, floating, floating_transform) in query.iter_mut() {
         let Ok((anchor, anchor_transform)) = anchor_query.get(floating.anchor) else {
             continue;
         };
 
+        // Keep the floating element within the window that it is displayed in.
+        let Some(window) = node_window
+            .window(entity)
+            .and_then(|window| windows.get(window).ok())
+        else {
+            continue;
+        };
+        let ww = window.resolution.physical_width() as f32;
+        let wh = window.resolution.physical_height() as f32;
+        let sf = window.resolution.scale_factor() as f32;
+
+        let window_rect = Rect::new(0., 0., ww / sf, wh / sf).inset(8.);
+
         let anchor_rect = anchor.logical_rect(anchor_transform);
         let mut best_occluded = f32::MAX;
         let mut best_rect = Rect::default();
@@ -1,19 +1,16 @@
+//! `use_element_rect` is now a method of [`Cx`]; this trait is kept for compatibility.
 use bevy::prelude::*;
 use bevy_quill::prelude::*;
 
 /// Trait which adds `use_element_rect` to [`Cx`].
+#[deprecated(note = "use the inherent `Cx::use_element_rect` instead")]
 pub trait ElementRectApi {
     fn use_element_rect(&mut self, id: Entity) -> Rect;
 }
 
+#[allow(deprecated)]
 impl<'w, 'p, Props> ElementRectApi for Cx<'w, 'p, Props> {
     fn use_element_rect(&mut self, id: Entity) -> Rect {
-        match (
-            self.use_component::<Node>(id),
-            self.use_component_untracked::<GlobalTransform>(id),
-        ) {
-            (Some(node), Some(transform)) => node.logical_rect(transform),
-            _ => Rect::new(0., 0., 0., 0.),
-        }
+        Cx::use_element_rect(self, id)
     }
 }
@@ -9,10 +9,10 @@ use bevy_mod_picking::{events::PointerCancel, prelude::*};
 use bevy_quill::prelude::*;
 use bevy_tabindex::TabIndex;
 
+use super::WidgetView;
 use crate::Clicked;
 
 const CLS_PRESSED: &str = "pressed";
-const CLS_DISABLED: &str = "disabled";
 
 #[derive(Clone, PartialEq, Default)]
 pub struct ButtonProps<'a, V: View + Clone, S: StyleTuple = (), C: ClassNames<'a> = ()> {
@@ -31,42 +31,44 @@ pub fn button<'a, V: View + Clone, S: StyleTuple, C: ClassNames<'a>>(
     // Needs to be a local variable so that it can be captured in the event handler.
     let id = cx.props.id;
     let disabled = cx.props.disabled;
+    // Activate the button from the keyboard when it has focus. Pointer events are blocked by
+    // `.disabled()`, but key handlers still run, so they must check for themselves.
+    let activate = move |target: Entity, world: &mut World| {
+        if !disabled {
+            world.send_event(Clicked { target, id });
+        }
+    };
     Element::new()
         .named("button")
         .class_names((
             cx.props.class_names.clone(),
             CLS_PRESSED.if_true(cx.read_atom(is_pressed)),
-            CLS_DISABLED.if_true(disabled),
         ))
         .insert((
             TabIndex(0),
             AccessibilityNode::from(NodeBuilder::new(Role::Button)),
             On::<Pointer<Click>>::run(
                 move |ev: Listener<Pointer<Click>>, mut writer: EventWriter<Clicked>| {
-                    if !disabled {
-                        writer.send(Clicked {
-                            target: ev.target,
-                            id,
-                        });
-                    }
+                    writer.send(Clicked {
+                        target: ev.target,
+                        id,
+                    });
                 },
             ),
             On::<Pointer<DragStart>>::run(move |mut atoms: AtomStore| {
-                if !disabled {
-                    atoms.set(is_pressed, true);
-                }
+                atoms.set(is_pressed, true);
             }),
             On::<Pointer<DragEnd>>::run(move |mut atoms: AtomStore| {
-                if !disabled {
-                    atoms.set(is_pressed, false);
-                }
+                atoms.set(is_pressed, false);
             }),
             On::<Pointer<PointerCancel>>::run(move |mut atoms: AtomStore| {
-                if !disabled {
-                    atoms.set(is_pressed, false);
-                }
+                atoms.set(is_pressed, false);
             }),
         ))
+        .disabled(disabled)
+        .disable_tab_index(disabled)
+        .on_key(KeyCode::Enter, activate)
+        .on_key(KeyCode::Space, activate)
         .styled(cx.props.style.clone())
         .children(cx.props.children.clone())
 }
@@ -0,0 +1,82 @@
+use bevy::{
+    a11y::{
+        accesskit::{Checked, NodeBuilder, Role},
+        AccessibilityNode,
+    },
+    prelude::*,
+};
+use bevy_mod_picking::prelude::*;
+use bevy_quill::prelude::*;
+use bevy_tabindex::TabIndex;
+
+use super::WidgetView;
+use crate::ValueChanged;
+
+const CLS_CHECKED: &str = "checked";
+
+#[derive(Clone, PartialEq, Default)]
+pub struct CheckboxProps<'a, V: View + Clone, S: StyleTuple = (), C: ClassNames<'a> = ()> {
+    pub id: &'static str,
+    pub checked: bool,
+    pub children: V,
+    pub style: S,
+    pub class_names: C,
+    pub disabled: bool,
+    pub marker: std::marker::PhantomData<&'a ()>,
+}
+
+/// Headless checkbox. Clicking the checkbox, or pressing Space while it has focus, sends a
+/// [`ValueChanged<bool>`] event with the opposite of `checked`; the owner is expected to update
+/// `checked` in response. The root element has the class `checked` when checked.
+pub fn checkbox<'a, V: View + Clone, S: StyleTuple, C: ClassNames<'a>>(
+    cx: Cx<CheckboxProps<'a, V, S, C>>,
+) -> impl View {
+    // Needs to be a local variable so that it can be captured in the event handler.
+    let id = cx.props.id;
+    let checked = cx.props.checked;
+    let disabled = cx.props.disabled;
+    // Shared by the click and key handlers. `.disabled()` doesn't stop key handlers, or a click
+    // which is already in flight, so this checks for itself.
+    let toggle = move |target: Entity, world: &mut World| {
+        if !disabled {
+            world.send_event(ValueChanged {
+                target,
+                id,
+                value: !checked,
+                finish: true,
+            });
+        }
+    };
+    Element::new()
+        .named("checkbox")
+        .class_names((cx.props.class_names.clone(), CLS_CHECKED.if_true(checked)))
+        .insert((
+            TabIndex(0),
+            On::<Pointer<Click>>::run(
+                move |ev: Listener<Pointer<Click>>, mut commands: Commands| {
+                    let target = ev.target;
+                    commands.add(move |world: &mut World| toggle(target, world));
+                },
+            ),
+        ))
+        .disabled(disabled)
+        .disable_tab_index(disabled)
+        .with_memo(
+            move |mut e| {
+                let mut node = NodeBuilder::new(Role::CheckBox);
+                node.set_checked(if checked {
+                    Checked::True
+                } else {
+                    Checked::False
+                });
+                if disabled {
+                    node.set_disabled();
+                }
+                e.insert(AccessibilityNode::from(node));
+            },
+            (checked, disabled),
+        )
+        .on_key(KeyCode::Space, toggle)
+        .styled(cx.props.style.clone())
+        .children(cx.props.children.clone())
+}
@@ -9,6 +9,7 @@ use bevy_mod_picking::prelude::*;
 use bevy_quill::prelude::*;
 use bevy_tabindex::TabIndex;
 
+use super::WidgetView;
 use crate::{
     hooks::{EnterExitApi, EnterExitState},
     Clicked, MenuAction, MenuEvent,
@@ -18,6 +19,10 @@ const CLS_OPEN: &str = "open";
 
 pub const MENU_ANCHOR: ScopedValueKey<Entity> = ScopedValueKey::new("menu-anchor");
 
+/// Scoped value containing the entity which popup menus should be parented to, typically a
+/// full-window overlay node. If no ancestor provides it, popups are UI roots.
+pub const POPUP_LAYER: ScopedValueKey<Entity> = ScopedValueKey::new("popup-layer");
+
 #[derive(Clone, PartialEq)]
 pub struct MenuButtonProps<
     'a,
@@ -57,9 +62,11 @@ pub fn menu_button<'a, V: View + Clone, VP: View + Clone, S: StyleTuple, C: Clas
     mut cx: Cx<MenuButtonProps<'a, V, VP, S, C>>,
 ) -> impl View {
     let id_anchor = cx.props.anchor;
+    let disabled = cx.props.disabled;
+    let popup_layer = cx.get_scoped_value(POPUP_LAYER);
     let is_open = cx.create_atom_init::<bool>(|| false);
     let state = cx.use_enter_exit(cx.read_atom(is_open), 0.3);
-    cx.define_scoped_value(MENU_ANCHOR, id_anchor);
+    cx.provide(MENU_ANCHOR, id_anchor);
     RefElement::new(cx.props.anchor)
         .named("menu-button")
         .class_names((
@@ -101,12 +108,19 @@ pub fn menu_button<'a, V: View + Clone, VP: View + Clone, S: StyleTuple, C: Clas
                 }
             }),
         ))
+        .disabled(disabled)
+        .disable_tab_index(disabled)
         .styled(cx.props.style.clone())
         .children((
             cx.props.children.clone(),
             If::new(
                 state != EnterExitState::Exited,
-                Portal::new().children(
+                match popup_layer {
+                    Some(layer) => Portal::to(layer),
+                    None => Portal::new(),
+                }
+                .with_z(100)
+                .children(
                     Element::new()
                         .class_names(state.as_class_name())
                         .insert((
@@ -124,7 +138,6 @@ pub fn menu_button<'a, V: View + Clone, VP: View + Clone, S: StyleTuple, C: Clas
                                 position_type: PositionType::Absolute,
                                 ..default()
                             },
-                            ZIndex::Global(100),
                         ))
                         .children(cx.props.popup.clone()),
                 ),
@@ -158,7 +171,7 @@ pub fn menu_item<V: View + Clone, S: StyleTuple>(mut cx: Cx<MenuItemProps<V, S>>
     let _is_selected = cx.create_atom_init::<bool>(|| false);
     // Needs to be a local variable so that it can be captured in the event handler.
     let id = cx.props.id;
-    let anchor = cx.get_scoped_value(MENU_ANCHOR).unwrap();
+    let anchor = cx.consume(MENU_ANCHOR);
     Element::new()
         .named("menu-item")
         // .class_names((
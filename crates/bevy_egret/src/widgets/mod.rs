@@ -1,9 +1,35 @@
+use bevy_quill::View;
+use bevy_tabindex::DisabledTabIndex;
+
 mod button;
+mod checkbox;
 mod menu;
 mod slider;
+mod spin_box;
 mod splitter;
 
 pub use button::*;
+pub use checkbox::*;
 pub use menu::*;
 pub use slider::*;
+pub use spin_box::*;
 pub use splitter::*;
+
+/// View methods shared by the widgets in this module.
+pub(crate) trait WidgetView: View + Sized {
+    /// Removes the output entity from the tab order while `disabled` is true.
+    fn disable_tab_index(self, disabled: bool) -> impl View {
+        self.with_memo(
+            move |mut e| {
+                if disabled {
+                    e.insert(DisabledTabIndex);
+                } else {
+                    e.remove::<DisabledTabIndex>();
+                }
+            },
+            disabled,
+        )
+    }
+}
+
+impl<V: View> WidgetView for V {}
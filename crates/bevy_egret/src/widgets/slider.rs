@@ -3,7 +3,7 @@ use std::sync::Arc;
 use crate::ValueChanged;
 use bevy::prelude::*;
 use bevy_mod_picking::{events::PointerCancel, prelude::*};
-use bevy_quill::prelude::*;
+use bevy_quill::{prelude::*, Cursor, CursorLock};
 
 /// Props which are passed to the children via the closure.
 pub struct SliderChildProps {
@@ -41,6 +41,10 @@ pub struct SliderProps<V: View, F: Fn(SliderChildProps) -> V, S: StyleTuple> {
 
     /// Style handle for slider root element.
     pub style: S,
+
+    /// Callback which is invoked with the new value when the slider is dragged. This is in
+    /// addition to the [`ValueChanged`] event.
+    pub on_change: Option<CallbackHandle<f32>>,
 }
 
 impl<V: View, F: Fn(SliderChildProps) -> V, S: StyleTuple> PartialEq for SliderProps<V, F, S> {
@@ -49,6 +53,7 @@ impl<V: View, F: Fn(SliderChildProps) -> V, S: StyleTuple> PartialEq for SliderP
             && self.min == other.min
             && self.max == other.max
             && self.value == other.value
+            && self.on_change == other.on_change
             && std::ptr::eq(
                 self.children.as_ref() as *const _,
                 other.children.as_ref() as *const _,
@@ -66,6 +71,7 @@ impl<V: View, F: Fn(SliderChildProps) -> V, S: StyleTuple> Clone for SliderProps
             thumb_size: self.thumb_size,
             children: self.children.clone(),
             style: self.style.clone(),
+            on_change: self.on_change,
         }
     }
 }
@@ -80,13 +86,14 @@ struct DragState {
 pub fn h_slider<V: View, F: Fn(SliderChildProps) -> V, S: StyleTuple>(
     mut cx: Cx<SliderProps<V, F, S>>,
 ) -> impl View {
-    let drag_state = cx.create_atom_init::<DragState>(DragState::default);
+    let (drag_state, set_drag_state) = cx.use_local(DragState::default);
     // Pain point: Need to capture all props for closures.
     let id = cx.props.id;
     let thumb_size = cx.props.thumb_size;
     let min = cx.props.min;
     let max = cx.props.max;
     let value = cx.props.value;
+    let on_change = cx.props.on_change;
     let range = cx.props.max - cx.props.min;
     let pos = if range > 0. {
         (cx.props.value - cx.props.min) / range
@@ -95,34 +102,41 @@ pub fn h_slider<V: View, F: Fn(SliderChildProps) -> V, S: StyleTuple>(
     }
     .clamp(0., 1.);
 
+    let on_drag_start = set_drag_state.clone();
+    let on_drag_end = set_drag_state.clone();
+    let on_drag = set_drag_state.clone();
+    let on_cancel = set_drag_state;
+
     Element::new()
         .styled(cx.props.style.clone())
         .insert((
-            On::<Pointer<DragStart>>::run(move |mut atoms: AtomStore| {
-                // Save initial value to use as drag offset.
-                atoms.set(
-                    drag_state,
-                    DragState {
+            On::<Pointer<DragStart>>::run(
+                move |ev: Listener<Pointer<DragStart>>, mut commands: Commands| {
+                    commands
+                        .entity(ev.listener())
+                        .insert(CursorLock(Cursor::Grab));
+                    // Save initial value to use as drag offset.
+                    on_drag_start.set(DragState {
                         dragging: true,
                         offset: value,
-                    },
-                );
-            }),
-            On::<Pointer<DragEnd>>::run(move |mut atoms: AtomStore| {
-                atoms.set(
-                    drag_state,
-                    DragState {
+                    });
+                },
+            ),
+            On::<Pointer<DragEnd>>::run(
+                move |ev: Listener<Pointer<DragEnd>>, mut commands: Commands| {
+                    commands.entity(ev.listener()).remove::<CursorLock>();
+                    on_drag_end.set(DragState {
                         dragging: false,
                         offset: value,
-                    },
-                );
-            }),
+                    });
+                },
+            ),
             On::<Pointer<Drag>>::run(
                 move |ev: Listener<Pointer<Drag>>,
                       query: Query<(&Node, &GlobalTransform)>,
-                      atoms: AtomStore,
-                      mut writer: EventWriter<ValueChanged<f32>>| {
-                    let ds = atoms.get(drag_state);
+                      mut writer: EventWriter<ValueChanged<f32>>,
+                      mut commands: Commands| {
+                    let ds = on_drag.get();
                     if ds.dragging {
                         if let Ok((node, transform)) = query.get(ev.listener()) {
                             // Measure node width and slider value.
@@ -132,32 +146,35 @@ pub fn h_slider<V: View, F: Fn(SliderChildProps) -> V, S: StyleTuple>(
                             } else {
                                 min + range * 0.5
                             };
+                            let new_value = new_value.clamp(min, max);
                             writer.send(ValueChanged::<f32> {
                                 target: ev.target,
                                 id,
-                                value: new_value.clamp(min, max),
+                                value: new_value,
                                 finish: false,
                             });
+                            if let Some(on_change) = on_change {
+                                commands.run_callback(on_change, new_value);
+                            }
                         }
                     }
                 },
             ),
-            On::<Pointer<PointerCancel>>::run(move |mut atoms: AtomStore| {
-                println!("Slider Cancel");
-                atoms.set(
-                    drag_state,
-                    DragState {
+            On::<Pointer<PointerCancel>>::run(
+                move |ev: Listener<Pointer<PointerCancel>>, mut commands: Commands| {
+                    commands.entity(ev.listener()).remove::<CursorLock>();
+                    on_cancel.set(DragState {
                         dragging: false,
                         offset: value,
-                    },
-                );
-            }),
+                    });
+                },
+            ),
         ))
         .children((cx.props.children)(SliderChildProps {
             percent: pos * 100.,
             min,
             max,
             value,
-            is_dragging: cx.read_atom(drag_state).dragging,
+            is_dragging: drag_state.dragging,
         }))
 }
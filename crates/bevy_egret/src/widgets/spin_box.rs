@@ -0,0 +1,467 @@
+use std::sync::Arc;
+
+use crate::ValueChanged;
+use bevy::{
+    a11y::{
+        accesskit::{NodeBuilder, Role},
+        AccessibilityNode, Focus,
+    },
+    prelude::*,
+};
+use bevy_mod_picking::{events::PointerCancel, prelude::*};
+use bevy_quill::{prelude::*, Cursor, CursorLock, KeyBindings, ScrollKeyboardOptOut};
+use bevy_tabindex::TabIndex;
+
+const CLS_DRAG: &str = "drag";
+const CLS_EDITING: &str = "editing";
+const CLS_SELECTED: &str = "selected";
+
+/// How far, in pixels, the pointer must be dragged to change the value by one step.
+const DRAG_PIXELS_PER_STEP: f32 = 4.;
+
+/// Keys which type a character into a spin box.
+const TYPED_KEYS: [(KeyCode, char); 24] = [
+    (KeyCode::Digit0, '0'),
+    (KeyCode::Digit1, '1'),
+    (KeyCode::Digit2, '2'),
+    (KeyCode::Digit3, '3'),
+    (KeyCode::Digit4, '4'),
+    (KeyCode::Digit5, '5'),
+    (KeyCode::Digit6, '6'),
+    (KeyCode::Digit7, '7'),
+    (KeyCode::Digit8, '8'),
+    (KeyCode::Digit9, '9'),
+    (KeyCode::Numpad0, '0'),
+    (KeyCode::Numpad1, '1'),
+    (KeyCode::Numpad2, '2'),
+    (KeyCode::Numpad3, '3'),
+    (KeyCode::Numpad4, '4'),
+    (KeyCode::Numpad5, '5'),
+    (KeyCode::Numpad6, '6'),
+    (KeyCode::Numpad7, '7'),
+    (KeyCode::Numpad8, '8'),
+    (KeyCode::Numpad9, '9'),
+    (KeyCode::Minus, '-'),
+    (KeyCode::NumpadSubtract, '-'),
+    (KeyCode::Period, '.'),
+    (KeyCode::NumpadDecimal, '.'),
+];
+
+/// Props which are passed to the children via the closure.
+pub struct SpinBoxChildProps {
+    /// The text to display: the formatted value, or the text being typed while editing.
+    pub text: String,
+    /// Spin box position as a percentage of the range from `min` to `max`, or `None` if the
+    /// range is unbounded.
+    pub percent: Option<f32>,
+    /// True if a value is being typed.
+    pub editing: bool,
+    /// True if the value is being changed by dragging.
+    pub is_dragging: bool,
+}
+
+/// Properties for spin box widget.
+pub struct SpinBoxProps<V: View, F: Fn(SpinBoxChildProps) -> V, S: StyleTuple> {
+    /// Unique ID for the spin box.
+    pub id: &'static str,
+
+    /// Current value.
+    pub value: f32,
+
+    /// Minimum value.
+    pub min: f32,
+
+    /// Maximum value.
+    pub max: f32,
+
+    /// Amount by which the value changes for each step.
+    pub step: f32,
+
+    /// Number of decimal places to display. Values are rounded to this precision.
+    pub precision: usize,
+
+    /// Closure which renders the spin box elements.
+    pub children: Arc<F>,
+
+    /// Style handle for spin box root element.
+    pub style: S,
+}
+
+impl<V: View, F: Fn(SpinBoxChildProps) -> V, S: StyleTuple> PartialEq for SpinBoxProps<V, F, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+            && self.value == other.value
+            && self.min == other.min
+            && self.max == other.max
+            && self.step == other.step
+            && self.precision == other.precision
+            && std::ptr::eq(
+                self.children.as_ref() as *const _,
+                other.children.as_ref() as *const _,
+            )
+    }
+}
+
+impl<V: View, F: Fn(SpinBoxChildProps) -> V, S: StyleTuple> Clone for SpinBoxProps<V, F, S> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            value: self.value,
+            min: self.min,
+            max: self.max,
+            step: self.step,
+            precision: self.precision,
+            children: self.children.clone(),
+            style: self.style.clone(),
+        }
+    }
+}
+
+/// Marks an element within a spin box, such as an arrow button, which changes the value by
+/// this many steps when clicked.
+#[derive(Component, Clone, Copy)]
+pub struct SpinBoxStep(pub f32);
+
+#[derive(Clone, PartialEq, Default, Copy)]
+struct DragState {
+    dragging: bool,
+    /// True if the pointer has moved far enough that releasing it isn't a click.
+    moved: bool,
+    origin: f32,
+    value: f32,
+}
+
+/// Round a value to the given number of decimal places.
+fn round_to(value: f32, precision: usize) -> f32 {
+    let scale = 10f32.powi(precision as i32);
+    (value * scale).round() / scale
+}
+
+/// Find the step of the [`SpinBoxStep`] element at or above `target`, stopping at the root.
+/// Text being typed into a spin box.
+#[derive(Clone, Default, PartialEq)]
+struct EditState {
+    text: String,
+    /// True until the first key is typed after editing starts with the current value: the
+    /// text is then treated as selected, and typing replaces it.
+    selected: bool,
+}
+
+impl EditState {
+    /// Start editing with the current value selected.
+    fn select(value: f32, precision: usize) -> Self {
+        Self {
+            text: format!("{:.*}", precision, value),
+            selected: true,
+        }
+    }
+}
+
+fn step_at(
+    target: Entity,
+    root: Entity,
+    steps: &Query<&SpinBoxStep>,
+    parents: &Query<&Parent>,
+) -> Option<f32> {
+    std::iter::once(target)
+        .chain(parents.iter_ancestors(target))
+        .take_while(|e| *e != root)
+        .find_map(|e| steps.get(e).ok().map(|step| step.0))
+}
+
+/// Numeric spin box widget. The value can be changed by dragging horizontally, where Shift
+/// makes smaller steps and Ctrl larger ones; by clicking [`SpinBoxStep`] elements; with the
+/// up and down arrow keys; or by clicking and typing a new value, which is committed with
+/// Enter and abandoned with Escape or when the spin box loses focus. Clicking starts editing
+/// with the current value selected, and the spin box has the class `selected` until the first
+/// key is typed, which replaces it. Sends a [`ValueChanged<f32>`] event for each change, with
+/// `finish` set when a drag is released.
+pub fn spin_box<V: View, F: Fn(SpinBoxChildProps) -> V, S: StyleTuple>(
+    mut cx: Cx<SpinBoxProps<V, F, S>>,
+) -> impl View {
+    let root = cx.create_entity();
+    let drag_state = cx.create_atom_init(DragState::default);
+    let edit = cx.create_atom_init::<Option<EditState>>(|| None);
+    // Pain point: Need to capture all props for closures.
+    let id = cx.props.id;
+    let value = cx.props.value;
+    let min = cx.props.min;
+    let max = cx.props.max;
+    let step = cx.props.step;
+    let precision = cx.props.precision;
+    let range = max - min;
+    let percent = if range > 0. && range.is_finite() {
+        Some(((value - min) / range).clamp(0., 1.) * 100.)
+    } else {
+        None
+    };
+
+    // Stop editing if the spin box loses focus.
+    let focus = cx.use_resource::<Focus>().0;
+    let mut text = cx.read_atom(edit);
+    if text.is_some() && focus != Some(root) {
+        cx.write_atom(edit, None);
+        text = None;
+    }
+    let editing = text.is_some();
+    let selected = text.as_ref().is_some_and(|edit| edit.selected);
+    let is_dragging = cx.read_atom(drag_state).dragging;
+
+    RefElement::new(root)
+        .named("spin-box")
+        .class_names((
+            CLS_DRAG.if_true(is_dragging),
+            CLS_EDITING.if_true(editing),
+            CLS_SELECTED.if_true(selected),
+        ))
+        .insert((
+            TabIndex(0),
+            // The arrow keys step the value rather than scrolling.
+            ScrollKeyboardOptOut,
+            On::<Pointer<Down>>::run(move |mut atoms: AtomStore| {
+                let ds = atoms.get(drag_state);
+                atoms.set(drag_state, DragState { moved: false, ..ds });
+            }),
+            On::<Pointer<DragStart>>::run(
+                move |ev: Listener<Pointer<DragStart>>,
+                      steps: Query<&SpinBoxStep>,
+                      parents: Query<&Parent>,
+                      mut atoms: AtomStore,
+                      mut commands: Commands| {
+                    // Dragging the arrow buttons doesn't change the value.
+                    if step_at(ev.target, root, &steps, &parents).is_some()
+                        || atoms.get(edit).is_some()
+                    {
+                        return;
+                    }
+                    commands.entity(root).insert(CursorLock(Cursor::ColResize));
+                    atoms.set(
+                        drag_state,
+                        DragState {
+                            dragging: true,
+                            moved: false,
+                            origin: value,
+                            value,
+                        },
+                    );
+                },
+            ),
+            On::<Pointer<Drag>>::run(
+                move |ev: Listener<Pointer<Drag>>,
+                      keys: Res<ButtonInput<KeyCode>>,
+                      mut atoms: AtomStore,
+                      mut writer: EventWriter<ValueChanged<f32>>| {
+                    let mut ds = atoms.get(drag_state);
+                    if !ds.dragging {
+                        return;
+                    }
+                    let scale = if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+                        0.1
+                    } else if keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+                        10.
+                    } else {
+                        1.
+                    };
+                    if ev.distance.x.abs() >= DRAG_PIXELS_PER_STEP {
+                        ds.moved = true;
+                    }
+                    let steps = (ev.distance.x / DRAG_PIXELS_PER_STEP).trunc();
+                    let new_value = round_to(
+                        (ds.origin + steps * step * scale).clamp(min, max),
+                        precision,
+                    );
+                    if new_value != ds.value {
+                        ds.value = new_value;
+                        writer.send(ValueChanged {
+                            target: root,
+                            id,
+                            value: new_value,
+                            finish: false,
+                        });
+                    }
+                    atoms.set(drag_state, ds);
+                },
+            ),
+            On::<Pointer<DragEnd>>::run(
+                move |mut atoms: AtomStore,
+                      mut writer: EventWriter<ValueChanged<f32>>,
+                      mut commands: Commands| {
+                    let ds = atoms.get(drag_state);
+                    if !ds.dragging {
+                        return;
+                    }
+                    commands.entity(root).remove::<CursorLock>();
+                    if ds.moved {
+                        writer.send(ValueChanged {
+                            target: root,
+                            id,
+                            value: ds.value,
+                            finish: true,
+                        });
+                    }
+                    atoms.set(
+                        drag_state,
+                        DragState {
+                            dragging: false,
+                            ..ds
+                        },
+                    );
+                },
+            ),
+            On::<Pointer<PointerCancel>>::run(
+                move |mut atoms: AtomStore,
+                      mut writer: EventWriter<ValueChanged<f32>>,
+                      mut commands: Commands| {
+                    let ds = atoms.get(drag_state);
+                    if !ds.dragging {
+                        return;
+                    }
+                    // Restore the value from before the drag.
+                    commands.entity(root).remove::<CursorLock>();
+                    writer.send(ValueChanged {
+                        target: root,
+                        id,
+                        value: ds.origin,
+                        finish: true,
+                    });
+                    atoms.set(drag_state, DragState::default());
+                },
+            ),
+            On::<Pointer<Click>>::run(
+                move |ev: Listener<Pointer<Click>>,
+                      steps: Query<&SpinBoxStep>,
+                      parents: Query<&Parent>,
+                      mut atoms: AtomStore,
+                      mut focus: ResMut<Focus>,
+                      mut writer: EventWriter<ValueChanged<f32>>| {
+                    if let Some(n) = step_at(ev.target, root, &steps, &parents) {
+                        let new_value = round_to((value + n * step).clamp(min, max), precision);
+                        if new_value != value {
+                            writer.send(ValueChanged {
+                                target: root,
+                                id,
+                                value: new_value,
+                                finish: true,
+                            });
+                        }
+                    } else if !atoms.get(drag_state).moved && atoms.get(edit).is_none() {
+                        // A click which wasn't the end of a drag starts editing.
+                        atoms.set(edit, Some(EditState::select(value, precision)));
+                        focus.0 = Some(root);
+                    }
+                },
+            ),
+        ))
+        .with_memo(
+            move |mut e| {
+                let mut node = NodeBuilder::new(Role::SpinButton);
+                node.set_numeric_value(value as f64);
+                node.set_min_numeric_value(min as f64);
+                node.set_max_numeric_value(max as f64);
+                node.set_numeric_value_step(step as f64);
+                e.insert((
+                    AccessibilityNode::from(node),
+                    key_bindings(root, id, value, min, max, step, precision, edit),
+                ));
+            },
+            (id, value, min, max, step, precision),
+        )
+        .styled(cx.props.style.clone())
+        .children((cx.props.children)(SpinBoxChildProps {
+            text: text.map_or_else(|| format!("{:.*}", precision, value), |edit| edit.text),
+            percent,
+            editing,
+            is_dragging,
+        }))
+}
+
+/// Build the key bindings of a spin box, which step the value, and type, commit or cancel an
+/// edited value.
+#[allow(clippy::too_many_arguments)]
+fn key_bindings(
+    root: Entity,
+    id: &'static str,
+    value: f32,
+    min: f32,
+    max: f32,
+    step: f32,
+    precision: usize,
+    edit: AtomHandle<Option<EditState>>,
+) -> KeyBindings {
+    let send = move |world: &mut World, new_value: f32| {
+        let new_value = round_to(new_value.clamp(min, max), precision);
+        if new_value != value {
+            world.send_event(ValueChanged {
+                target: root,
+                id,
+                value: new_value,
+                finish: true,
+            });
+        }
+    };
+    let mut bindings = KeyBindings::default();
+    for (key, ch) in TYPED_KEYS {
+        bindings.bind(
+            key,
+            Arc::new(move |_, world: &mut World| {
+                // Typing while not editing, or while the text is selected, replaces the value.
+                let mut text = match world.get_atom(edit) {
+                    Some(edit) if !edit.selected => edit.text,
+                    _ => String::new(),
+                };
+                text.push(ch);
+                let selected = false;
+                world.set_atom(edit, Some(EditState { text, selected }));
+                world.resource_mut::<Focus>().0 = Some(root);
+            }),
+        );
+    }
+    bindings.bind(
+        KeyCode::Backspace,
+        Arc::new(move |_, world: &mut World| {
+            if let Some(mut state) = world.get_atom(edit) {
+                if state.selected {
+                    state.text.clear();
+                    state.selected = false;
+                } else {
+                    state.text.pop();
+                }
+                world.set_atom(edit, Some(state));
+            }
+        }),
+    );
+    bindings.bind(
+        KeyCode::Enter,
+        Arc::new(move |_, world: &mut World| match world.get_atom(edit) {
+            Some(state) => {
+                world.set_atom(edit, None);
+                // Text which isn't a number leaves the value unchanged.
+                if let Ok(new_value) = state.text.trim().parse::<f32>() {
+                    send(world, new_value);
+                }
+            }
+            None => world.set_atom(edit, Some(EditState::select(value, precision))),
+        }),
+    );
+    bindings.bind(
+        KeyCode::Escape,
+        Arc::new(move |_, world: &mut World| world.set_atom(edit, None)),
+    );
+    bindings.bind(
+        KeyCode::ArrowUp,
+        Arc::new(move |_, world: &mut World| {
+            if world.get_atom(edit).is_none() {
+                send(world, value + step);
+            }
+        }),
+    );
+    bindings.bind(
+        KeyCode::ArrowDown,
+        Arc::new(move |_, world: &mut World| {
+            if world.get_atom(edit).is_none() {
+                send(world, value - step);
+            }
+        }),
+    );
+    bindings
+}
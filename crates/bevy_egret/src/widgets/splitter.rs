@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 use bevy_mod_picking::{events::PointerCancel, prelude::*};
-use bevy_quill::prelude::*;
+use bevy_quill::{prelude::*, Cursor, CursorLock};
 
 use crate::SplitterEvent;
 
@@ -32,25 +32,38 @@ pub fn v_splitter<V: View + Clone, S: StyleTuple>(mut cx: Cx<SplitterProps<V, S>
         .class_names(CLS_DRAG.if_true(cx.read_atom(drag_state).dragging))
         .styled(cx.props.style.clone())
         .insert((
-            On::<Pointer<DragStart>>::run(move |mut atoms: AtomStore| {
-                // Save initial value to use as drag offset.
-                atoms.set(
-                    drag_state,
-                    DragState {
-                        dragging: true,
-                        offset: current_offset,
-                    },
-                );
-            }),
-            On::<Pointer<DragEnd>>::run(move |mut atoms: AtomStore| {
-                atoms.set(
-                    drag_state,
-                    DragState {
-                        dragging: false,
-                        offset: current_offset,
-                    },
-                );
-            }),
+            On::<Pointer<DragStart>>::run(
+                move |ev: Listener<Pointer<DragStart>>,
+                      mut atoms: AtomStore,
+                      mut commands: Commands| {
+                    // Keep the resize cursor while dragging, even if the pointer leaves the bar.
+                    commands
+                        .entity(ev.listener())
+                        .insert(CursorLock(Cursor::ColResize));
+                    // Save initial value to use as drag offset.
+                    atoms.set(
+                        drag_state,
+                        DragState {
+                            dragging: true,
+                            offset: current_offset,
+                        },
+                    );
+                },
+            ),
+            On::<Pointer<DragEnd>>::run(
+                move |ev: Listener<Pointer<DragEnd>>,
+                      mut atoms: AtomStore,
+                      mut commands: Commands| {
+                    commands.entity(ev.listener()).remove::<CursorLock>();
+                    atoms.set(
+                        drag_state,
+                        DragState {
+                            dragging: false,
+                            offset: current_offset,
+                        },
+                    );
+                },
+            ),
             On::<Pointer<Drag>>::run(
                 move |ev: Listener<Pointer<Drag>>,
                       mut writer: EventWriter<SplitterEvent>,
@@ -65,16 +78,20 @@ pub fn v_splitter<V: View + Clone, S: StyleTuple>(mut cx: Cx<SplitterProps<V, S>
                     }
                 },
             ),
-            On::<Pointer<PointerCancel>>::run(move |mut atoms: AtomStore| {
-                println!("Splitter Cancel");
-                atoms.set(
-                    drag_state,
-                    DragState {
-                        dragging: true,
-                        offset: current_offset,
-                    },
-                );
-            }),
+            On::<Pointer<PointerCancel>>::run(
+                move |ev: Listener<Pointer<PointerCancel>>,
+                      mut atoms: AtomStore,
+                      mut commands: Commands| {
+                    commands.entity(ev.listener()).remove::<CursorLock>();
+                    atoms.set(
+                        drag_state,
+                        DragState {
+                            dragging: false,
+                            offset: current_offset,
+                        },
+                    );
+                },
+            ),
         ))
         .children(cx.props.children.clone())
 }
@@ -7,7 +7,6 @@ impl Plugin for GracklePlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
             bevy_egret::EgretEventsPlugin,
-            bevy_egret::hooks::EnterExitPlugin,
             bevy_egret::EgretFloatingPlugin,
         ));
     }
@@ -133,6 +133,41 @@ static STYLE_DK_BUTTON_DANGER: StyleHandle = StyleHandle::build(|ss| {
         })
 });
 
+// Checkbox
+
+// The box of the checkbox.
+#[dynamic]
+static STYLE_LT_CHECKBOX: StyleHandle = StyleHandle::build(|ss| {
+    ss.background_color(COLOR_GRAY_200)
+        .border_color(COLOR_GRAY_700)
+        .selector(":hover > &", |ss| ss.background_color(COLOR_GRAY_50))
+        .selector(".checked > &", |ss| ss.background_color(COLOR_TEAL_500))
+        .selector(".checked:hover > &", |ss| {
+            ss.background_color(COLOR_TEAL_400)
+        })
+        .selector(":focus > &", |ss| {
+            ss.outline_color(COLOR_GRAY_400)
+                .outline_width(2)
+                .outline_offset(1)
+        })
+});
+
+#[dynamic]
+static STYLE_DK_CHECKBOX: StyleHandle = StyleHandle::build(|ss| {
+    ss.background_color(COLOR_BLUEGRAY_900)
+        .border_color(COLOR_BLACK)
+        .selector(":hover > &", |ss| ss.background_color(COLOR_BLUEGRAY_700))
+        .selector(".checked > &", |ss| ss.background_color(COLOR_TEAL_600))
+        .selector(".checked:hover > &", |ss| {
+            ss.background_color(COLOR_TEAL_500)
+        })
+        .selector(":focus > &", |ss| {
+            ss.outline_color(COLOR_GRAY_400)
+                .outline_width(2)
+                .outline_offset(1)
+        })
+});
+
 // Splitter
 
 #[dynamic]
@@ -241,6 +276,7 @@ pub fn init_grackle_theme<T>(cx: &mut Cx<T>, theme: GrackleTheme) {
             cx.define_scoped_value(BUTTON_DEFAULT, STYLE_LT_BUTTON_DEFAULT.clone());
             cx.define_scoped_value(BUTTON_PRIMARY, STYLE_DK_BUTTON_PRIMARY.clone());
             cx.define_scoped_value(BUTTON_DANGER, STYLE_DK_BUTTON_DANGER.clone());
+            cx.define_scoped_value(CHECKBOX, STYLE_LT_CHECKBOX.clone());
             cx.define_scoped_value(SPLITTER, STYLE_LT_SPLITTER.clone());
             cx.define_scoped_value(SPLITTER_INNER, STYLE_LT_SPLITTER_INNER.clone());
             cx.define_scoped_value(H_SLIDER_TRACK, STYLE_LT_SLIDER_TRACK.clone());
@@ -255,6 +291,7 @@ pub fn init_grackle_theme<T>(cx: &mut Cx<T>, theme: GrackleTheme) {
             cx.define_scoped_value(BUTTON_DEFAULT, STYLE_DK_BUTTON_DEFAULT.clone());
             cx.define_scoped_value(BUTTON_PRIMARY, STYLE_DK_BUTTON_PRIMARY.clone());
             cx.define_scoped_value(BUTTON_DANGER, STYLE_DK_BUTTON_DANGER.clone());
+            cx.define_scoped_value(CHECKBOX, STYLE_DK_CHECKBOX.clone());
             cx.define_scoped_value(SPLITTER, STYLE_DK_SPLITTER.clone());
             cx.define_scoped_value(SPLITTER_INNER, STYLE_DK_SPLITTER_INNER.clone());
             cx.define_scoped_value(H_SLIDER_TRACK, STYLE_DK_SLIDER_TRACK.clone());
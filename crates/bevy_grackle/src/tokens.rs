@@ -5,6 +5,7 @@ pub const SIDEBAR: ScopedValueKey<StyleHandle> = ScopedValueKey::new("sidebar");
 pub const BUTTON_DEFAULT: ScopedValueKey<StyleHandle> = ScopedValueKey::new("button-default");
 pub const BUTTON_PRIMARY: ScopedValueKey<StyleHandle> = ScopedValueKey::new("button-primary");
 pub const BUTTON_DANGER: ScopedValueKey<StyleHandle> = ScopedValueKey::new("button-danger");
+pub const CHECKBOX: ScopedValueKey<StyleHandle> = ScopedValueKey::new("checkbox");
 pub const SPLITTER: ScopedValueKey<StyleHandle> = ScopedValueKey::new("splitter");
 pub const SPLITTER_INNER: ScopedValueKey<StyleHandle> = ScopedValueKey::new("splitter-inner");
 pub const H_SLIDER_TRACK: ScopedValueKey<StyleHandle> = ScopedValueKey::new("h-slider-track");
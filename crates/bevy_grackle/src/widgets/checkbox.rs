@@ -0,0 +1,111 @@
+use bevy::{asset::AssetPath, ui};
+use bevy_quill::prelude::*;
+use static_init::dynamic;
+
+use crate::tokens::CHECKBOX;
+
+const BOX_SIZE: f32 = 16.;
+
+// Style definitions for checkbox widget.
+
+// The checkbox, containing the box and the label.
+#[dynamic]
+static STYLE_CHECKBOX: StyleHandle = StyleHandle::build(|ss| {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .gap(6)
+});
+
+// The box
+#[dynamic]
+static STYLE_BOX: StyleHandle = StyleHandle::build(|ss| {
+    ss.width(BOX_SIZE)
+        .height(BOX_SIZE)
+        .flex_shrink(0.)
+        .border(1)
+});
+
+// The checkmark inside the box, which is only shown when checked.
+#[dynamic]
+static STYLE_CHECKMARK: StyleHandle = StyleHandle::build(|ss| {
+    ss.position(ui::PositionType::Absolute)
+        .top(0)
+        .left(0)
+        .bottom(0)
+        .right(0)
+        .background_image(Some(AssetPath::from("grackle://icons/checkmark.png")))
+        .display(ui::Display::None)
+        .selector(".checked > * > &", |ss| ss.display(ui::Display::Flex))
+        .pointer_events(PointerEvents::None)
+});
+
+#[derive(PartialEq, Clone, Default)]
+pub struct CheckboxProps<V: View + Clone = (), S: StyleTuple = ()> {
+    pub id: &'static str,
+    pub checked: bool,
+    pub label: V,
+    pub disabled: bool,
+    pub style: S,
+}
+
+impl CheckboxProps<(), ()> {
+    pub fn new(id: &'static str) -> Self {
+        Self {
+            id,
+            ..Default::default()
+        }
+    }
+}
+
+impl<V: View + Clone, S: StyleTuple> CheckboxProps<V, S> {
+    pub fn label<V2: View + Clone>(self, label: V2) -> CheckboxProps<V2, S> {
+        CheckboxProps {
+            id: self.id,
+            checked: self.checked,
+            label,
+            disabled: self.disabled,
+            style: self.style,
+        }
+    }
+
+    pub fn style<S2: StyleTuple>(self, style: S2) -> CheckboxProps<V, S2> {
+        CheckboxProps {
+            id: self.id,
+            checked: self.checked,
+            label: self.label,
+            disabled: self.disabled,
+            style,
+        }
+    }
+
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// Checkbox widget, which sends a `ValueChanged<bool>` event when toggled.
+pub fn checkbox<V: View + Clone + PartialEq + 'static, ST: StyleTuple + PartialEq + 'static>(
+    cx: Cx<CheckboxProps<V, ST>>,
+) -> impl View {
+    bevy_egret::widgets::checkbox.bind(bevy_egret::widgets::CheckboxProps {
+        id: cx.props.id,
+        checked: cx.props.checked,
+        children: Fragment::new((
+            Element::new()
+                .styled((STYLE_BOX.clone(), cx.get_scoped_value(CHECKBOX)))
+                .children(Element::new().styled(STYLE_CHECKMARK.clone())),
+            cx.props.label.clone(),
+        )),
+        style: (STYLE_CHECKBOX.clone(), cx.props.style.clone()),
+        class_names: (),
+        disabled: cx.props.disabled,
+        marker: std::marker::PhantomData,
+    })
+}
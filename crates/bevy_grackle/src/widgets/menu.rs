@@ -6,6 +6,8 @@ use bevy_egret::{
 use bevy_quill::prelude::*;
 use static_init::dynamic;
 
+pub use bevy_egret::widgets::POPUP_LAYER;
+
 use crate::{
     tokens::{BUTTON_DEFAULT, MENU_ITEM, MENU_POPUP, TYPOGRAPHY},
     Size,
@@ -1,9 +1,13 @@
 mod button;
+mod checkbox;
 mod menu;
 mod slider;
+mod spin_box;
 mod splitter;
 
 pub use button::*;
+pub use checkbox::*;
 pub use menu::*;
 pub use slider::*;
+pub use spin_box::*;
 pub use splitter::*;
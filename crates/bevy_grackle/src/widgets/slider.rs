@@ -1,6 +1,10 @@
 use std::sync::Arc;
 
-use bevy::{asset::AssetPath, ui};
+use bevy::{
+    asset::{AssetPath, LoadState},
+    prelude::Image,
+    ui,
+};
 use bevy_egret::widgets::SliderChildProps;
 use bevy_quill::prelude::*;
 use static_init::dynamic;
@@ -85,10 +89,14 @@ pub struct SliderProps<S: StyleTuple = ()> {
     pub max: f32,
     pub value: f32,
     pub style: S,
+    pub on_change: Option<CallbackHandle<f32>>,
 }
 
 // Horizontal slider widget
-pub fn h_slider<S: StyleTuple + PartialEq + 'static>(cx: Cx<SliderProps<S>>) -> impl View {
+pub fn h_slider<S: StyleTuple + PartialEq + 'static>(mut cx: Cx<SliderProps<S>>) -> impl View {
+    // Don't show the thumb shadow until the image has loaded, otherwise it renders as a square.
+    let (_, shadow_state) = cx.use_asset::<Image>("grackle://icons/disc.png");
+    let shadow_loaded = shadow_state == LoadState::Loaded;
     // Get styles from theme. These will be combined with built-in styles.
     let track_style = cx.get_scoped_value(H_SLIDER_TRACK);
     let track_active_style = cx.get_scoped_value(H_SLIDER_TRACK_ACTIVE);
@@ -102,6 +110,7 @@ pub fn h_slider<S: StyleTuple + PartialEq + 'static>(cx: Cx<SliderProps<S>>) ->
         value: cx.props.value,
         thumb_size: THUMB_SIZE,
         style: (STYLE_SLIDER.clone(), cx.props.style.clone()),
+        on_change: cx.props.on_change,
         children: Arc::new(move |spc: SliderChildProps| {
             Fragment::new((
                 Element::new().styled((STYLE_TRACK.clone(), track_style.clone())),
@@ -122,7 +131,11 @@ pub fn h_slider<S: StyleTuple + PartialEq + 'static>(cx: Cx<SliderProps<S>>) ->
                             .children((
                                 Element::new()
                                     .styled((STYLE_THUMB_FG.clone(), thumb_style.clone())),
-                                Element::new().styled(STYLE_THUMB_SHADOW.clone()),
+                                If::new(
+                                    shadow_loaded,
+                                    Element::new().styled(STYLE_THUMB_SHADOW.clone()),
+                                    (),
+                                ),
                             )),
                     ),
             ))
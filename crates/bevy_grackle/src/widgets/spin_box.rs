@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use bevy::ui;
+use bevy_egret::widgets::{SpinBoxChildProps, SpinBoxStep};
+use bevy_quill::{prelude::*, Cursor};
+use static_init::dynamic;
+
+use crate::tokens::{H_SLIDER_TRACK, H_SLIDER_TRACK_ACTIVE};
+
+const BUTTON_WIDTH: f32 = 16.;
+
+// Style definitions for spin box widget.
+
+// The spin box, containing the arrow buttons and the field.
+#[dynamic]
+static STYLE_SPIN_BOX: StyleHandle = StyleHandle::build(|ss| {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Stretch)
+        .min_width(64)
+        .height(20)
+});
+
+// Arrow buttons at either end.
+#[dynamic]
+static STYLE_BUTTON: StyleHandle = StyleHandle::build(|ss| {
+    ss.display(ui::Display::Flex)
+        .align_items(ui::AlignItems::Center)
+        .justify_content(ui::JustifyContent::Center)
+        .width(BUTTON_WIDTH)
+        .flex_shrink(0.)
+        .z_index(1)
+        .cursor(Cursor::Pointer)
+        .selector(":hover", |ss| ss.background_color("#fff2"))
+});
+
+// The field between the buttons, which shows the value and can be dragged.
+#[dynamic]
+static STYLE_FIELD: StyleHandle = StyleHandle::build(|ss| {
+    ss.position(ui::PositionType::Absolute)
+        .top(0)
+        .bottom(0)
+        .left(0)
+        .right(0)
+        .cursor(Cursor::ColResize)
+        .selector(".editing > &", |ss| ss.cursor(Cursor::Text))
+});
+
+// Fill showing the value as a proportion of the range.
+#[dynamic]
+static STYLE_FILL: StyleHandle = StyleHandle::build(|ss| {
+    ss.position(ui::PositionType::Absolute)
+        .top(0)
+        .bottom(0)
+        .left(0)
+        .pointer_events(PointerEvents::None)
+        .display(ui::Display::Flex)
+        .selector(".editing > * > &", |ss| ss.display(ui::Display::None))
+});
+
+#[dynamic]
+static STYLE_TEXT: StyleHandle = StyleHandle::build(|ss| {
+    ss.flex_grow(1.)
+        .display(ui::Display::Flex)
+        .align_items(ui::AlignItems::Center)
+        .justify_content(ui::JustifyContent::Center)
+        .pointer_events(PointerEvents::None)
+});
+
+#[derive(PartialEq, Clone)]
+pub struct SpinBoxProps<S: StyleTuple = ()> {
+    pub id: &'static str,
+    pub value: f32,
+    pub min: f32,
+    pub max: f32,
+    pub step: f32,
+    pub precision: usize,
+    pub style: S,
+}
+
+impl<S: StyleTuple + Default> Default for SpinBoxProps<S> {
+    fn default() -> Self {
+        Self {
+            id: "",
+            value: 0.,
+            min: f32::MIN,
+            max: f32::MAX,
+            step: 1.,
+            precision: 0,
+            style: S::default(),
+        }
+    }
+}
+
+/// Numeric spin box widget, which sends a `ValueChanged<f32>` event when the value is dragged,
+/// stepped with the arrow buttons or keys, or typed in.
+pub fn spin_box<S: StyleTuple + PartialEq + 'static>(cx: Cx<SpinBoxProps<S>>) -> impl View {
+    // Shares the slider tokens: the field is drawn as a track which fills up to the value.
+    let track_style = cx.get_scoped_value(H_SLIDER_TRACK);
+    let track_active_style = cx.get_scoped_value(H_SLIDER_TRACK_ACTIVE);
+    bevy_egret::widgets::spin_box.bind(bevy_egret::widgets::SpinBoxProps {
+        id: cx.props.id,
+        value: cx.props.value,
+        min: cx.props.min,
+        max: cx.props.max,
+        step: cx.props.step,
+        precision: cx.props.precision,
+        style: (STYLE_SPIN_BOX.clone(), cx.props.style.clone()),
+        children: Arc::new(move |sbc: SpinBoxChildProps| {
+            Fragment::new((
+                Element::new()
+                    .styled((STYLE_FIELD.clone(), track_style.clone()))
+                    .children(If::new(
+                        sbc.percent.is_some(),
+                        Element::new().styled((
+                            STYLE_FILL.clone(),
+                            track_active_style.clone(),
+                            StyleHandle::build(|s| {
+                                s.width(ui::Val::Percent(sbc.percent.unwrap_or(0.)))
+                            }),
+                        )),
+                        (),
+                    )),
+                Element::new()
+                    .styled(STYLE_BUTTON.clone())
+                    .insert(SpinBoxStep(-1.))
+                    .children("-"),
+                Element::new().styled(STYLE_TEXT.clone()).children(
+                    // Show a caret at the end of the text while editing.
+                    if sbc.editing {
+                        format!("{}|", sbc.text)
+                    } else {
+                        sbc.text
+                    },
+                ),
+                Element::new()
+                    .styled(STYLE_BUTTON.clone())
+                    .insert(SpinBoxStep(1.))
+                    .children("+"),
+            ))
+        }),
+    })
+}
@@ -1,5 +1,5 @@
 use bevy::ui;
-use bevy_quill::prelude::*;
+use bevy_quill::{prelude::*, Cursor};
 use static_init::dynamic;
 
 use crate::tokens::{SPLITTER, SPLITTER_INNER};
@@ -15,6 +15,7 @@ static STYLE_VSPLITTER: StyleHandle = StyleHandle::build(|ss| {
         .flex_direction(ui::FlexDirection::Column)
         .gap(8)
         .width(9)
+        .cursor(Cursor::ColResize)
 });
 
 // The decorative handle inside the splitter.
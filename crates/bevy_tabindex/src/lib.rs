@@ -1,15 +1,30 @@
 use bevy::{
+    a11y::{AccessibilityNode, Focus},
+    app::{App, Plugin, Update},
     ecs::{
+        change_detection::DetectChanges,
         component::Component,
-        entity::Entity,
-        query::{With, Without},
-        system::{Query, SystemParam},
+        entity::{Entities, Entity},
+        event::{Event, EventWriter},
+        query::{Added, Changed, Has, Or, With, Without},
+        removal_detection::RemovedComponents,
+        schedule::IntoSystemConfigs,
+        system::{Commands, Local, Query, Res, ResMut, Resource, SystemParam},
     },
-    hierarchy::{Children, Parent},
+    hierarchy::{Children, HierarchyQueryExt, Parent},
+    input::{keyboard::KeyCode, ButtonInput},
     log::*,
-    ui::Node,
+    math::{primitives::Direction2d, Vec2},
+    render::view::Visibility,
+    transform::components::GlobalTransform,
+    ui::{Display, Node, Style},
+    utils::{HashMap, HashSet},
 };
 
+/// How much distance perpendicular to the direction of travel counts against a candidate in
+/// [`TabNavigation::navigate_direction`], relative to distance along it.
+const PERPENDICULAR_PENALTY: f32 = 2.;
+
 /// A component which indicates that an entity wants to participate in tab navigation.
 ///
 /// The rules of tabbing are derived from the HTML specification, and are as follows:
@@ -25,8 +40,52 @@ use bevy::{
 #[derive(Debug, Default, Component, Copy, Clone)]
 pub struct TabIndex(pub i32);
 
-/// A component used to mark a tree of entities as containing tabbable elements.
+/// A marker component which excludes an entity, and all of its descendants, from sequential
+/// navigation, without removing its [`TabIndex`]. This is typically added to widgets which
+/// have been disabled.
+#[derive(Debug, Default, Component, Copy, Clone)]
+pub struct DisabledTabIndex;
+
+/// A marker component which hides an entity, and all of its descendants, from assistive
+/// technology, for content which is decorative or off-screen. Such entities are also excluded
+/// from keyboard navigation, even if they are visible. An [`AccessibilityNode`] which is
+/// marked as hidden has the same effect.
+#[derive(Debug, Default, Component, Copy, Clone)]
+pub struct AriaHidden;
+
+/// A marker component which is added to the entity that currently has keyboard focus, as
+/// determined by the [`Focus`] resource.
+#[derive(Debug, Default, Component, Copy, Clone)]
+pub struct Focused;
+
+/// A component for composite widgets such as menus and toolbars, where only one member can be
+/// reached with Tab, and the arrow keys move the focus between members. The members are the
+/// descendants of this entity which have a [`TabIndex`], not including those in nested groups.
+///
+/// [`TabNavigationPlugin`] sets `TabIndex(0)` on the active member and `TabIndex(-1)` on the
+/// others, and makes a member active when it receives focus.
+#[derive(Debug, Default, Component, Copy, Clone)]
+pub struct RovingTabGroup {
+    /// The member which can be reached with Tab. If `None`, the first member is used.
+    pub active: Option<Entity>,
+}
+
+/// A marker component which requests that an entity receive keyboard focus when it is spawned,
+/// for example the first field of a dialog. The entity must also have a [`TabIndex`].
 #[derive(Debug, Default, Component, Copy, Clone)]
+pub struct AutoFocus;
+
+/// An event which is sent whenever the keyboard focus moves from one entity to another.
+#[derive(Debug, Event, Clone, Copy, PartialEq, Eq)]
+pub struct FocusChanged {
+    /// The entity which previously had focus, if any.
+    pub prev: Option<Entity>,
+    /// The entity which now has focus, if any.
+    pub next: Option<Entity>,
+}
+
+/// A component used to mark a tree of entities as containing tabbable elements.
+#[derive(Debug, Component, Copy, Clone)]
 pub struct TabGroup {
     /// The order of the tab group relative to other tab groups.
     pub order: i32,
@@ -36,13 +95,102 @@ pub struct TabGroup {
     /// of this group. If false, then tabbing within the group will cycle through all non-modal
     /// tab groups.
     pub modal: bool,
+
+    /// Whether pressing Escape while the focus is within this group sends a
+    /// [`CloseRequested`] event for it. This only applies to modal groups.
+    pub close_on_escape: bool,
+
+    /// Whether tabbing past the last focusable entity (or before the first) wraps around to
+    /// the other end. If false, the focus stops at the ends. For non-modal groups, this is
+    /// taken from the group which contains the focus.
+    pub wrap: bool,
+}
+
+impl Default for TabGroup {
+    fn default() -> Self {
+        Self {
+            order: 0,
+            modal: false,
+            close_on_escape: false,
+            wrap: true,
+        }
+    }
+}
+
+/// An event which is sent when Escape is pressed while the focus is within a modal [`TabGroup`]
+/// which has `close_on_escape` set. The owner of the group, such as a dialog or popup menu,
+/// can respond by closing it.
+#[derive(Debug, Event, Clone, Copy, PartialEq, Eq)]
+pub struct CloseRequested {
+    /// The tab group entity.
+    pub group: Entity,
 }
 
+/// A resource which caches the focusable entities in tab order, so that they don't need to be
+/// gathered again each time the focus moves. It is kept up to date by [`TabNavigationPlugin`];
+/// if the resource is not present, [`TabNavigation`] gathers the entities on every call instead.
+#[derive(Debug, Default, Resource)]
+pub struct FocusableEntities {
+    /// The focusable entities in all non-modal tab groups.
+    pub entities: Vec<Entity>,
+    /// The focusable entities within each modal tab group.
+    pub modal: HashMap<Entity, Vec<Entity>>,
+}
+
+/// Records, for each modal tab group which has received focus, the entity which was focused
+/// before it, so that focus can be restored when the group is removed.
+#[derive(Debug, Default, Resource)]
+struct ModalFocusHistory(Vec<(Entity, Option<Entity>)>);
+
 /// An injectable object that provides tab navigation functionality.
 #[doc(hidden)]
 #[derive(SystemParam)]
-#[allow(clippy::type_complexity)]
 pub struct TabNavigation<'w, 's> {
+    queries: TabQueries<'w, 's>,
+    // Cached focus order, if available.
+    cache: Option<Res<'w, FocusableEntities>>,
+}
+
+/// An injectable object for moving the keyboard focus from code, for example to give the first
+/// field of a dialog the focus when the dialog opens.
+#[derive(SystemParam)]
+pub struct FocusManager<'w, 's> {
+    queries: TabQueries<'w, 's>,
+    focus: ResMut<'w, Focus>,
+}
+
+impl FocusManager<'_, '_> {
+    /// Returns the entity which currently has keyboard focus, if any.
+    pub fn focused(&self) -> Option<Entity> {
+        self.focus.0
+    }
+
+    /// Give `entity` keyboard focus. The entity must have a [`TabIndex`], which may be negative,
+    /// and neither it nor any of its ancestors can be hidden or disabled. If the entity can't be
+    /// focused, a warning is logged, the focus is left unchanged, and this returns false.
+    pub fn set_focus(&mut self, entity: Entity) -> bool {
+        if self.queries.focusable_index(entity).is_none() {
+            warn!("Entity {:?} can't be focused", entity);
+            return false;
+        }
+        if self.focus.0 != Some(entity) {
+            self.focus.0 = Some(entity);
+        }
+        true
+    }
+
+    /// Remove keyboard focus from whichever entity has it.
+    pub fn clear_focus(&mut self) {
+        if self.focus.0.is_some() {
+            self.focus.0 = None;
+        }
+    }
+}
+
+/// The queries used to gather focusable entities.
+#[derive(SystemParam)]
+#[allow(clippy::type_complexity)]
+struct TabQueries<'w, 's> {
     // Query for tab groups.
     tabgroup: Query<'w, 's, (Entity, &'static TabGroup, &'static Children)>,
     // Query for tab indices.
@@ -54,6 +202,18 @@ pub struct TabNavigation<'w, 's> {
     >,
     // Query for parents.
     parent: Query<'w, 's, &'static Parent, With<Node>>,
+    // Query for disabled entities.
+    disabled: Query<'w, 's, (), With<DisabledTabIndex>>,
+    // Query for display and visibility, used to skip hidden subtrees.
+    visibility: Query<'w, 's, (Option<&'static Style>, Option<&'static Visibility>), With<Node>>,
+    // Query for entities hidden from assistive technology, whose subtrees are also skipped.
+    aria_hidden: Query<'w, 's, (Has<AriaHidden>, Option<&'static AccessibilityNode>)>,
+    // Query for node positions, used for directional navigation.
+    transform: Query<'w, 's, &'static GlobalTransform, With<Node>>,
+    // Query for children, used to find the position of an entity in the hierarchy.
+    children: Query<'w, 's, &'static Children, With<Node>>,
+    // Query for roving tab groups.
+    roving: Query<'w, 's, (), With<RovingTabGroup>>,
 }
 
 impl TabNavigation<'_, '_> {
@@ -65,13 +225,99 @@ impl TabNavigation<'_, '_> {
     /// * `reverse`: Whether to navigate in reverse order.
     pub fn navigate(&self, focus: Option<Entity>, reverse: bool) -> Option<Entity> {
         // If there are no tab groups, then there are no focusable entities.
-        if self.tabgroup.is_empty() {
+        if self.queries.tabgroup.is_empty() {
             warn!("No tab groups found");
             return None;
         }
 
-        // Start by identifying which tab group we are in. Mainly what we want to know is if
-        // we're in a modal group.
+        let tabgroup = self.queries.find_group(focus);
+        let gathered: Vec<Entity>;
+        let focusable: &[Entity] = match self.cache {
+            Some(ref cache) => match tabgroup {
+                Some((tg_entity, tg)) if tg.modal => {
+                    cache.modal.get(&tg_entity).map_or(&[], |e| e.as_slice())
+                }
+                _ => &cache.entities,
+            },
+            None => {
+                gathered = self.queries.gather_ordered(tabgroup);
+                &gathered
+            }
+        };
+
+        if focusable.is_empty() {
+            warn!("No focusable entities found");
+            return None;
+        }
+
+        let index = focusable.iter().position(|e| Some(*e) == focus);
+        let count = focusable.len();
+        let wrap = tabgroup.is_none_or(|(_, tg)| tg.wrap);
+        let next = match (index, reverse) {
+            (Some(idx), false) if idx + 1 == count && !wrap => return None,
+            (Some(0), true) if !wrap => return None,
+            (Some(idx), false) => (idx + 1).rem_euclid(count),
+            (Some(idx), true) => (idx + count - 1).rem_euclid(count),
+            (None, false) => 0,
+            (None, true) => count - 1,
+        };
+        focusable.get(next).copied()
+    }
+
+    /// Returns the first focusable entity in the global tab order, taking into account the
+    /// order of tab groups. Returns `None` if there are no focusable entities.
+    pub fn first(&self) -> Option<Entity> {
+        self.navigate(None, false)
+    }
+
+    /// Returns the last focusable entity in the global tab order, taking into account the
+    /// order of tab groups. Returns `None` if there are no focusable entities.
+    pub fn last(&self) -> Option<Entity> {
+        self.navigate(None, true)
+    }
+
+    /// Navigate to the nearest focusable entity in the given direction from `focus`, using
+    /// the centers of the entities' layout rectangles. Directions are in UI coordinates, so
+    /// positive y points down the screen. Returns `None` if there is nothing in that direction.
+    ///
+    /// Candidates are scored by their distance along `direction`, plus a penalty for their
+    /// distance perpendicular to it, so that entities which are in line with the focus are
+    /// preferred over ones which are closer but off to the side. Entities more than 45 degrees
+    /// away from `direction` are not considered.
+    pub fn navigate_direction(&self, focus: Entity, direction: Direction2d) -> Option<Entity> {
+        let origin = self.queries.center(focus)?;
+        let direction = *direction;
+        // Within a roving tab group, navigate between the members of the group, even though
+        // they can't be reached by Tab.
+        let candidates = match self.queries.roving_group(focus) {
+            Some(group) => self.queries.roving_members(group),
+            None => self
+                .queries
+                .gather_candidates(self.queries.find_group(Some(focus))),
+        };
+        candidates
+            .into_iter()
+            .filter(|(entity, _)| *entity != focus)
+            .filter_map(|(entity, _)| {
+                let offset = self.queries.center(entity)? - origin;
+                let distance = offset.dot(direction);
+                let perpendicular = offset.perp_dot(direction).abs();
+                // Only consider entities within 45 degrees of the direction of travel.
+                if distance <= f32::EPSILON || perpendicular > distance {
+                    return None;
+                }
+                let score = distance + perpendicular * PERPENDICULAR_PENALTY;
+                Some((entity, score))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(entity, _)| entity)
+    }
+}
+
+impl TabQueries<'_, '_> {
+    /// Find the outermost tab group which contains `focus`. Mainly what we want to know is if
+    /// we're in a modal group.
+    fn find_group(&self, focus: Option<Entity>) -> Option<(Entity, &TabGroup)> {
         let mut tabgroup: Option<(Entity, &TabGroup)> = None;
         let mut entity = focus;
         while let Some(ent) = entity {
@@ -81,14 +327,27 @@ impl TabNavigation<'_, '_> {
             // Search up
             entity = self.parent.get(ent).ok().map(|parent| parent.get());
         }
+        tabgroup
+    }
 
+    /// Gather the focusable entities which are reachable from within `tabgroup`: either the
+    /// members of the group, if it is modal, or the members of all non-modal groups.
+    fn gather_candidates(&self, tabgroup: Option<(Entity, &TabGroup)>) -> Vec<(Entity, TabIndex)> {
         // List of all focusable entities found.
         let mut focusable: Vec<(Entity, TabIndex)> = Vec::with_capacity(self.tabindex.iter().len());
 
         match tabgroup {
             Some((tg_entity, tg)) if tg.modal => {
                 // We're in a modal tab group, then gather all tab indices in that group.
-                self.gather_focusable(&mut focusable, tg_entity);
+                // `gather_focusable` doesn't descend into modal groups, so start from their
+                // children.
+                if let Ok((_, _, children)) = self.tabgroup.get(tg_entity) {
+                    if !self.is_hidden(tg_entity) {
+                        for child in children.iter() {
+                            self.gather_focusable(&mut focusable, *child);
+                        }
+                    }
+                }
             }
             _ => {
                 // Otherwise, gather all tab indices in all non-modal tab groups.
@@ -98,8 +357,9 @@ impl TabNavigation<'_, '_> {
                     .filter(|(_, tg, _)| !tg.modal)
                     .map(|(e, tg, _)| (e, *tg))
                     .collect();
-                // Stable sort by group order
-                tab_groups.sort_by(compare_tab_groups);
+                // Sort by group order, then by position in the hierarchy, since the query
+                // order is arbitrary.
+                tab_groups.sort_by_cached_key(|(e, tg)| (tg.order, self.hierarchy_path(*e)));
 
                 // Search group descendants
                 tab_groups.iter().for_each(|(tg_entity, _)| {
@@ -108,26 +368,91 @@ impl TabNavigation<'_, '_> {
             }
         }
 
-        if focusable.is_empty() {
-            warn!("No focusable entities found");
-            return None;
+        focusable
+    }
+
+    /// Gather the focusable entities which are reachable from within `tabgroup`, in tab order.
+    fn gather_ordered(&self, tabgroup: Option<(Entity, &TabGroup)>) -> Vec<Entity> {
+        // Entities are gathered depth-first, so the order they were gathered in is their
+        // order in the hierarchy. Sort by tab index, then by hierarchy order.
+        let mut focusable: Vec<(usize, (Entity, TabIndex))> = self
+            .gather_candidates(tabgroup)
+            .into_iter()
+            .enumerate()
+            .collect();
+        focusable.sort_by_key(|(hierarchy_index, (_, tabindex))| (tabindex.0, *hierarchy_index));
+        focusable.into_iter().map(|(_, (e, _))| e).collect()
+    }
+
+    /// Returns a key which orders entities depth-first by their position in the hierarchy:
+    /// the index of each ancestor among its siblings, starting from the root. Roots are
+    /// ordered by entity id.
+    fn hierarchy_path(&self, entity: Entity) -> Vec<u64> {
+        let mut path = Vec::new();
+        let mut child = entity;
+        while let Ok(parent) = self.parent.get(child) {
+            let index = self
+                .children
+                .get(parent.get())
+                .ok()
+                .and_then(|children| children.iter().position(|c| *c == child))
+                .unwrap_or_default();
+            path.push(index as u64);
+            child = parent.get();
         }
+        path.push(child.to_bits());
+        path.reverse();
+        path
+    }
 
-        // Stable sort by tabindex
-        focusable.sort_by(compare_tab_indices);
+    /// Returns the nearest ancestor of `entity` which is a [`RovingTabGroup`].
+    fn roving_group(&self, entity: Entity) -> Option<Entity> {
+        self.parent
+            .iter_ancestors(entity)
+            .find(|ancestor| self.roving.contains(*ancestor))
+    }
 
-        let index = focusable.iter().position(|e| Some(e.0) == focus);
-        let count = focusable.len();
-        let next = match (index, reverse) {
-            (Some(idx), false) => (idx + 1).rem_euclid(count),
-            (Some(idx), true) => (idx + count - 1).rem_euclid(count),
-            (None, false) => 0,
-            (None, true) => count - 1,
+    /// Returns the members of a [`RovingTabGroup`] which can be focused, in hierarchy order.
+    fn roving_members(&self, group: Entity) -> Vec<(Entity, TabIndex)> {
+        let mut members = Vec::new();
+        self.gather_roving_members(&mut members, group);
+        members
+    }
+
+    fn gather_roving_members(&self, out: &mut Vec<(Entity, TabIndex)>, parent: Entity) {
+        let Ok(children) = self.children.get(parent) else {
+            return;
         };
-        focusable.get(next).map(|(e, _)| e).copied()
+        for child in children.iter().copied() {
+            // Hidden or disabled entities, and all of their descendants, can't be focused.
+            // Nested groups have their own members.
+            if self.is_hidden(child)
+                || self.disabled.contains(child)
+                || self.roving.contains(child)
+                || self.tabgroup.contains(child)
+            {
+                continue;
+            }
+            match self.tabindex.get(child) {
+                Ok((_, Some(tabindex), _)) => out.push((child, *tabindex)),
+                _ => self.gather_roving_members(out, child),
+            }
+        }
+    }
+
+    /// Returns the center of the entity's layout rectangle, in UI coordinates.
+    fn center(&self, entity: Entity) -> Option<Vec2> {
+        self.transform
+            .get(entity)
+            .ok()
+            .map(|transform| transform.translation().truncate())
     }
 
     fn gather_focusable(&self, out: &mut Vec<(Entity, TabIndex)>, parent: Entity) {
+        // Hidden or disabled entities, and all of their descendants, can't be focused.
+        if self.is_hidden(parent) || self.disabled.contains(parent) {
+            return;
+        }
         if let Ok((entity, tabindex, children)) = self.tabindex.get(parent) {
             if let Some(tabindex) = tabindex {
                 if tabindex.0 >= 0 {
@@ -150,13 +475,1084 @@ impl TabNavigation<'_, '_> {
             }
         }
     }
+
+    /// Returns the tab index of the entity, if it can currently be focused: that is, it has a
+    /// [`TabIndex`], and neither it nor any of its ancestors are hidden or disabled.
+    fn focusable_index(&self, entity: Entity) -> Option<TabIndex> {
+        let tabindex = *self.tabindex.get(entity).ok()?.1?;
+        let mut ancestor = Some(entity);
+        while let Some(ent) = ancestor {
+            if self.is_hidden(ent) || self.disabled.contains(ent) {
+                return None;
+            }
+            ancestor = self.parent.get(ent).ok().map(|parent| parent.get());
+        }
+        Some(tabindex)
+    }
+
+    /// Returns true if the entity is not displayed, is explicitly hidden, or is hidden from
+    /// assistive technology.
+    fn is_hidden(&self, entity: Entity) -> bool {
+        let hidden = match self.visibility.get(entity) {
+            Ok((style, visibility)) => {
+                style.is_some_and(|style| style.display == Display::None)
+                    || visibility.is_some_and(|visibility| *visibility == Visibility::Hidden)
+            }
+            Err(_) => false,
+        };
+        hidden
+            || self
+                .aria_hidden
+                .get(entity)
+                .is_ok_and(|(aria_hidden, node)| aria_hidden || node.is_some_and(|n| n.is_hidden()))
+    }
+}
+
+/// Plugin which moves the keyboard focus when Tab or Shift-Tab is pressed, and keeps the
+/// [`Focused`] marker in sync with the [`Focus`] resource.
+pub struct TabNavigationPlugin;
+
+impl Plugin for TabNavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Focus>()
+            .init_resource::<FocusableEntities>()
+            .init_resource::<ModalFocusHistory>()
+            .add_event::<FocusChanged>()
+            .add_event::<CloseRequested>()
+            .add_systems(
+                Update,
+                (
+                    // Roving tab indices need to be updated before the focus order is cached.
+                    update_roving_tab_groups,
+                    update_focusable_entities,
+                    handle_autofocus,
+                    handle_tab,
+                    handle_roving_arrows,
+                    handle_escape,
+                    restore_modal_focus,
+                    update_focused,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// System which rebuilds [`FocusableEntities`] whenever the hierarchy, or any of the components
+/// which affect tab navigation, have changed. Styles change often, for example when elements
+/// are hovered or scrolled, so a restyle only counts if it shows or hides the element; `hidden`
+/// holds the entities which were last seen with `Display::None`.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn update_focusable_entities(
+    nav: TabQueries,
+    restyled: Query<(Entity, &Style), (With<Node>, Changed<Style>)>,
+    mut hidden: Local<HashSet<Entity>>,
+    changed: Query<
+        (),
+        (
+            With<Node>,
+            Or<(
+                Changed<TabIndex>,
+                Changed<TabGroup>,
+                Added<DisabledTabIndex>,
+                Changed<Parent>,
+                Changed<Children>,
+                Changed<Visibility>,
+                Added<AriaHidden>,
+                Changed<AccessibilityNode>,
+            )>,
+        ),
+    >,
+    mut removed_tabindex: RemovedComponents<TabIndex>,
+    mut removed_tabgroup: RemovedComponents<TabGroup>,
+    mut removed_disabled: RemovedComponents<DisabledTabIndex>,
+    mut removed_aria_hidden: RemovedComponents<AriaHidden>,
+    mut removed_parent: RemovedComponents<Parent>,
+    mut removed_children: RemovedComponents<Children>,
+    mut focusable: ResMut<FocusableEntities>,
+) {
+    // Drain all of the removal events, so that they aren't seen again next frame.
+    let removed = removed_tabindex.read().count()
+        + removed_tabgroup.read().count()
+        + removed_disabled.read().count()
+        + removed_aria_hidden.read().count()
+        + removed_parent.read().count()
+        + removed_children.read().count();
+    let mut display_changed = false;
+    for (entity, style) in restyled.iter() {
+        let changed = if style.display == Display::None {
+            hidden.insert(entity)
+        } else {
+            hidden.remove(&entity)
+        };
+        display_changed |= changed;
+    }
+    if !focusable.is_added() && removed == 0 && !display_changed && changed.is_empty() {
+        return;
+    }
+    // Forget entities which have been despawned.
+    hidden.retain(|entity| nav.visibility.contains(*entity));
+
+    let focusable = focusable.as_mut();
+    focusable.entities = nav.gather_ordered(None);
+    focusable.modal.clear();
+    for (tg_entity, tg, _) in nav.tabgroup.iter().filter(|(_, tg, _)| tg.modal) {
+        focusable
+            .modal
+            .insert(tg_entity, nav.gather_ordered(Some((tg_entity, tg))));
+    }
+}
+
+/// System which focuses newly-added [`AutoFocus`] entities. If the current focus is trapped
+/// within a modal tab group, entities outside of that group are ignored.
+fn handle_autofocus(
+    nav: TabNavigation,
+    added: Query<Entity, Added<AutoFocus>>,
+    mut focus: ResMut<Focus>,
+) {
+    if added.is_empty() {
+        return;
+    }
+    let trap = match nav.queries.find_group(focus.0) {
+        Some((tg_entity, tg)) if tg.modal => Some(tg_entity),
+        _ => None,
+    };
+    let mut candidates: Vec<(Entity, TabIndex)> = added
+        .iter()
+        .filter(|entity| {
+            trap.is_none()
+                || trap
+                    == nav
+                        .queries
+                        .find_group(Some(*entity))
+                        .map(|(tg_entity, _)| tg_entity)
+        })
+        .filter_map(|entity| Some((entity, nav.queries.focusable_index(entity)?)))
+        .collect();
+    candidates
+        .sort_by_cached_key(|(entity, tabindex)| (tabindex.0, nav.queries.hierarchy_path(*entity)));
+    if candidates.len() > 1 {
+        warn!(
+            "Multiple entities requested autofocus, focusing {:?}",
+            candidates[0].0
+        );
+    }
+    if let Some((entity, _)) = candidates.first() {
+        focus.0 = Some(*entity);
+    }
 }
 
-fn compare_tab_groups(a: &(Entity, TabGroup), b: &(Entity, TabGroup)) -> std::cmp::Ordering {
-    a.1.order.cmp(&b.1.order)
+/// System which navigates to the next (or, with Shift, previous) focusable entity when Tab is
+/// pressed. If nothing has focus yet, the first (or last) focusable entity is chosen.
+pub fn handle_tab(nav: TabNavigation, key: Res<ButtonInput<KeyCode>>, mut focus: ResMut<Focus>) {
+    if key.just_pressed(KeyCode::Tab) {
+        let next = nav.navigate(
+            focus.0,
+            key.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+        );
+        if next.is_some() {
+            focus.0 = next;
+        }
+    }
 }
 
-// Stable sort which compares by tab index
-fn compare_tab_indices(a: &(Entity, TabIndex), b: &(Entity, TabIndex)) -> std::cmp::Ordering {
-    a.1 .0.cmp(&b.1 .0)
+/// System which sends [`CloseRequested`] when Escape is pressed while the focus is within a
+/// modal tab group which has `close_on_escape` set.
+pub fn handle_escape(
+    nav: TabNavigation,
+    key: Res<ButtonInput<KeyCode>>,
+    focus: Res<Focus>,
+    mut writer: EventWriter<CloseRequested>,
+) {
+    if !key.just_pressed(KeyCode::Escape) || focus.0.is_none() {
+        return;
+    }
+    if let Some((group, tg)) = nav.queries.find_group(focus.0) {
+        if tg.modal && tg.close_on_escape {
+            writer.send(CloseRequested { group });
+        }
+    }
+}
+
+/// System which moves the focus between the members of a [`RovingTabGroup`] when an arrow key
+/// is pressed, using [`TabNavigation::navigate_direction`].
+pub fn handle_roving_arrows(
+    nav: TabNavigation,
+    key: Res<ButtonInput<KeyCode>>,
+    mut focus: ResMut<Focus>,
+) {
+    let Some(current) = focus.0 else {
+        return;
+    };
+    if nav.queries.roving_group(current).is_none() {
+        return;
+    }
+    let direction = [
+        (KeyCode::ArrowLeft, Direction2d::NEG_X),
+        (KeyCode::ArrowRight, Direction2d::X),
+        (KeyCode::ArrowUp, Direction2d::NEG_Y),
+        (KeyCode::ArrowDown, Direction2d::Y),
+    ]
+    .into_iter()
+    .find(|(key_code, _)| key.just_pressed(*key_code));
+    if let Some((_, direction)) = direction {
+        if let Some(next) = nav.navigate_direction(current, direction) {
+            focus.0 = Some(next);
+        }
+    }
+}
+
+/// System which makes the focused member of a [`RovingTabGroup`] the active one, and updates
+/// the tab indices of the members so that only the active member can be reached with Tab.
+fn update_roving_tab_groups(
+    mut commands: Commands,
+    nav: TabNavigation,
+    focus: Res<Focus>,
+    mut groups: Query<(Entity, &mut RovingTabGroup)>,
+) {
+    for (group, mut roving) in groups.iter_mut() {
+        let members = nav.queries.roving_members(group);
+        let is_member = |entity: Option<Entity>| members.iter().any(|(e, _)| Some(*e) == entity);
+        let active = if is_member(focus.0) {
+            focus.0
+        } else if is_member(roving.active) {
+            roving.active
+        } else {
+            members.first().map(|(e, _)| *e)
+        };
+        if roving.active != active {
+            roving.active = active;
+        }
+        for (entity, tabindex) in members.iter() {
+            let index = if Some(*entity) == active { 0 } else { -1 };
+            if tabindex.0 != index {
+                commands.entity(*entity).insert(TabIndex(index));
+            }
+        }
+    }
+}
+
+/// System which remembers which entity was focused before focus entered a modal tab group,
+/// and restores it when that group is removed, unless focus has since moved elsewhere.
+fn restore_modal_focus(
+    nav: TabNavigation,
+    entities: &Entities,
+    mut removed: RemovedComponents<TabGroup>,
+    mut history: ResMut<ModalFocusHistory>,
+    mut focus: ResMut<Focus>,
+    mut prev_focus: Local<Option<Entity>>,
+) {
+    for group in removed.read() {
+        if let Some(index) = history.0.iter().position(|(tg, _)| *tg == group) {
+            let (_, restore) = history.0.remove(index);
+            if focus.0.is_none_or(|entity| !entities.contains(entity)) {
+                focus.0 = restore.filter(|entity| entities.contains(*entity));
+            }
+        }
+    }
+
+    if focus.0 != *prev_focus {
+        if let Some((tg_entity, tg)) = nav.queries.find_group(focus.0) {
+            if tg.modal && !history.0.iter().any(|(tg, _)| *tg == tg_entity) {
+                history.0.push((tg_entity, *prev_focus));
+            }
+        }
+        *prev_focus = focus.0;
+    }
+}
+
+/// System which moves the [`Focused`] marker to the entity in the [`Focus`] resource whenever
+/// it changes, and sends a [`FocusChanged`] event.
+fn update_focused(
+    mut commands: Commands,
+    focus: Res<Focus>,
+    focused: Query<Entity, With<Focused>>,
+    mut writer: EventWriter<FocusChanged>,
+    mut prev_focus: Local<Option<Entity>>,
+) {
+    if !focus.is_changed() {
+        return;
+    }
+    if focus.0 != *prev_focus {
+        writer.send(FocusChanged {
+            prev: *prev_focus,
+            next: focus.0,
+        });
+        *prev_focus = focus.0;
+    }
+    for entity in focused.iter() {
+        if Some(entity) != focus.0 {
+            commands.entity(entity).remove::<Focused>();
+        }
+    }
+    if let Some(entity) = focus.0 {
+        if !focused.contains(entity) {
+            if let Some(mut entt) = commands.get_entity(entity) {
+                entt.insert(Focused);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        a11y::accesskit::{NodeBuilder, Role},
+        app::App,
+        ecs::{event::Events, system::SystemState, world::World},
+        hierarchy::{BuildWorldChildren, DespawnRecursiveExt},
+        ui::node_bundles::NodeBundle,
+    };
+
+    use super::*;
+
+    /// An app with the tab navigation plugin.
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .add_plugins(TabNavigationPlugin);
+        app
+    }
+
+    /// Spawn a focusable UI node with the given tab index.
+    fn focusable(world: &mut World, tabindex: i32) -> Entity {
+        world
+            .spawn((NodeBundle::default(), TabIndex(tabindex)))
+            .id()
+    }
+
+    #[test]
+    fn test_skip_hidden() {
+        let mut world = World::new();
+        let first = focusable(&mut world, 0);
+        let hidden_child = focusable(&mut world, 0);
+        let invisible = focusable(&mut world, 0);
+        world.entity_mut(invisible).insert(Visibility::Hidden);
+        let last = focusable(&mut world, 0);
+        let collapsed = world
+            .spawn(NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .push_children(&[hidden_child])
+            .id();
+        world
+            .spawn((NodeBundle::default(), TabGroup::default()))
+            .push_children(&[first, collapsed, invisible, last]);
+
+        let mut state: SystemState<TabNavigation> = SystemState::new(&mut world);
+        let nav = state.get(&world);
+        assert_eq!(nav.navigate(None, false), Some(first));
+        assert_eq!(nav.navigate(Some(first), false), Some(last));
+        assert_eq!(nav.navigate(Some(last), false), Some(first));
+        assert_eq!(nav.navigate(None, true), Some(last));
+    }
+
+    #[test]
+    fn test_skip_disabled() {
+        let mut world = World::new();
+        let enabled = focusable(&mut world, 0);
+        let disabled = world
+            .spawn((NodeBundle::default(), TabIndex(0), DisabledTabIndex))
+            .id();
+        world
+            .spawn((NodeBundle::default(), TabGroup::default()))
+            .push_children(&[disabled, enabled]);
+
+        let mut state: SystemState<TabNavigation> = SystemState::new(&mut world);
+        let nav = state.get(&world);
+        assert_eq!(nav.navigate(None, false), Some(enabled));
+        assert_eq!(nav.navigate(Some(enabled), false), Some(enabled));
+        assert_eq!(nav.navigate(Some(enabled), true), Some(enabled));
+    }
+
+    #[test]
+    fn test_skip_aria_hidden() {
+        let mut world = World::new();
+        // A row of entities 10 units apart, so that directional navigation can be tested too.
+        let spawn = |world: &mut World, x: f32| {
+            world
+                .spawn((NodeBundle::default(), TabIndex(0)))
+                .insert(GlobalTransform::from_xyz(x, 0., 0.))
+                .id()
+        };
+        let first = spawn(&mut world, 0.);
+        let decorative_child = spawn(&mut world, 10.);
+        let offscreen = spawn(&mut world, 20.);
+        let mut node = NodeBuilder::new(Role::Button);
+        node.set_hidden();
+        world
+            .entity_mut(offscreen)
+            .insert(AccessibilityNode::from(node));
+        let last = spawn(&mut world, 30.);
+        let decorative = world
+            .spawn((NodeBundle::default(), AriaHidden))
+            .push_children(&[decorative_child])
+            .id();
+        world
+            .spawn((NodeBundle::default(), TabGroup::default()))
+            .push_children(&[first, decorative, offscreen, last]);
+
+        let mut state: SystemState<TabNavigation> = SystemState::new(&mut world);
+        let nav = state.get(&world);
+        assert_eq!(nav.navigate(None, false), Some(first));
+        assert_eq!(nav.navigate(Some(first), false), Some(last));
+        assert_eq!(nav.navigate(Some(last), true), Some(first));
+        assert_eq!(nav.navigate_direction(first, Direction2d::X), Some(last));
+    }
+
+    #[test]
+    fn test_focus_manager() {
+        let mut world = World::new();
+        world.init_resource::<Focus>();
+        let button = focusable(&mut world, 0);
+        let programmatic = focusable(&mut world, -1);
+        let disabled = world
+            .spawn((NodeBundle::default(), TabIndex(0), DisabledTabIndex))
+            .id();
+        let label = world.spawn(NodeBundle::default()).id();
+        world
+            .spawn((NodeBundle::default(), TabGroup::default()))
+            .push_children(&[button, programmatic, disabled, label]);
+
+        let mut state: SystemState<FocusManager> = SystemState::new(&mut world);
+        let mut focus = state.get_mut(&mut world);
+        assert!(focus.set_focus(button));
+        assert_eq!(focus.focused(), Some(button));
+
+        // Entities which can't be reached with Tab can still be focused directly.
+        assert!(focus.set_focus(programmatic));
+        assert_eq!(focus.focused(), Some(programmatic));
+
+        // Entities which can't be focused leave the focus where it was.
+        assert!(!focus.set_focus(disabled));
+        assert!(!focus.set_focus(label));
+        assert_eq!(focus.focused(), Some(programmatic));
+
+        focus.clear_focus();
+        assert_eq!(focus.focused(), None);
+        state.apply(&mut world);
+        assert_eq!(world.resource::<Focus>().0, None);
+    }
+
+    #[test]
+    fn test_wrap() {
+        let mut world = World::new();
+        let spawn_group = |world: &mut World, tabgroup: TabGroup| {
+            let fields: Vec<Entity> = (0..2).map(|_| focusable(world, 0)).collect();
+            world
+                .spawn((NodeBundle::default(), tabgroup))
+                .push_children(&fields);
+            fields
+        };
+        let stop = TabGroup {
+            wrap: false,
+            ..Default::default()
+        };
+        let group = spawn_group(&mut world, stop);
+        let dialog = spawn_group(
+            &mut world,
+            TabGroup {
+                modal: true,
+                ..Default::default()
+            },
+        );
+        let stop_dialog = spawn_group(
+            &mut world,
+            TabGroup {
+                modal: true,
+                ..stop
+            },
+        );
+
+        let mut state: SystemState<TabNavigation> = SystemState::new(&mut world);
+        let nav = state.get(&world);
+        // A group which doesn't wrap stops at the ends.
+        assert_eq!(nav.navigate(Some(group[0]), false), Some(group[1]));
+        assert_eq!(nav.navigate(Some(group[1]), false), None);
+        assert_eq!(nav.navigate(Some(group[0]), true), None);
+        assert_eq!(nav.navigate(None, false), Some(group[0]));
+
+        // Modal groups choose for themselves.
+        assert_eq!(nav.navigate(Some(dialog[1]), false), Some(dialog[0]));
+        assert_eq!(nav.navigate(Some(dialog[0]), true), Some(dialog[1]));
+        assert_eq!(
+            nav.navigate(Some(stop_dialog[0]), false),
+            Some(stop_dialog[1])
+        );
+        assert_eq!(nav.navigate(Some(stop_dialog[1]), false), None);
+    }
+
+    #[test]
+    fn test_tab_focus() {
+        let mut app = app();
+        let items: Vec<Entity> = (0..3).map(|i| focusable(&mut app.world, i)).collect();
+        app.world
+            .spawn((NodeBundle::default(), TabGroup::default()))
+            .push_children(&items);
+
+        let press = |app: &mut App, shift: bool| {
+            let mut input = app.world.resource_mut::<ButtonInput<KeyCode>>();
+            if shift {
+                input.press(KeyCode::ShiftLeft);
+            }
+            input.press(KeyCode::Tab);
+            app.update();
+            let mut input = app.world.resource_mut::<ButtonInput<KeyCode>>();
+            input.release_all();
+            input.clear();
+            app.world.resource::<Focus>().0
+        };
+        let focused = |app: &mut App| {
+            app.world
+                .query_filtered::<Entity, With<Focused>>()
+                .iter(&app.world)
+                .collect::<Vec<_>>()
+        };
+
+        // Starting with no focus, Tab focuses the first entity.
+        app.update();
+        assert_eq!(app.world.resource::<Focus>().0, None);
+        assert_eq!(press(&mut app, false), Some(items[0]));
+        assert_eq!(focused(&mut app), vec![items[0]]);
+        assert_eq!(press(&mut app, false), Some(items[1]));
+        assert_eq!(focused(&mut app), vec![items[1]]);
+        assert_eq!(press(&mut app, false), Some(items[2]));
+        assert_eq!(press(&mut app, false), Some(items[0]));
+        assert_eq!(press(&mut app, true), Some(items[2]));
+        assert_eq!(focused(&mut app), vec![items[2]]);
+
+        // Shift-Tab with no focus starts from the end.
+        app.world.resource_mut::<Focus>().0 = None;
+        app.update();
+        assert!(focused(&mut app).is_empty());
+        assert_eq!(press(&mut app, true), Some(items[2]));
+    }
+
+    #[test]
+    fn test_focusable_entities() {
+        let mut app = app();
+        let a = focusable(&mut app.world, 1);
+        let b = focusable(&mut app.world, 0);
+        let c = focusable(&mut app.world, 0);
+        let d = focusable(&mut app.world, 2);
+        let unfocusable = focusable(&mut app.world, -1);
+        app.world
+            .spawn((
+                NodeBundle::default(),
+                TabGroup {
+                    order: 1,
+                    modal: false,
+                    ..Default::default()
+                },
+            ))
+            .push_children(&[a, b]);
+        app.world
+            .spawn((
+                NodeBundle::default(),
+                TabGroup {
+                    order: 0,
+                    modal: false,
+                    ..Default::default()
+                },
+            ))
+            .push_children(&[c, d, unfocusable]);
+
+        // Walk the focus order without the cache, starting from no focus.
+        let walk = |app: &mut App| {
+            let cache = app.world.remove_resource::<FocusableEntities>().unwrap();
+            let mut state: SystemState<TabNavigation> = SystemState::new(&mut app.world);
+            let nav = state.get(&app.world);
+            let mut order = Vec::new();
+            let mut focus = nav.navigate(None, false);
+            while let Some(entity) = focus {
+                if order.contains(&entity) {
+                    break;
+                }
+                order.push(entity);
+                focus = nav.navigate(focus, false);
+            }
+            app.world.insert_resource(cache);
+            order
+        };
+
+        app.update();
+        let expected = walk(&mut app);
+        assert_eq!(expected, vec![c, b, a, d]);
+        assert_eq!(app.world.resource::<FocusableEntities>().entities, expected);
+
+        // Changing a tab index rebuilds the cache.
+        app.world.entity_mut(d).insert(TabIndex(0));
+        app.update();
+        let expected = walk(&mut app);
+        assert_eq!(expected, vec![c, d, b, a]);
+        assert_eq!(app.world.resource::<FocusableEntities>().entities, expected);
+
+        // As does removing an entity.
+        app.world.entity_mut(b).despawn_recursive();
+        app.update();
+        let expected = walk(&mut app);
+        assert_eq!(expected, vec![c, d, a]);
+        assert_eq!(app.world.resource::<FocusableEntities>().entities, expected);
+    }
+
+    #[test]
+    fn test_focusable_entities_reused() {
+        use bevy::{ecs::change_detection::DetectChangesMut, ui::Val};
+
+        let mut app = app();
+        let items: Vec<Entity> = (0..3).map(|_| focusable(&mut app.world, 0)).collect();
+        app.world
+            .spawn((NodeBundle::default(), TabGroup::default()))
+            .push_children(&items);
+        app.update();
+        assert_eq!(app.world.resource::<FocusableEntities>().entities, items);
+
+        // Reorder the cache behind the plugin's back. Since nothing in the hierarchy has changed,
+        // the cache isn't rebuilt, and navigation follows the cached order.
+        let mut reversed = items.clone();
+        reversed.reverse();
+        app.world
+            .resource_mut::<FocusableEntities>()
+            .bypass_change_detection()
+            .entities
+            .clone_from(&reversed);
+        app.update();
+        assert!(!app.world.is_resource_changed::<FocusableEntities>());
+        let mut state: SystemState<TabNavigation> = SystemState::new(&mut app.world);
+        let nav = state.get(&app.world);
+        assert_eq!(nav.navigate(None, false), Some(reversed[0]));
+        assert_eq!(nav.navigate(Some(reversed[0]), false), Some(reversed[1]));
+
+        // A hierarchy change rebuilds the cache.
+        app.world.entity_mut(items[1]).insert(TabIndex(1));
+        app.update();
+        assert_eq!(
+            app.world.resource::<FocusableEntities>().entities,
+            vec![items[0], items[2], items[1]]
+        );
+
+        // Restyling an element doesn't rebuild the cache, unless it changes the display.
+        app.world.get_mut::<Style>(items[0]).unwrap().width = Val::Px(10.);
+        app.update();
+        assert!(!app.world.is_resource_changed::<FocusableEntities>());
+        app.world.get_mut::<Style>(items[0]).unwrap().display = Display::None;
+        app.update();
+        assert_eq!(
+            app.world.resource::<FocusableEntities>().entities,
+            vec![items[2], items[1]]
+        );
+        app.world.get_mut::<Style>(items[0]).unwrap().width = Val::Px(20.);
+        app.update();
+        assert!(!app.world.is_resource_changed::<FocusableEntities>());
+        app.world.get_mut::<Style>(items[0]).unwrap().display = Display::Flex;
+        app.update();
+        assert_eq!(
+            app.world.resource::<FocusableEntities>().entities,
+            vec![items[0], items[2], items[1]]
+        );
+    }
+
+    #[test]
+    fn test_autofocus() {
+        let mut app = app();
+        let button = focusable(&mut app.world, 0);
+        app.world
+            .spawn((NodeBundle::default(), TabGroup::default()))
+            .add_child(button);
+        app.world.resource_mut::<Focus>().0 = Some(button);
+        app.update();
+
+        // Opening a dialog focuses the field which requests it.
+        let name = focusable(&mut app.world, 0);
+        let email = app
+            .world
+            .spawn((NodeBundle::default(), TabIndex(0), AutoFocus))
+            .id();
+        let dialog = app
+            .world
+            .spawn((
+                NodeBundle::default(),
+                TabGroup {
+                    order: 0,
+                    modal: true,
+                    ..Default::default()
+                },
+            ))
+            .push_children(&[name, email])
+            .id();
+        app.update();
+        assert_eq!(app.world.resource::<Focus>().0, Some(email));
+
+        // Focus is trapped in the dialog, so entities outside it can't take the focus.
+        let outside = app
+            .world
+            .spawn((NodeBundle::default(), TabIndex(0), AutoFocus))
+            .id();
+        app.world.entity_mut(button).add_child(outside);
+        app.update();
+        assert_eq!(app.world.resource::<Focus>().0, Some(email));
+
+        // Disabled entities can't take the focus either.
+        let disabled = app
+            .world
+            .spawn((
+                NodeBundle::default(),
+                TabIndex(0),
+                AutoFocus,
+                DisabledTabIndex,
+            ))
+            .id();
+        app.world.entity_mut(dialog).add_child(disabled);
+        app.update();
+        assert_eq!(app.world.resource::<Focus>().0, Some(email));
+
+        // When several entities request focus, the first in tab order wins.
+        let second = app
+            .world
+            .spawn((NodeBundle::default(), TabIndex(2), AutoFocus))
+            .id();
+        let first = app
+            .world
+            .spawn((NodeBundle::default(), TabIndex(1), AutoFocus))
+            .id();
+        app.world.entity_mut(dialog).push_children(&[second, first]);
+        app.update();
+        assert_eq!(app.world.resource::<Focus>().0, Some(first));
+    }
+
+    #[test]
+    fn test_restore_modal_focus() {
+        let mut app = app();
+        let trigger = focusable(&mut app.world, 0);
+        let other = focusable(&mut app.world, 0);
+        app.world
+            .spawn((NodeBundle::default(), TabGroup::default()))
+            .push_children(&[trigger, other]);
+        app.world.resource_mut::<Focus>().0 = Some(trigger);
+        app.update();
+
+        // Open a dialog, and tab within it.
+        let open_dialog = |app: &mut App| {
+            let fields: Vec<Entity> = (0..2).map(|_| focusable(&mut app.world, 0)).collect();
+            app.world.entity_mut(fields[0]).insert(AutoFocus);
+            let dialog = app
+                .world
+                .spawn((
+                    NodeBundle::default(),
+                    TabGroup {
+                        order: 0,
+                        modal: true,
+                        ..Default::default()
+                    },
+                ))
+                .push_children(&fields)
+                .id();
+            app.update();
+            assert_eq!(app.world.resource::<Focus>().0, Some(fields[0]));
+            app.world.resource_mut::<Focus>().0 = Some(fields[1]);
+            app.update();
+            dialog
+        };
+        let dialog = open_dialog(&mut app);
+
+        // Closing the dialog returns focus to the trigger.
+        app.world.entity_mut(dialog).despawn_recursive();
+        app.update();
+        assert_eq!(app.world.resource::<Focus>().0, Some(trigger));
+
+        // Focus isn't restored if it was moved out of the dialog before closing it.
+        let dialog = open_dialog(&mut app);
+        app.world.resource_mut::<Focus>().0 = Some(other);
+        app.update();
+        app.world.entity_mut(dialog).despawn_recursive();
+        app.update();
+        assert_eq!(app.world.resource::<Focus>().0, Some(other));
+    }
+
+    #[test]
+    fn test_close_on_escape() {
+        let mut app = app();
+        let outside = focusable(&mut app.world, 0);
+        app.world
+            .spawn((NodeBundle::default(), TabGroup::default()))
+            .push_children(&[outside]);
+        let spawn_dialog = |app: &mut App, close_on_escape: bool| {
+            let field = focusable(&mut app.world, 0);
+            let dialog = app
+                .world
+                .spawn((
+                    NodeBundle::default(),
+                    TabGroup {
+                        modal: true,
+                        close_on_escape,
+                        ..Default::default()
+                    },
+                ))
+                .push_children(&[field])
+                .id();
+            (dialog, field)
+        };
+        let (dialog, field) = spawn_dialog(&mut app, true);
+        let (_, other_field) = spawn_dialog(&mut app, false);
+
+        let press_escape = |app: &mut App, focus: Entity| {
+            app.world.resource_mut::<Focus>().0 = Some(focus);
+            app.world
+                .resource_mut::<ButtonInput<KeyCode>>()
+                .press(KeyCode::Escape);
+            app.update();
+            let mut input = app.world.resource_mut::<ButtonInput<KeyCode>>();
+            input.release(KeyCode::Escape);
+            input.clear();
+            app.world
+                .resource_mut::<Events<CloseRequested>>()
+                .drain()
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            press_escape(&mut app, field),
+            vec![CloseRequested { group: dialog }]
+        );
+        // Escape is ignored outside of trapped groups, and in groups which don't opt in.
+        assert_eq!(press_escape(&mut app, outside), vec![]);
+        assert_eq!(press_escape(&mut app, other_field), vec![]);
+    }
+
+    #[test]
+    fn test_focus_changed() {
+        let mut app = app();
+        let items: Vec<Entity> = (0..2).map(|i| focusable(&mut app.world, i)).collect();
+        app.world
+            .spawn((NodeBundle::default(), TabGroup::default()))
+            .push_children(&items);
+
+        let set_focus = |app: &mut App, entity: Option<Entity>| {
+            app.world.resource_mut::<Focus>().0 = entity;
+            app.update();
+            let mut events = app.world.resource_mut::<Events<FocusChanged>>();
+            let sent: Vec<FocusChanged> = events.drain().collect();
+            sent
+        };
+
+        app.update();
+        app.world.resource_mut::<Events<FocusChanged>>().clear();
+        assert_eq!(
+            set_focus(&mut app, Some(items[0])),
+            vec![FocusChanged {
+                prev: None,
+                next: Some(items[0])
+            }]
+        );
+        assert_eq!(
+            set_focus(&mut app, Some(items[1])),
+            vec![FocusChanged {
+                prev: Some(items[0]),
+                next: Some(items[1])
+            }]
+        );
+        // Setting the same focus again isn't a change.
+        assert_eq!(set_focus(&mut app, Some(items[1])), vec![]);
+        assert_eq!(
+            set_focus(&mut app, None),
+            vec![FocusChanged {
+                prev: Some(items[1]),
+                next: None
+            }]
+        );
+    }
+
+    #[test]
+    fn test_hierarchy_order() {
+        let mut world = World::new();
+        // Spawn the entities in the reverse of their hierarchy order, so that the query order
+        // doesn't match the document order.
+        let mut siblings: Vec<Entity> = (0..3).map(|_| focusable(&mut world, 0)).collect();
+        siblings.reverse();
+        let group_b = world
+            .spawn((NodeBundle::default(), TabGroup::default()))
+            .push_children(&siblings[2..])
+            .id();
+        let group_a = world
+            .spawn((NodeBundle::default(), TabGroup::default()))
+            .push_children(&siblings[..2])
+            .id();
+        world
+            .spawn(NodeBundle::default())
+            .push_children(&[group_a, group_b]);
+
+        let mut state: SystemState<TabNavigation> = SystemState::new(&mut world);
+        let nav = state.get(&world);
+        let mut order = vec![nav.navigate(None, false).unwrap()];
+        for _ in 1..3 {
+            order.push(nav.navigate(order.last().copied(), false).unwrap());
+        }
+        assert_eq!(order, siblings);
+    }
+
+    #[test]
+    fn test_first_last() {
+        let mut world = World::new();
+        let a = [focusable(&mut world, 1), focusable(&mut world, 0)];
+        let b = [focusable(&mut world, 0), focusable(&mut world, 2)];
+        // Group `b` is ordered before group `a`, which breaks ties between equal tab indices.
+        let group_a = world
+            .spawn((
+                NodeBundle::default(),
+                TabGroup {
+                    order: 1,
+                    ..Default::default()
+                },
+            ))
+            .push_children(&a)
+            .id();
+        let group_b = world
+            .spawn((NodeBundle::default(), TabGroup::default()))
+            .push_children(&b)
+            .id();
+        world
+            .spawn(NodeBundle::default())
+            .push_children(&[group_a, group_b]);
+
+        let mut state: SystemState<TabNavigation> = SystemState::new(&mut world);
+        let nav = state.get(&world);
+        let mut order = vec![a[0]];
+        loop {
+            let next = nav.navigate(order.last().copied(), false).unwrap();
+            if next == order[0] {
+                break;
+            }
+            order.push(next);
+        }
+        let start = order.iter().position(|e| Some(*e) == nav.first()).unwrap();
+        order.rotate_left(start);
+        assert_eq!(order, vec![b[0], a[1], a[0], b[1]]);
+        assert_eq!(nav.first(), Some(order[0]));
+        assert_eq!(nav.last(), Some(order[3]));
+        assert_eq!(nav.navigate(nav.last(), false), nav.first());
+
+        // No focusable entities.
+        let mut world = World::new();
+        world.spawn((NodeBundle::default(), TabGroup::default()));
+        let mut state: SystemState<TabNavigation> = SystemState::new(&mut world);
+        let nav = state.get(&world);
+        assert_eq!(nav.first(), None);
+        assert_eq!(nav.last(), None);
+    }
+
+    #[test]
+    fn test_roving_tab_group() {
+        let mut app = app();
+        // A toolbar with three buttons in a row, followed by another tabbable entity.
+        let buttons: Vec<Entity> = (0..3)
+            .map(|i| {
+                app.world
+                    .spawn((NodeBundle::default(), TabIndex(0)))
+                    .insert(GlobalTransform::from_xyz(i as f32 * 10., 0., 0.))
+                    .id()
+            })
+            .collect();
+        let toolbar = app
+            .world
+            .spawn((
+                NodeBundle::default(),
+                RovingTabGroup {
+                    active: Some(buttons[1]),
+                },
+            ))
+            .push_children(&buttons)
+            .id();
+        let other = focusable(&mut app.world, 0);
+        app.world
+            .spawn((NodeBundle::default(), TabGroup::default()))
+            .push_children(&[toolbar, other]);
+        app.update();
+
+        let tab_order = |app: &mut App| {
+            let mut state: SystemState<TabNavigation> = SystemState::new(&mut app.world);
+            let nav = state.get(&app.world);
+            let first = nav.navigate(None, false).unwrap();
+            let second = nav.navigate(Some(first), false).unwrap();
+            assert_eq!(nav.navigate(Some(second), false), Some(first));
+            vec![first, second]
+        };
+
+        // Only the active member can be reached with Tab.
+        assert_eq!(tab_order(&mut app), vec![buttons[1], other]);
+        {
+            let mut state: SystemState<TabNavigation> = SystemState::new(&mut app.world);
+            let nav = state.get(&app.world);
+            assert_eq!(
+                nav.navigate_direction(buttons[1], Direction2d::X),
+                Some(buttons[2])
+            );
+            assert_eq!(
+                nav.navigate_direction(buttons[1], Direction2d::NEG_X),
+                Some(buttons[0])
+            );
+        }
+
+        // The arrow keys move the focus within the group, which changes the active member.
+        app.world.resource_mut::<Focus>().0 = Some(buttons[1]);
+        app.update();
+        let mut input = app.world.resource_mut::<ButtonInput<KeyCode>>();
+        input.press(KeyCode::ArrowLeft);
+        app.update();
+        app.world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release_all();
+        app.update();
+        assert_eq!(app.world.resource::<Focus>().0, Some(buttons[0]));
+        assert_eq!(
+            app.world.get::<RovingTabGroup>(toolbar).unwrap().active,
+            Some(buttons[0])
+        );
+        assert_eq!(tab_order(&mut app), vec![buttons[0], other]);
+    }
+
+    #[test]
+    fn test_navigate_direction() {
+        let mut world = World::new();
+        // A 3x3 grid of cells 10 units apart, with the middle row shifted slightly right.
+        let grid: Vec<Vec<Entity>> = (0..3)
+            .map(|row| {
+                (0..3)
+                    .map(|col| {
+                        let x = col as f32 * 10. + if row == 1 { 3. } else { 0. };
+                        let y = row as f32 * 10.;
+                        world
+                            .spawn((NodeBundle::default(), TabIndex(0)))
+                            .insert(GlobalTransform::from_xyz(x, y, 0.))
+                            .id()
+                    })
+                    .collect()
+            })
+            .collect();
+        let cells: Vec<Entity> = grid.iter().flatten().copied().collect();
+        world
+            .spawn((NodeBundle::default(), TabGroup::default()))
+            .push_children(&cells);
+
+        let mut state: SystemState<TabNavigation> = SystemState::new(&mut world);
+        let nav = state.get(&world);
+        let right = Direction2d::X;
+        let down = Direction2d::Y;
+        assert_eq!(nav.navigate_direction(grid[0][0], right), Some(grid[0][1]));
+        assert_eq!(nav.navigate_direction(grid[0][1], right), Some(grid[0][2]));
+        assert_eq!(nav.navigate_direction(grid[0][2], right), None);
+        assert_eq!(nav.navigate_direction(grid[0][0], down), Some(grid[1][0]));
+        assert_eq!(nav.navigate_direction(grid[1][0], down), Some(grid[2][0]));
+        assert_eq!(nav.navigate_direction(grid[2][2], down), None);
+
+        // Moving down from the shifted row prefers the cell directly below, rather than
+        // one which is diagonally closer.
+        assert_eq!(nav.navigate_direction(grid[1][1], down), Some(grid[2][1]));
+        assert_eq!(
+            nav.navigate_direction(grid[1][1], Direction2d::NEG_Y),
+            Some(grid[0][1])
+        );
+    }
 }
@@ -1,5 +1,4 @@
 use bevy::{prelude::*, ui};
-use bevy_grackle::hooks::ElementRectApi;
 use bevy_quill::prelude::*;
 use static_init::dynamic;
 
@@ -1,4 +1,8 @@
 //! Complex example with multiple views
+
+// The example is built as a test target, which doesn't use everything that `main` does.
+#![cfg_attr(test, allow(dead_code))]
+
 mod collapse;
 mod dialog;
 mod disclosure;
@@ -8,10 +12,9 @@ mod swatch;
 mod test_scene;
 mod viewport;
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use bevy::{
-    a11y::Focus,
     asset::io::{file::FileAssetReader, AssetSource},
     prelude::*,
     ui,
@@ -27,19 +30,19 @@ use bevy_mod_picking::{
     prelude::*,
 };
 use bevy_quill::prelude::*;
-use bevy_tabindex::{TabGroup, TabNavigation};
+use bevy_tabindex::{TabGroup, TabNavigationPlugin};
 use dialog::{dialog, RequestClose};
 use disclosure::DisclosureTrianglePlugin;
 use node_tree::{node_tree, NodeTreePlugin};
 use static_init::dynamic;
 use swatch::{swatch, swatch_grid, SwatchGridProps, SwatchProps};
+use test_scene::Rotating;
 use viewport::{ViewportInset, ViewportInsetElement};
 
 fn main() {
     App::new()
         .init_resource::<ViewportInset>()
         .init_resource::<PanelWidth>()
-        .init_resource::<ClickLog>()
         .insert_resource(EditColor {
             color: Color::Rgba {
                 red: 1.0,
@@ -48,6 +51,7 @@ fn main() {
                 alpha: 1.0,
             },
         })
+        .insert_resource(Rotating(true))
         .insert_resource(ThemeSelection {
             theme: GrackleTheme::Dark,
         })
@@ -57,10 +61,11 @@ fn main() {
                 .with_reader(|| Box::new(FileAssetReader::new("crates/bevy_grackle/assets"))),
         )
         .add_plugins((
-            QuillPlugin,
+            QuillPlugin::default(),
             NodeTreePlugin,
             DisclosureTrianglePlugin,
             bevy_grackle::GracklePlugin,
+            TabNavigationPlugin,
         ))
         .add_plugins(
             DefaultPlugins
@@ -81,12 +86,41 @@ fn main() {
                 test_scene::rotate,
                 test_scene::update_viewport_inset,
                 test_scene::update_camera_viewport,
-                handle_tab,
             ),
         )
         .run();
 }
 
+/// Items in the "File" menu, as (label, id) pairs. Groups are separated by dividers.
+const MENU_GROUPS: &[&[(&str, &str)]] = &[
+    &[("Light Theme", "light-theme"), ("Dark Theme", "dark-theme")],
+    &[
+        ("Save", "save"),
+        ("Save As…", "save-as"),
+        ("Export…", "export"),
+        ("Import…", "import"),
+    ],
+];
+
+/// The items of the "File" menu, with dividers between groups.
+fn file_menu_items(_cx: Cx) -> impl View {
+    For::each_separated(
+        MENU_GROUPS,
+        |group| file_menu_group.bind(*group),
+        || menu_divider.bind(()),
+    )
+}
+
+fn file_menu_group(cx: Cx<&'static [(&'static str, &'static str)]>) -> impl View {
+    For::each(cx.props, |(label, id)| {
+        menu_item.bind(MenuItemProps {
+            label: *label,
+            id,
+            ..default()
+        })
+    })
+}
+
 #[dynamic]
 static STYLE_MAIN: StyleHandle = StyleHandle::build(|ss| {
     ss.position(ui::PositionType::Absolute)
@@ -106,7 +140,7 @@ static STYLE_BUTTON_ROW: StyleHandle = StyleHandle::build(|ss| ss.gap(8));
 static STYLE_BUTTON_FLEX: StyleHandle = StyleHandle::build(|ss| ss.flex_grow(1.));
 
 #[dynamic]
-static STYLE_SLIDER: StyleHandle = StyleHandle::build(|ss| ss.align_self(ui::AlignSelf::Stretch));
+static STYLE_SPIN_BOX: StyleHandle = StyleHandle::build(|ss| ss.align_self(ui::AlignSelf::Stretch));
 
 #[dynamic]
 static STYLE_ASIDE: StyleHandle = StyleHandle::build(|ss| {
@@ -130,6 +164,8 @@ static STYLE_VIEWPORT: StyleHandle = StyleHandle::build(|ss| {
         .display(ui::Display::Flex)
         .flex_direction(ui::FlexDirection::Column)
         .justify_content(ui::JustifyContent::FlexEnd)
+        .cursor_image("crosshair.png")
+        .cursor_offset(IVec2::new(12, 12))
 });
 
 #[dynamic]
@@ -212,9 +248,6 @@ impl Default for PanelWidth {
     }
 }
 
-#[derive(Resource, Default)]
-pub struct ClickLog(Vec<String>);
-
 fn setup_view_root(mut commands: Commands) {
     let camera2d = commands
         .spawn((Camera2dBundle {
@@ -228,16 +261,36 @@ fn setup_view_root(mut commands: Commands) {
         },))
         .id();
 
+    // Layer which popup menus are parented to, so that they render on the HUD camera.
+    let overlay = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    ..default()
+                },
+                z_index: ZIndex::Global(100),
+                ..default()
+            },
+            Pickable::IGNORE,
+            Name::new("Overlay"),
+            TargetCamera(camera2d),
+        ))
+        .id();
+
     commands.spawn((
-        ViewHandle::new(ui_main, ()),
+        ViewHandle::new(ui_main, overlay),
         Name::new("ViewRoot"),
-        TargetCamera(camera2d)
+        TargetCamera(camera2d),
     ));
 }
 
-fn ui_main(mut cx: Cx) -> impl View {
+fn ui_main(mut cx: Cx<Entity>) -> impl View {
     let theme = cx.use_resource::<ThemeSelection>().theme;
     init_grackle_theme(&mut cx, theme);
+    cx.provide(POPUP_LAYER, *cx.props);
     let target = cx.use_view_entity().id();
     let open = cx.create_atom_init(|| false);
     cx.use_effect(
@@ -281,7 +334,6 @@ fn ui_main(mut cx: Cx) -> impl View {
                 .insert(On::<Clicked>::run(
                     move |ev: Listener<Clicked>,
                           mut atoms: AtomStore,
-                          mut log: ResMut<ClickLog>,
                           mut theme: ResMut<ThemeSelection>| {
                         match ev.id {
                             "save" => {
@@ -295,7 +347,13 @@ fn ui_main(mut cx: Cx) -> impl View {
                             }
                             _ => (),
                         }
-                        log.0.push(format!("Clicked: id='{}'", ev.id).to_string());
+                    },
+                ))
+                .insert(On::<ValueChanged<bool>>::run(
+                    |ev: Listener<ValueChanged<bool>>, mut rotating: ResMut<Rotating>| {
+                        if ev.id == "rotate" {
+                            rotating.0 = ev.value;
+                        }
                     },
                 ))
                 .insert(On::<MenuEvent>::run(
@@ -320,39 +378,7 @@ fn ui_main(mut cx: Cx) -> impl View {
                                 MenuButtonProps::new()
                                     .children("File…")
                                     .indent(true)
-                                    .items(Fragment::new((
-                                        menu_item.bind(MenuItemProps {
-                                            label: "Light Theme",
-                                            id: "light-theme",
-                                            ..default()
-                                        }),
-                                        menu_item.bind(MenuItemProps {
-                                            label: "Dark Theme",
-                                            id: "dark-theme",
-                                            ..default()
-                                        }),
-                                        menu_divider.bind(()),
-                                        menu_item.bind(MenuItemProps {
-                                            label: "Save",
-                                            id: "save",
-                                            ..default()
-                                        }),
-                                        menu_item.bind(MenuItemProps {
-                                            label: "Save As…",
-                                            id: "save-as",
-                                            ..default()
-                                        }),
-                                        menu_item.bind(MenuItemProps {
-                                            label: "Export…",
-                                            id: "export",
-                                            ..default()
-                                        }),
-                                        menu_item.bind(MenuItemProps {
-                                            label: "Import…",
-                                            id: "import",
-                                            ..default()
-                                        }),
-                                    )))
+                                    .items(file_menu_items.bind(()))
                                     .style(STYLE_BUTTON_FLEX.clone()),
                             ),
                         )),
@@ -366,6 +392,7 @@ fn ui_main(mut cx: Cx) -> impl View {
                         style: (),
                         ..default()
                     }),
+                    rotate_checkbox,
                     color_edit,
                     node_tree,
                 )),
@@ -384,24 +411,30 @@ fn ui_main(mut cx: Cx) -> impl View {
         ))
 }
 
+fn rotate_checkbox(cx: Cx) -> impl View {
+    let rotating = cx.use_resource::<Rotating>().0;
+    checkbox.bind(
+        CheckboxProps::new("rotate")
+            .checked(rotating)
+            .label("Rotate shapes"),
+    )
+}
+
 fn color_edit(cx: Cx) -> impl View {
     let edit_color = cx.use_resource::<EditColor>();
     Element::new()
         .styled(COLOR_EDIT.clone())
-        .insert((On::<ValueChanged<f32>>::run(
-            move |ev: Listener<ValueChanged<f32>>, mut color: ResMut<EditColor>| match ev.id {
-                "r" => {
-                    color.as_mut().color.set_r(ev.value / 255.0);
-                }
-                "g" => {
-                    color.as_mut().color.set_g(ev.value / 255.0);
-                }
-                "b" => {
-                    color.as_mut().color.set_b(ev.value / 255.0);
-                }
-                _ => (),
+        .insert(On::<ValueChanged<f32>>::run(
+            |ev: Listener<ValueChanged<f32>>, mut color: ResMut<EditColor>| {
+                let value = ev.value / 255.0;
+                match ev.id {
+                    "r" => color.color.set_r(value),
+                    "g" => color.color.set_g(value),
+                    "b" => color.color.set_b(value),
+                    _ => return,
+                };
             },
-        ),))
+        ))
         .children((
             swatch.bind(SwatchProps {
                 color: edit_color.color,
@@ -410,51 +443,44 @@ fn color_edit(cx: Cx) -> impl View {
                 colors: &COLORS,
                 row_span: 4,
             }),
-            h_slider.bind(SliderProps {
-                id: "r",
-                min: 0.,
-                max: 255.,
-                value: edit_color.color.r() * 255.0,
-                style: STYLE_SLIDER.clone(),
-            }),
-            h_slider.bind(SliderProps {
-                id: "g",
-                min: 0.,
-                max: 255.,
-                value: edit_color.color.g() * 255.0,
-                style: STYLE_SLIDER.clone(),
-            }),
-            h_slider.bind(SliderProps {
-                id: "b",
-                min: 0.,
-                max: 255.,
-                value: edit_color.color.b() * 255.0,
-                style: STYLE_SLIDER.clone(),
-            }),
+            color_spin_box("r", edit_color.color.r()),
+            color_spin_box("g", edit_color.color.g()),
+            color_spin_box("b", edit_color.color.b()),
         ))
 }
 
-fn handle_tab(nav: TabNavigation, key: Res<ButtonInput<KeyCode>>, mut focus: ResMut<Focus>) {
-    if key.just_pressed(KeyCode::Tab) {
-        let next = nav.navigate(
-            focus.0,
-            key.pressed(KeyCode::ShiftLeft) || key.pressed(KeyCode::ShiftRight),
-        );
-        if next.is_some() {
-            focus.0 = next;
-        }
-    }
+fn color_spin_box(id: &'static str, channel: f32) -> impl View {
+    spin_box.bind(SpinBoxProps {
+        id,
+        value: (channel * 255.0).round(),
+        min: 0.,
+        max: 255.,
+        style: STYLE_SPIN_BOX.clone(),
+        ..default()
+    })
 }
 
-fn event_log(cx: Cx) -> impl View {
-    let log = cx.use_resource::<ClickLog>();
+fn event_log(mut cx: Cx) -> impl View {
+    let log = cx.create_atom_init::<Vec<(String, u64)>>(Vec::new);
+    let seconds = cx.use_interval(Duration::from_secs(1));
+    let clicks = cx.use_event::<Clicked>();
+    let mut entries = cx.read_atom(log);
+    if !clicks.is_empty() {
+        entries.extend(
+            clicks
+                .iter()
+                .map(|ev| (format!("Clicked: id='{}'", ev.id), seconds)),
+        );
+        cx.write_atom(log, entries.clone());
+    }
     Element::new().styled(STYLE_LOG.clone()).children(
         Element::new()
             .styled(STYLE_LOG_INNER.clone())
-            .children(For::each(&log.0, |item| {
-                Element::new()
-                    .styled(STYLE_LOG_ENTRY.clone())
-                    .children((item.to_owned(), "00:00:00"))
+            .children(For::each(&entries, |(item, time)| {
+                Element::new().styled(STYLE_LOG_ENTRY.clone()).children((
+                    item.to_owned(),
+                    format!("{:02}:{:02}:{:02}", time / 3600, time / 60 % 60, time % 60),
+                ))
             })),
     )
 }
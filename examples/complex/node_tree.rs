@@ -1,7 +1,7 @@
 use bevy::{asset::AssetPath, prelude::*, ui};
 use bevy_grackle::hooks::{EnterExitApi, EnterExitState};
 use bevy_mod_picking::prelude::*;
-use bevy_quill::prelude::*;
+use bevy_quill::{prelude::*, ScrollIntoView};
 use static_init::dynamic;
 
 use crate::{
@@ -14,9 +14,8 @@ pub struct NodeTreePlugin;
 
 impl Plugin for NodeTreePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<RootEntityList>()
-            .init_resource::<SelectedEntity>()
-            .add_systems(Update, (update_root_entities, update_node_entities));
+        app.init_resource::<SelectedEntity>()
+            .add_systems(Update, (update_node_entities, scroll_to_selection));
     }
 }
 
@@ -37,12 +36,13 @@ impl Ord for EntityListNode {
     }
 }
 
-#[derive(Resource, Default)]
-pub struct RootEntityList(pub Vec<EntityListNode>);
-
 #[derive(Resource, Default)]
 pub struct SelectedEntity(pub Option<Entity>);
 
+/// Marks the header of the tree node which displays an entity.
+#[derive(Component)]
+pub struct NodeHeader(Entity);
+
 #[derive(Component)]
 pub struct NodeInfo {
     entity: Entity,
@@ -113,14 +113,31 @@ static STYLE_TREE_NODE_CHILDREN: StyleHandle = StyleHandle::build(|ss| {
         .margin_left(16)
 });
 
-pub fn node_tree(cx: Cx) -> impl View {
-    let roots = cx.use_resource::<RootEntityList>();
+pub fn node_tree(mut cx: Cx) -> impl View {
+    let mut roots: Vec<EntityListNode> = cx
+        .use_query::<Entity, (Without<Parent>, Without<AtomCell>, Without<Callback>)>()
+        .into_iter()
+        .map(|entity| EntityListNode { entity })
+        .collect();
+    roots.sort();
     scroll_view.bind(ScrollViewProps {
         children: ViewParam::new(Element::new().styled(STYLE_TREE_INNER.clone()).children(
-            For::keyed(&roots.0, |e| e.entity, |e| node_item.bind(e.clone())),
+            For::keyed_or(
+                &roots,
+                |e| e.entity,
+                |e| node_item.bind(e.clone()),
+                "(no entities)",
+            ),
         )),
         scroll_enable_x: true,
         scroll_enable_y: true,
+        overlay_scrollbars: false,
+        auto_hide_scrollbars: Some(1.),
+        min_thumb_size: 10.,
+        scrollbar_thickness: 8.,
+        scroll_left: 0.,
+        scroll_top: 0.,
+        on_scroll: None,
         style: STYLE_TREE.clone(),
         content_style: STYLE_CONTENT.clone(),
     })
@@ -156,6 +173,7 @@ pub fn node_item(mut cx: Cx<EntityListNode>) -> impl View {
             .with_memo(
                 move |mut e| {
                     e.insert((
+                        NodeHeader(entity),
                         On::<Pointer<Click>>::run(move |mut selected: ResMut<SelectedEntity>| {
                             selected.0 = Some(entity);
                         }),
@@ -215,22 +233,6 @@ pub fn node_desc(cx: Cx<EntityListNode>) -> impl View {
     ))
 }
 
-fn update_root_entities(
-    query: Query<Entity, (Without<Parent>, Without<AtomCell>)>,
-    mut roots: ResMut<RootEntityList>,
-) {
-    let mut sorted: Vec<EntityListNode> = Vec::with_capacity(query.iter().len());
-    for entity in query.iter() {
-        let node = EntityListNode { entity };
-        sorted.push(node);
-    }
-    sorted.sort();
-
-    if sorted != roots.0 {
-        roots.0 = sorted;
-    }
-}
-
 fn update_node_entities(
     mut query: Query<&mut NodeInfo>,
     query_children: Query<&Children, Without<AtomCell>>,
@@ -246,3 +248,20 @@ fn update_node_entities(
         }
     }
 }
+
+/// Scroll the header of the selected entity into view when the selection changes.
+fn scroll_to_selection(
+    mut commands: Commands,
+    selected: Res<SelectedEntity>,
+    query: Query<(Entity, &NodeHeader)>,
+) {
+    if !selected.is_changed() {
+        return;
+    }
+    if let Some((header, _)) = query.iter().find(|(_, h)| Some(h.0) == selected.0) {
+        commands.add(ScrollIntoView {
+            target: header,
+            margin: 4.,
+        });
+    }
+}
@@ -1,6 +1,10 @@
 use bevy::{prelude::*, ui};
 use bevy_mod_picking::{events::PointerCancel, prelude::*};
-use bevy_quill::{prelude::*, ScrollArea, ScrollBar, ScrollBarThumb, ScrollContent, ScrollWheel};
+use bevy_quill::{
+    prelude::*, ScrollArea, ScrollBar, ScrollBarAutoHide, ScrollBarThumb, ScrollContent,
+    ScrollKeyboardInput, ScrollWheel,
+};
+use bevy_tabindex::TabIndex;
 use static_init::dynamic;
 
 // Style definitions for scrollview widget.
@@ -41,7 +45,12 @@ static STYLE_SCROLL_CONTENT: StyleHandle = StyleHandle::build(|ss| {
 static STYLE_SCROLLBAR_X: StyleHandle = StyleHandle::build(|ss| {
     ss.grid_column(ui::GridPlacement::start_span(1, 1))
         .grid_row(ui::GridPlacement::start_span(2, 1))
-        .height(8)
+        .selector(".overlay > &", |ss| {
+            ss.position(ui::PositionType::Absolute)
+                .left(0)
+                .right(0)
+                .bottom(0)
+        })
 });
 
 #[dynamic]
@@ -50,14 +59,29 @@ static STYLE_SCROLLBAR_X_THUMB: StyleHandle = StyleHandle::build(|ss| {
         .position(ui::PositionType::Absolute)
         .top(1)
         .bottom(1)
+        .selector(".auto-hide > * > &", |ss| ss.background_color("#3340"))
+        .selector(".auto-hide > .scrolling > &", |ss| {
+            ss.background_color("#334")
+        })
         .selector(":hover > &,.drag", |ss| ss.background_color("#556"))
+        .transition(&[Transition {
+            property: TransitionProperty::BackgroundColor,
+            duration: 0.3,
+            timing: timing::EASE_IN_OUT,
+            ..default()
+        }])
 });
 
 #[dynamic]
 static STYLE_SCROLLBAR_Y: StyleHandle = StyleHandle::build(|ss| {
     ss.grid_column(ui::GridPlacement::start_span(2, 1))
         .grid_row(ui::GridPlacement::start_span(1, 1))
-        .width(8)
+        .selector(".overlay > &", |ss| {
+            ss.position(ui::PositionType::Absolute)
+                .top(0)
+                .right(0)
+                .bottom(0)
+        })
 });
 
 #[dynamic]
@@ -66,18 +90,64 @@ static STYLE_SCROLLBAR_Y_THUMB: StyleHandle = StyleHandle::build(|ss| {
         .position(ui::PositionType::Absolute)
         .left(1)
         .right(1)
+        .selector(".auto-hide > * > &", |ss| ss.background_color("#3340"))
+        .selector(".auto-hide > .scrolling > &", |ss| {
+            ss.background_color("#334")
+        })
         .selector(":hover > &,.drag", |ss| ss.background_color("#556"))
+        .transition(&[Transition {
+            property: TransitionProperty::BackgroundColor,
+            duration: 0.3,
+            timing: timing::EASE_IN_OUT,
+            ..default()
+        }])
 });
 
 const CLS_DRAG: &str = "drag";
 
-#[derive(Clone, PartialEq, Default)]
+#[derive(Clone, PartialEq)]
 pub struct ScrollViewProps<V: View> {
     pub children: V,
     pub style: StyleHandle,
     pub content_style: StyleHandle,
     pub scroll_enable_x: bool,
     pub scroll_enable_y: bool,
+    /// If true, the scrollbars float on top of the content rather than taking up space.
+    pub overlay_scrollbars: bool,
+    /// If set, the scrollbars are only shown while the view is scrolled or hovered, and fade
+    /// out after this many seconds of inactivity.
+    pub auto_hide_scrollbars: Option<f32>,
+    /// The smallest size of the scrollbar thumbs along the track, in pixels.
+    pub min_thumb_size: f32,
+    /// The width of the vertical scrollbar and the height of the horizontal one, in pixels.
+    pub scrollbar_thickness: f32,
+    /// Initial horizontal scroll position.
+    pub scroll_left: f32,
+    /// Initial vertical scroll position.
+    pub scroll_top: f32,
+    /// Callback which is invoked with the new scroll position whenever it changes. Together
+    /// with the initial position, this lets a presenter preserve the scroll position when the
+    /// scroll view is rebuilt.
+    pub on_scroll: Option<CallbackHandle<Vec2>>,
+}
+
+impl<V: View + Default> Default for ScrollViewProps<V> {
+    fn default() -> Self {
+        Self {
+            children: V::default(),
+            style: StyleHandle::default(),
+            content_style: StyleHandle::default(),
+            scroll_enable_x: false,
+            scroll_enable_y: false,
+            overlay_scrollbars: false,
+            auto_hide_scrollbars: None,
+            min_thumb_size: 10.,
+            scrollbar_thickness: 8.,
+            scroll_left: 0.,
+            scroll_top: 0.,
+            on_scroll: None,
+        }
+    }
 }
 
 #[derive(Clone, PartialEq, Default, Copy)]
@@ -99,33 +169,80 @@ pub fn scroll_view<V: View + Clone>(mut cx: Cx<ScrollViewProps<V>>) -> impl View
     let enable_x = cx.props.scroll_enable_x;
     let enable_y = cx.props.scroll_enable_y;
     let id_scroll_area = cx.create_entity();
-    let id_scrollbar_x = cx.create_entity();
-    let id_scrollbar_y = cx.create_entity();
-    let drag_state = cx.create_atom_init(DragState::default);
+    // The scrollbars' entities and drag state are only needed while a scrollbar is shown, so
+    // they are allocated last, and released when scrolling is disabled.
+    cx.release_unused_entities();
+    let scrollbars = (enable_x || enable_y).then(|| ScrollbarIds {
+        id_x: cx.create_entity(),
+        id_y: cx.create_entity(),
+        drag_state: cx.create_atom_init(DragState::default),
+    });
+    let id_scrollbar_x = scrollbars.filter(|_| enable_x).map(|ids| ids.id_x);
+    let id_scrollbar_y = scrollbars.filter(|_| enable_y).map(|ids| ids.id_y);
+    let scroll_left = cx.props.scroll_left;
+    let scroll_top = cx.props.scroll_top;
+    let on_scroll = cx.props.on_scroll;
+    let auto_hide = cx.props.auto_hide_scrollbars;
+    let min_thumb_size = cx.props.min_thumb_size;
+    let thickness = cx.props.scrollbar_thickness;
+    let scrollbar_view =
+        |id_scrollbar: Option<Entity>, vertical: bool| match (id_scrollbar, scrollbars) {
+            (Some(id_scrollbar), Some(ids)) => scrollbar
+                .bind(ScrollbarProps {
+                    id_scroll_area,
+                    id_scrollbar,
+                    drag_state: ids.drag_state,
+                    vertical,
+                    auto_hide,
+                    min_thumb_size,
+                    thickness,
+                })
+                .boxed(),
+            _ => ().boxed(),
+        };
     Element::new()
         .styled((STYLE_SCROLL_VIEW.clone(), cx.props.style.clone()))
+        .class_names((
+            "overlay".if_true(cx.props.overlay_scrollbars),
+            "auto-hide".if_true(auto_hide.is_some()),
+        ))
         .children((
             // Scroll area
             RefElement::new(id_scroll_area)
                 .with_memo(
                     move |mut e| {
+                        // Keep the scroll position when the scrollbars change.
+                        if let Some(mut scroll_area) = e.get_mut::<ScrollArea>() {
+                            scroll_area.id_scrollbar_x = id_scrollbar_x;
+                            scroll_area.id_scrollbar_y = id_scrollbar_y;
+                            return;
+                        }
                         e.insert((
                             ScrollArea {
-                                id_scrollbar_x: if enable_x { Some(id_scrollbar_x) } else { None },
-                                id_scrollbar_y: if enable_y { Some(id_scrollbar_y) } else { None },
+                                id_scrollbar_x,
+                                id_scrollbar_y,
+                                scroll_left,
+                                scroll_top,
                                 ..default()
                             },
+                            // Allow the scroll area to be focused and scrolled with the keyboard.
+                            TabIndex(0),
+                            ScrollKeyboardInput,
                             On::<ScrollWheel>::listener_component_mut::<ScrollArea>(
                                 move |ev, scrolling| {
-                                    // TODO: stop prop
-                                    // ev.stop_propagation();
+                                    ev.stop_propagation();
                                     scrolling.scroll_by(-ev.delta.x, -ev.delta.y);
                                 },
                             ),
                         ));
                     },
-                    (),
+                    (id_scrollbar_x, id_scrollbar_y),
                 )
+                .on_scroll(move |_, pos, world| {
+                    if let Some(on_scroll) = on_scroll {
+                        world.run_callback(on_scroll, pos);
+                    }
+                })
                 .styled(STYLE_SCROLL_REGION.clone())
                 .children(
                     Element::new()
@@ -134,43 +251,48 @@ pub fn scroll_view<V: View + Clone>(mut cx: Cx<ScrollViewProps<V>>) -> impl View
                         .children(cx.props.children.clone()),
                 ),
             // Horizontal scroll bar
-            If::new(
-                cx.props.scroll_enable_x,
-                scrollbar.bind(ScrollbarProps {
-                    id_scroll_area,
-                    id_scrollbar: id_scrollbar_x,
-                    drag_state,
-                    vertical: false,
-                }),
-                (),
-            ),
+            scrollbar_view(id_scrollbar_x, false),
             // Vertical scroll bar
-            If::new(
-                cx.props.scroll_enable_y,
-                scrollbar.bind(ScrollbarProps {
-                    id_scroll_area,
-                    id_scrollbar: id_scrollbar_y,
-                    drag_state,
-                    vertical: true,
-                }),
-                (),
-            ),
+            scrollbar_view(id_scrollbar_y, true),
         ))
 }
 
+/// Entities and state which are only allocated while a scrollbar is shown.
+#[derive(Clone, Copy)]
+struct ScrollbarIds {
+    id_x: Entity,
+    id_y: Entity,
+    drag_state: AtomHandle<DragState>,
+}
+
 #[derive(Clone, PartialEq)]
 pub struct ScrollbarProps {
     id_scroll_area: Entity,
     id_scrollbar: Entity,
     drag_state: AtomHandle<DragState>,
     vertical: bool,
+    auto_hide: Option<f32>,
+    min_thumb_size: f32,
+    thickness: f32,
 }
 
 fn scrollbar(mut cx: Cx<ScrollbarProps>) -> impl View {
     let vertical = cx.props.vertical;
     let drag_state = cx.props.drag_state;
     let id_scroll_area = cx.props.id_scroll_area;
+    let auto_hide = cx.props.auto_hide;
+    let min_thumb_size = cx.props.min_thumb_size;
+    let thickness = cx.props.thickness;
     let id_thumb = cx.create_entity();
+    let style_thickness = cx.use_memo((vertical, thickness), || {
+        StyleHandle::build(|ss| {
+            if vertical {
+                ss.width(thickness)
+            } else {
+                ss.height(thickness)
+            }
+        })
+    });
     let mode = if vertical {
         DragMode::DragY
     } else {
@@ -178,42 +300,51 @@ fn scrollbar(mut cx: Cx<ScrollbarProps>) -> impl View {
     };
     RefElement::new(cx.props.id_scrollbar)
         .insert(
-            (
-                ScrollBar {
-                    id_scroll_area,
-                    vertical,
-                    min_thumb_size: 10.,
+            // Click outside of thumb
+            On::<Pointer<DragStart>>::run(
+                move |mut ev: ListenerMut<Pointer<DragStart>>,
+                        mut query: Query<&mut ScrollArea>,
+                        query_thumb: Query<(
+                    &Node,
+                    &mut ScrollBarThumb,
+                    &GlobalTransform,
+                )>| {
+                    ev.stop_propagation();
+                    if let Ok(mut scroll_area) = query.get_mut(id_scroll_area) {
+                        if let Ok((thumb, _, transform)) = query_thumb.get(id_thumb) {
+                            // Get thumb rectangle
+                            let rect = thumb.logical_rect(transform);
+                            handle_track_click(
+                                &mut scroll_area,
+                                vertical,
+                                ev.pointer_location.position,
+                                rect,
+                            );
+                        }
+                    };
                 },
-                // Click outside of thumb
-                On::<Pointer<DragStart>>::run(
-                    move |mut ev: ListenerMut<Pointer<DragStart>>,
-                            mut query: Query<&mut ScrollArea>,
-                            query_thumb: Query<(
-                        &Node,
-                        &mut ScrollBarThumb,
-                        &GlobalTransform,
-                    )>| {
-                        ev.stop_propagation();
-                        if let Ok(mut scroll_area) = query.get_mut(id_scroll_area) {
-                            if let Ok((thumb, _, transform)) = query_thumb.get(id_thumb) {
-                                // Get thumb rectangle
-                                let rect = thumb.logical_rect(transform);
-                                handle_track_click(
-                                    &mut scroll_area,
-                                    vertical,
-                                    ev.pointer_location.position,
-                                    rect,
-                                );
-                            }
-                        };
-                    },
-                ),
             ),
         )
+        .with_memo(
+            move |mut e| {
+                e.insert(ScrollBar {
+                    id_scroll_area,
+                    vertical,
+                    min_thumb_size,
+                    hide_when_fits: true,
+                });
+                if let Some(delay) = auto_hide {
+                    e.insert(ScrollBarAutoHide::new(delay));
+                } else {
+                    e.remove::<ScrollBarAutoHide>();
+                }
+            },
+            (auto_hide, min_thumb_size),
+        )
         .styled(if vertical {
-            STYLE_SCROLLBAR_Y.clone()
+            (STYLE_SCROLLBAR_Y.clone(), style_thickness)
         } else {
-            STYLE_SCROLLBAR_X.clone()
+            (STYLE_SCROLLBAR_X.clone(), style_thickness)
         })
         .children(
             RefElement::new(id_thumb)
@@ -229,11 +360,11 @@ fn scrollbar(mut cx: Cx<ScrollbarProps>) -> impl View {
                     On::<Pointer<DragStart>>::run(
                         move |mut ev: ListenerMut<Pointer<DragStart>>,
                                 mut atoms: AtomStore,
-                                query: Query<&mut ScrollArea>| {
+                                mut query: Query<&mut ScrollArea>| {
                             ev.stop_propagation();
-                            if let Ok(scroll_area) = query.get(id_scroll_area) {
+                            if let Ok(mut scroll_area) = query.get_mut(id_scroll_area) {
                                 handle_thumb_drag_start(
-                                    scroll_area,
+                                    &mut scroll_area,
                                     vertical,
                                     &mut atoms,
                                     drag_state,
@@ -254,14 +385,25 @@ fn scrollbar(mut cx: Cx<ScrollbarProps>) -> impl View {
                         },
                     ),
                     On::<Pointer<DragEnd>>::run(
-                        move |mut ev: ListenerMut<Pointer<DragEnd>>, mut atoms: AtomStore| {
+                        move |mut ev: ListenerMut<Pointer<DragEnd>>,
+                              mut atoms: AtomStore,
+                              mut query: Query<&mut ScrollArea>| {
                             ev.stop_propagation();
+                            if let Ok(mut scroll_area) = query.get_mut(id_scroll_area) {
+                                // Keep scrolling with the momentum of the drag.
+                                scroll_area.end_drag(true);
+                            }
                             handle_thumb_drag_end(&mut atoms, drag_state);
                         },
                     ),
                     On::<Pointer<PointerCancel>>::run(
-                        move |mut ev: ListenerMut<Pointer<DragEnd>>, mut atoms: AtomStore| {
+                        move |mut ev: ListenerMut<Pointer<DragEnd>>,
+                              mut atoms: AtomStore,
+                              mut query: Query<&mut ScrollArea>| {
                             ev.stop_propagation();
+                            if let Ok(mut scroll_area) = query.get_mut(id_scroll_area) {
+                                scroll_area.end_drag(false);
+                            }
                             handle_thumb_drag_end(&mut atoms, drag_state);
                         },
                     ),
@@ -270,11 +412,12 @@ fn scrollbar(mut cx: Cx<ScrollbarProps>) -> impl View {
 }
 
 fn handle_thumb_drag_start(
-    scroll_area: &ScrollArea,
+    scroll_area: &mut ScrollArea,
     vertical: bool,
     atoms: &mut AtomStore,
     drag_state: AtomHandle<DragState>,
 ) {
+    scroll_area.begin_drag();
     if vertical {
         atoms.set(
             drag_state,
@@ -295,23 +438,11 @@ fn handle_thumb_drag_start(
 }
 
 fn handle_thumb_drag(scroll_area: &mut ScrollArea, ds: &DragState, distance: Vec2) {
-    if ds.mode == DragMode::DragY {
-        let left = scroll_area.scroll_left;
-        let top = if scroll_area.visible_size.y > 0. {
-            ds.offset + distance.y * scroll_area.content_size.x / scroll_area.visible_size.y
-        } else {
-            0.
-        };
-        scroll_area.scroll_to(left, top);
-    } else if ds.mode == DragMode::DragX {
-        let top = scroll_area.scroll_top;
-        let left = if scroll_area.visible_size.x > 0. {
-            ds.offset + distance.x * scroll_area.content_size.y / scroll_area.visible_size.x
-        } else {
-            0.
-        };
-        scroll_area.scroll_to(left, top);
-    };
+    match ds.mode {
+        DragMode::DragX => scroll_area.drag_thumb(false, ds.offset, distance.x),
+        DragMode::DragY => scroll_area.drag_thumb(true, ds.offset, distance.y),
+        DragMode::None => {}
+    }
 }
 
 fn handle_thumb_drag_end(atoms: &mut AtomStore, drag_state: AtomHandle<DragState>) {
@@ -324,20 +455,111 @@ fn handle_thumb_drag_end(atoms: &mut AtomStore, drag_state: AtomHandle<DragState
     );
 }
 
+/// How long it takes to scroll by a page when the scrollbar track is clicked, in seconds.
+const PAGE_DURATION: f32 = 0.15;
+
 fn handle_track_click(scroll_area: &mut ScrollArea, vertical: bool, position: Vec2, rect: Rect) {
     if vertical {
         let page_size = scroll_area.visible_size.y;
         if position.y >= rect.max.y {
-            scroll_area.scroll_by(0., page_size);
+            scroll_area.scroll_by_animated(0., page_size, PAGE_DURATION);
         } else if position.y < rect.min.y {
-            scroll_area.scroll_by(0., -page_size);
+            scroll_area.scroll_by_animated(0., -page_size, PAGE_DURATION);
         }
     } else {
         let page_size = scroll_area.visible_size.x;
         if position.x >= rect.max.x {
-            scroll_area.scroll_by(page_size, 0.);
+            scroll_area.scroll_by_animated(page_size, 0., PAGE_DURATION);
         } else if position.x < rect.min.x {
-            scroll_area.scroll_by(-page_size, 0.);
+            scroll_area.scroll_by_animated(-page_size, 0., PAGE_DURATION);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dense_scroll_view(_cx: Cx) -> impl View {
+        scroll_view.bind(ScrollViewProps::<()> {
+            scroll_enable_x: true,
+            scroll_enable_y: true,
+            min_thumb_size: 24.,
+            scrollbar_thickness: 4.,
+            ..default()
+        })
+    }
+
+    #[test]
+    fn test_scrollbar_props() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .add_plugins(QuillPlugin::default())
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<bevy::a11y::Focus>()
+            .add_event::<bevy::input::mouse::MouseWheel>()
+            .add_event::<bevy::input::keyboard::KeyboardInput>();
+        app.world.spawn(ViewHandle::new(dense_scroll_view, ()));
+        app.update();
+
+        let mut scrollbars = app.world.query::<&ScrollBar>();
+        let sizes: Vec<f32> = scrollbars
+            .iter(&app.world)
+            .map(|scrollbar| scrollbar.min_thumb_size)
+            .collect();
+        assert_eq!(sizes, vec![24., 24.]);
+    }
+
+    #[derive(Resource)]
+    struct Shown {
+        view: bool,
+        scrollbars: bool,
+    }
+
+    fn toggled_scroll_view(cx: Cx) -> impl View {
+        let shown = cx.use_resource::<Shown>();
+        If::new(
+            shown.view,
+            scroll_view.bind(ScrollViewProps::<()> {
+                scroll_enable_x: shown.scrollbars,
+                scroll_enable_y: shown.scrollbars,
+                ..default()
+            }),
+            (),
+        )
+    }
+
+    #[test]
+    fn test_scroll_view_leaks() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .add_plugins(QuillPlugin::default())
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<bevy::a11y::Focus>()
+            .add_event::<bevy::input::mouse::MouseWheel>()
+            .add_event::<bevy::input::keyboard::KeyboardInput>()
+            .insert_resource(Shown {
+                view: true,
+                scrollbars: false,
+            });
+        app.world.spawn(ViewHandle::new(toggled_scroll_view, ()));
+        app.update();
+        let without_scrollbars = app.world.entities().len();
+
+        let mut step = |view: bool, scrollbars: bool| {
+            *app.world.resource_mut::<Shown>() = Shown { view, scrollbars };
+            app.update();
+            app.world.entities().len()
+        };
+        let with_scrollbars = step(true, true);
+        for _ in 0..3 {
+            // Disabling the scrollbars releases their entities and drag state.
+            assert_eq!(step(true, false), without_scrollbars);
+            assert_eq!(step(true, true), with_scrollbars);
+
+            // Toggling the scroll view itself doesn't leak.
+            step(false, true);
+            assert_eq!(step(true, true), with_scrollbars);
         }
     }
 }
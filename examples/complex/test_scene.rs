@@ -1,8 +1,15 @@
 use std::f32::consts::PI;
 
 use bevy::{
-    prelude::*, render::{camera::Viewport, render_asset::RenderAssetUsages, render_resource::{Extent3d, TextureDimension, TextureFormat}}
+    prelude::*,
+    render::{
+        camera::Viewport,
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+    window::PrimaryWindow,
 };
+use bevy_quill::{target_window, NodeWindow};
 
 use crate::viewport::*;
 
@@ -83,24 +90,29 @@ pub fn setup(
         },
         PrimaryCamera,
     ));
-
 }
 
 pub(crate) fn update_viewport_inset(
     windows: Query<&Window>,
-    query: Query<(&Node, &GlobalTransform), With<ViewportInsetElement>>,
+    node_window: NodeWindow,
+    query: Query<(Entity, &Node, &GlobalTransform), With<ViewportInsetElement>>,
     mut viewport_inset: ResMut<ViewportInset>,
 ) {
     let mut inset = ViewportInset::default();
     match query.get_single() {
-        Ok((node, transform)) => {
+        Ok((entity, node, transform)) => {
             let position = transform.translation();
             let ui_position = position.truncate();
             let extents = node.size() / 2.0;
             let min = ui_position - extents;
             let max = ui_position + extents;
 
-            let window = windows.single();
+            let Some(window) = node_window
+                .window(entity)
+                .and_then(|window| windows.get(window).ok())
+            else {
+                return;
+            };
             let ww = window.resolution.physical_width() as f32;
             let wh = window.resolution.physical_height() as f32;
             let sf = window.resolution.scale_factor() as f32;
@@ -127,9 +139,15 @@ pub(crate) fn update_viewport_inset(
 pub(crate) fn update_camera_viewport(
     viewport_inset: Res<ViewportInset>,
     windows: Query<&Window>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
     mut camera_query: Query<(&mut Camera, &mut Projection), With<PrimaryCamera>>,
 ) {
-    let window = windows.single();
+    let (mut camera, mut projection) = camera_query.single_mut();
+    let Some(window) = target_window(&camera.target, primary_window.get_single().ok())
+        .and_then(|window| windows.get(window).ok())
+    else {
+        return;
+    };
     let ww = window.resolution.physical_width() as f32;
     let wh = window.resolution.physical_height() as f32;
     let sf = window.resolution.scale_factor() as f32;
@@ -140,7 +158,6 @@ pub(crate) fn update_camera_viewport(
     let vw = (ww - left - right).max(1.);
     let vh = (wh - top - bottom).max(1.);
 
-    let (mut camera, mut projection) = camera_query.single_mut();
     camera.viewport = Some(Viewport {
         physical_position: UVec2::new(left as u32, top as u32),
         physical_size: UVec2::new(vw as u32, vh as u32),
@@ -156,7 +173,18 @@ pub(crate) fn update_camera_viewport(
     }
 }
 
-pub(crate) fn rotate(mut query: Query<&mut Transform, With<Shape>>, time: Res<Time>) {
+/// Whether the shapes are rotating, which is toggled by a checkbox in the side panel.
+#[derive(Resource)]
+pub(crate) struct Rotating(pub bool);
+
+pub(crate) fn rotate(
+    mut query: Query<&mut Transform, With<Shape>>,
+    time: Res<Time>,
+    rotating: Res<Rotating>,
+) {
+    if !rotating.0 {
+        return;
+    }
     for mut transform in &mut query {
         transform.rotate_y(time.delta_seconds() / 2.);
     }
@@ -187,6 +215,6 @@ fn uv_debug_texture() -> Image {
         TextureDimension::D2,
         &texture_data,
         TextureFormat::Rgba8UnormSrgb,
-        RenderAssetUsages::default()
+        RenderAssetUsages::default(),
     )
 }
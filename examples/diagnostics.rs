@@ -0,0 +1,139 @@
+//! Example of an overlay which displays the Quill frame diagnostics, rendered with Quill itself.
+//!
+//! The overlay reads the diagnostics store, which changes every frame, so the overlay presenter
+//! accounts for one of the presenters rebuilt each frame.
+
+use std::time::Duration;
+
+use bevy::{
+    diagnostic::{DiagnosticPath, DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+    ui,
+};
+use bevy_quill::{prelude::*, QuillDiagnosticsPlugin};
+use static_init::dynamic;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((
+            QuillPlugin::default(),
+            QuillDiagnosticsPlugin,
+            FrameTimeDiagnosticsPlugin,
+        ))
+        .add_systems(Startup, setup_view_root)
+        .add_systems(Update, bevy::window::close_on_esc)
+        .run();
+}
+
+#[dynamic]
+static STYLE_MAIN: StyleHandle = StyleHandle::build(|ss| {
+    ss.position(ui::PositionType::Absolute)
+        .left(0)
+        .top(0)
+        .right(0)
+        .bottom(0)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .padding(8)
+        .gap(4)
+        .background_color("#111")
+});
+
+#[dynamic]
+static STYLE_OVERLAY: StyleHandle = StyleHandle::build(|ss| {
+    ss.position(ui::PositionType::Absolute)
+        .right(8)
+        .top(8)
+        .display(ui::Display::Grid)
+        .grid_template_columns(vec![ui::RepeatedGridTrack::auto(2)])
+        .column_gap(12)
+        .padding(6)
+        .background_color("#000c")
+        .border(1)
+        .border_color("#444")
+        .font_size(14.)
+});
+
+#[dynamic]
+static STYLE_VALUE: StyleHandle = StyleHandle::build(|ss| ss.justify_self(ui::JustifySelf::End));
+
+fn setup_view_root(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+    commands.spawn(ViewHandle::new(root_presenter, ()));
+}
+
+fn root_presenter(_: Cx) -> impl View {
+    Element::new()
+        .styled(STYLE_MAIN.clone())
+        .children((ticker.bind(()), diagnostics_overlay.bind(())))
+}
+
+/// Some content which is rebuilt once a second, to give the diagnostics something to count.
+fn ticker(mut cx: Cx) -> impl View {
+    let seconds = cx.use_interval(Duration::from_secs(1));
+    For::index(&(0..(seconds % 10 + 1)).collect::<Vec<_>>(), |n, _| {
+        format!("Row {}", n)
+    })
+}
+
+/// The diagnostics to display, with their labels and the number of decimal places to show.
+const ROWS: &[(&str, DiagnosticPath, usize)] = &[
+    ("FPS", FrameTimeDiagnosticsPlugin::FPS, 0),
+    (
+        "Presenters built",
+        QuillDiagnosticsPlugin::PRESENTERS_BUILT,
+        1,
+    ),
+    ("Views attached", QuillDiagnosticsPlugin::VIEWS_ATTACHED, 1),
+    (
+        "Styles computed",
+        QuillDiagnosticsPlugin::STYLES_COMPUTED,
+        1,
+    ),
+    (
+        "Active animations",
+        QuillDiagnosticsPlugin::ACTIVE_ANIMATIONS,
+        0,
+    ),
+    ("Build views", QuillDiagnosticsPlugin::BUILD_VIEWS_TIME, 3),
+    (
+        "Attach children",
+        QuillDiagnosticsPlugin::ATTACH_CHILDREN_TIME,
+        3,
+    ),
+    (
+        "Compute styles",
+        QuillDiagnosticsPlugin::COMPUTE_STYLES_TIME,
+        3,
+    ),
+    ("Animate", QuillDiagnosticsPlugin::ANIMATE_TIME, 3),
+];
+
+/// Overlay which shows the smoothed value of each diagnostic.
+fn diagnostics_overlay(cx: Cx) -> impl View {
+    let store = cx.use_resource::<DiagnosticsStore>();
+    let rows: Vec<(String, String)> = ROWS
+        .iter()
+        .map(|(label, path, precision)| {
+            let value = match store.get(path) {
+                Some(diagnostic) => match diagnostic.smoothed() {
+                    Some(value) => format!("{:.*}{}", precision, value, diagnostic.suffix),
+                    None => "-".to_string(),
+                },
+                None => "-".to_string(),
+            };
+            (label.to_string(), value)
+        })
+        .collect();
+    Element::new()
+        .styled(STYLE_OVERLAY.clone())
+        .children(For::each(&rows, |(label, value)| {
+            Fragment::new((
+                label.clone(),
+                Element::new()
+                    .styled(STYLE_VALUE.clone())
+                    .children(value.clone()),
+            ))
+        }))
+}
@@ -10,13 +10,14 @@ use bevy::{
         render_resource::{Extent3d, TextureDimension, TextureFormat},
     },
     ui,
+    window::PrimaryWindow,
 };
 use bevy_mod_picking::{
     events::PointerCancel,
     picking_core::{CorePlugin, InteractionPlugin},
     prelude::*,
 };
-use bevy_quill::prelude::*;
+use bevy_quill::{prelude::*, target_window, NodeWindow};
 use static_init::dynamic;
 
 fn main() {
@@ -26,7 +27,7 @@ fn main() {
         .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
         .add_plugins((CorePlugin, InputPlugin, InteractionPlugin, BevyUiBackend))
         .add_plugins(EventListenerPlugin::<Clicked>::default())
-        .add_plugins(QuillPlugin)
+        .add_plugins(QuillPlugin::default())
         .add_systems(Startup, (setup, setup_view_root))
         .add_event::<Clicked>()
         .add_systems(
@@ -327,19 +328,25 @@ fn setup(
 
 pub fn update_viewport_inset(
     windows: Query<&Window>,
-    query: Query<(&Node, &GlobalTransform), With<ViewportInsetElement>>,
+    node_window: NodeWindow,
+    query: Query<(Entity, &Node, &GlobalTransform), With<ViewportInsetElement>>,
     mut viewport_inset: ResMut<ViewportInset>,
 ) {
     let mut inset = ViewportInset::default();
     match query.get_single() {
-        Ok((node, transform)) => {
+        Ok((entity, node, transform)) => {
             let position = transform.translation();
             let ui_position = position.truncate();
             let extents = node.size() / 2.0;
             let min = ui_position - extents;
             let max = ui_position + extents;
 
-            let window = windows.single();
+            let Some(window) = node_window
+                .window(entity)
+                .and_then(|window| windows.get(window).ok())
+            else {
+                return;
+            };
             let ww = window.resolution.physical_width() as f32;
             let wh = window.resolution.physical_height() as f32;
             let sf = window.resolution.scale_factor() as f32;
@@ -366,9 +373,15 @@ pub fn update_viewport_inset(
 pub fn update_camera_viewport(
     viewport_inset: Res<ViewportInset>,
     windows: Query<&Window>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
     mut camera_query: Query<(&mut Camera, &mut Projection), With<PrimaryCamera>>,
 ) {
-    let window = windows.single();
+    let (mut camera, mut projection) = camera_query.single_mut();
+    let Some(window) = target_window(&camera.target, primary_window.get_single().ok())
+        .and_then(|window| windows.get(window).ok())
+    else {
+        return;
+    };
     let ww = window.resolution.physical_width() as f32;
     let wh = window.resolution.physical_height() as f32;
     let sf = window.resolution.scale_factor() as f32;
@@ -379,7 +392,6 @@ pub fn update_camera_viewport(
     let vw = (ww - left - right).max(1.);
     let vh = (wh - top - bottom).max(1.);
 
-    let (mut camera, mut projection) = camera_query.single_mut();
     camera.viewport = Some(Viewport {
         physical_position: UVec2::new(left as u32, top as u32),
         physical_size: UVec2::new(vw as u32, vh as u32),
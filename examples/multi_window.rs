@@ -0,0 +1,98 @@
+//! Example of a UI spread across two windows, with a secondary tool palette window which has its
+//! own camera and view root.
+
+use bevy::{
+    prelude::*,
+    render::camera::RenderTarget,
+    ui,
+    window::{PrimaryWindow, WindowRef},
+};
+use bevy_quill::prelude::*;
+use static_init::dynamic;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(QuillPlugin::default())
+        .add_systems(Startup, setup_view_roots)
+        .add_systems(Update, bevy::window::close_on_esc)
+        .run();
+}
+
+#[dynamic]
+static STYLE_MAIN: StyleHandle = StyleHandle::build(|ss| {
+    ss.position(ui::PositionType::Absolute)
+        .left(0)
+        .top(0)
+        .right(0)
+        .bottom(0)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .padding(8)
+        .gap(8)
+        .background_color("#111")
+});
+
+#[dynamic]
+static STYLE_PALETTE: StyleHandle = StyleHandle::build(|ss| {
+    ss.position(ui::PositionType::Absolute)
+        .left(0)
+        .top(0)
+        .right(0)
+        .bottom(0)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .padding(8)
+        .gap(8)
+        .background_color("#223")
+});
+
+fn setup_view_roots(mut commands: Commands, primary: Query<Entity, With<PrimaryWindow>>) {
+    // The main window uses the default UI camera.
+    commands.spawn(Camera2dBundle::default());
+    commands.spawn(ViewHandle::new(main_presenter, primary.single()));
+
+    // The palette window needs a camera which renders to it, and its view root names that
+    // camera.
+    let palette = commands
+        .spawn(Window {
+            title: "Palette".to_string(),
+            resolution: (300., 400.).into(),
+            ..default()
+        })
+        .id();
+    let palette_camera = commands
+        .spawn(Camera2dBundle {
+            camera: Camera {
+                target: RenderTarget::Window(WindowRef::Entity(palette)),
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+    commands.spawn((
+        ViewHandle::new(palette_presenter, palette),
+        TargetCamera(palette_camera),
+    ));
+}
+
+fn main_presenter(cx: Cx<Entity>) -> impl View {
+    Element::new()
+        .styled(STYLE_MAIN.clone())
+        .children(("Main window", window_size.bind(*cx.props)))
+}
+
+fn palette_presenter(cx: Cx<Entity>) -> impl View {
+    Element::new()
+        .styled(STYLE_PALETTE.clone())
+        .children(("Palette window", window_size.bind(*cx.props)))
+}
+
+/// Displays the size of a window, which updates as the window is resized.
+fn window_size(mut cx: Cx<Entity>) -> impl View {
+    let info = cx.use_window_entity(*cx.props);
+    format!(
+        "{} x {} @ {}x",
+        info.logical_size.x, info.logical_size.y, info.scale_factor
+    )
+}
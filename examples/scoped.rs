@@ -14,7 +14,7 @@ fn main() {
         .init_resource::<Counter>()
         .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
         .add_plugins((CorePlugin, InputPlugin, InteractionPlugin, BevyUiBackend))
-        .add_plugins(QuillPlugin)
+        .add_plugins(QuillPlugin::default())
         .add_systems(Startup, (setup, setup_view_root))
         .add_systems(Update, (bevy::window::close_on_esc, update_counter))
         .run();
@@ -53,7 +53,7 @@ fn setup_view_root(mut commands: Commands) {
     commands.spawn(ViewHandle::new(ui_main, ()));
 }
 
-pub const COUNTER: ScopedValueKey<u32> = ScopedValueKey::new("counter");
+pub const COUNTER: ScopedValueKey<u32> = ScopedValueKey::with_default("counter", 0);
 
 fn ui_main(mut cx: Cx) -> impl View {
     let counter = cx.use_resource::<Counter>();
@@ -67,7 +67,7 @@ fn ui_main(mut cx: Cx) -> impl View {
 }
 
 fn nested(cx: Cx) -> impl View {
-    let counter = cx.get_scoped_value(COUNTER).unwrap();
+    let counter = cx.consume(COUNTER);
     Element::new()
         .styled(STYLE_VSPLITTER.clone())
         .children(format!("scoped counter: {}", counter))
@@ -0,0 +1,86 @@
+//! Example of using a SpatialElement to manage 3D scene entities.
+
+use bevy::prelude::*;
+use bevy_quill::prelude::*;
+
+fn main() {
+    App::new()
+        .init_resource::<CubeRow>()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(QuillPlugin::default())
+        .add_systems(Startup, (setup, setup_view_root))
+        .add_systems(Update, (bevy::window::close_on_esc, update_row))
+        .run();
+}
+
+fn setup_view_root(mut commands: Commands) {
+    commands.spawn(ViewHandle::new(root_presenter, ()));
+}
+
+fn root_presenter(cx: Cx) -> impl View {
+    let row = cx.use_resource::<CubeRow>();
+    let assets = cx.use_resource::<CubeAssets>().clone();
+    SpatialElement::new().children(For::each(&row.columns, move |column| {
+        SpatialElement::new().insert((
+            assets.mesh.clone(),
+            assets.material.clone(),
+            Transform::from_xyz(*column as f32 * 1.5 - 6., 0.5, 0.),
+        ))
+    }))
+}
+
+/// Column positions of the cubes in the row.
+#[derive(Resource, Default)]
+pub struct CubeRow {
+    pub columns: Vec<i32>,
+}
+
+#[derive(Resource, Clone)]
+struct CubeAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+/// Press Space to add a cube, Backspace to remove one.
+fn update_row(mut row: ResMut<CubeRow>, key: Res<ButtonInput<KeyCode>>) {
+    if key.just_pressed(KeyCode::Space) && row.columns.len() < 9 {
+        let next = row.columns.len() as i32;
+        row.columns.push(next);
+    } else if key.just_pressed(KeyCode::Backspace) {
+        row.columns.pop();
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(CubeAssets {
+        mesh: meshes.add(Cuboid::new(1., 1., 1.)),
+        material: materials.add(Color::rgb(0.8, 0.7, 0.6)),
+    });
+
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            intensity: 9_000_000.0,
+            range: 100.,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(8.0, 16.0, 8.0),
+        ..default()
+    });
+
+    // ground plane
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Plane3d::default().mesh().size(50.0, 50.0)),
+        material: materials.add(Color::SILVER),
+        ..default()
+    });
+
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 6., 12.0).looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+        ..default()
+    });
+}
@@ -0,0 +1,60 @@
+//! Example of displaying a fallback while a file is loaded asynchronously.
+
+use bevy::{prelude::*, ui};
+use bevy_mod_picking::{
+    backends::bevy_ui::BevyUiBackend,
+    input::InputPlugin,
+    picking_core::{CorePlugin, InteractionPlugin},
+};
+use bevy_quill::prelude::*;
+use static_init::dynamic;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((CorePlugin, InputPlugin, InteractionPlugin, BevyUiBackend))
+        .add_plugins(QuillPlugin::default())
+        .add_systems(Startup, (setup, setup_view_root))
+        .add_systems(Update, bevy::window::close_on_esc)
+        .run();
+}
+
+#[dynamic]
+static STYLE_MAIN: StyleHandle = StyleHandle::build(|ss| {
+    ss.position(ui::PositionType::Absolute)
+        .left(10.)
+        .top(10.)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+});
+
+const CONFIG_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/data/config.ron");
+
+fn setup_view_root(mut commands: Commands) {
+    commands.spawn(ViewHandle::new(ui_main, ()));
+}
+
+fn ui_main(mut cx: Cx) -> impl View {
+    let config = cx.use_task(
+        || async {
+            // Simulate a slow load, so that the fallback is visible.
+            std::thread::sleep(std::time::Duration::from_secs(1));
+            std::fs::read_to_string(CONFIG_PATH).map_err(|err| err.to_string())
+        },
+        (),
+    );
+    Element::new().styled(STYLE_MAIN.clone()).children((
+        format!("{}:", CONFIG_PATH),
+        Suspense::new(config, "Loading...", |result| {
+            let lines: Vec<String> = match result {
+                Ok(text) => text.lines().map(String::from).collect(),
+                Err(err) => vec![format!("Error: {}", err)],
+            };
+            For::index(&lines, |line, _| line.clone())
+        }),
+    ))
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
@@ -0,0 +1,143 @@
+//! Example of a view hierarchy rendered to a texture which is displayed on a quad in the 3D
+//! scene, alongside a HUD which is rendered by the default UI camera.
+
+use std::time::Duration;
+
+use bevy::{
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+    },
+    ui,
+};
+use bevy_quill::prelude::*;
+use static_init::dynamic;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(QuillPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (bevy::window::close_on_esc, rotate_screen))
+        .run();
+}
+
+#[dynamic]
+static STYLE_HUD: StyleHandle = StyleHandle::build(|ss| {
+    ss.position(ui::PositionType::Absolute)
+        .left(8)
+        .top(8)
+        .padding(6)
+        .background_color("#000a")
+});
+
+#[dynamic]
+static STYLE_SCREEN: StyleHandle = StyleHandle::build(|ss| {
+    ss.position(ui::PositionType::Absolute)
+        .left(0)
+        .top(0)
+        .right(0)
+        .bottom(0)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Center)
+        .justify_content(ui::JustifyContent::Center)
+        .gap(8)
+        .background_color("#234")
+        .font_size(32.)
+});
+
+/// Marker for the quad which displays the texture.
+#[derive(Component)]
+struct Screen;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    // The texture which the in-world view is rendered to.
+    let size = Extent3d {
+        width: 512,
+        height: 384,
+        ..default()
+    };
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: None,
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        ..default()
+    };
+    image.resize(size);
+    let image = images.add(image);
+
+    // A camera which renders only the in-world view to the texture. It runs before the main
+    // camera, so that the texture is ready when the scene is drawn.
+    let texture_camera = commands
+        .spawn(Camera2dBundle {
+            camera: Camera {
+                order: -1,
+                target: RenderTarget::Image(image.clone()),
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+    commands.spawn((
+        ViewHandle::new(screen_presenter, ()),
+        TargetCamera(texture_camera),
+    ));
+
+    // The 3D scene, with a quad showing the texture.
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Rectangle::new(4., 3.)),
+            material: materials.add(StandardMaterial {
+                base_color_texture: Some(image),
+                unlit: true,
+                ..default()
+            }),
+            ..default()
+        },
+        Screen,
+    ));
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0., 0., 6.).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+
+    // The HUD is displayed by the main camera, since its root has no target camera.
+    commands.spawn(ViewHandle::new(hud_presenter, ()));
+}
+
+fn hud_presenter(_: Cx) -> impl View {
+    Element::new()
+        .styled(STYLE_HUD.clone())
+        .children("This text is on the HUD")
+}
+
+fn screen_presenter(mut cx: Cx) -> impl View {
+    let seconds = cx.use_interval(Duration::from_secs(1));
+    Element::new()
+        .styled(STYLE_SCREEN.clone())
+        .children(("This text is on a screen", format!("{} seconds", seconds)))
+}
+
+fn rotate_screen(mut query: Query<&mut Transform, With<Screen>>, time: Res<Time>) {
+    for mut transform in query.iter_mut() {
+        transform.rotation = Quat::from_rotation_y(time.elapsed_seconds().sin() * 0.5);
+    }
+}
@@ -0,0 +1,129 @@
+//! Example of a virtualized list, which scrolls through a large number of rows while only
+//! building the rows which are visible.
+
+use bevy::{prelude::*, ui};
+use bevy_mod_picking::{
+    backends::bevy_ui::BevyUiBackend,
+    input::InputPlugin,
+    picking_core::{CorePlugin, InteractionPlugin},
+    prelude::*,
+};
+use bevy_quill::{prelude::*, ScrollArea, ScrollContent, ScrollWheel};
+use static_init::dynamic;
+
+/// Number of rows in the list.
+const ROW_COUNT: usize = 50_000;
+
+/// Height of each row, in pixels.
+const ROW_HEIGHT: f32 = 20.;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((CorePlugin, InputPlugin, InteractionPlugin, BevyUiBackend))
+        .add_plugins(QuillPlugin::default())
+        .add_systems(Startup, setup_view_root)
+        .add_systems(Update, bevy::window::close_on_esc)
+        .run();
+}
+
+#[dynamic]
+static STYLE_MAIN: StyleHandle = StyleHandle::build(|ss| {
+    ss.position(ui::PositionType::Absolute)
+        .left(0)
+        .top(0)
+        .right(0)
+        .bottom(0)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .padding(8)
+        .gap(8)
+        .background_color("#111")
+});
+
+/// The scrolling region which defines the clipping bounds.
+#[dynamic]
+static STYLE_SCROLL_REGION: StyleHandle = StyleHandle::build(|ss| {
+    ss.flex_grow(1.)
+        .border(1)
+        .border_color("#333")
+        .overflow(ui::OverflowAxis::Clip)
+});
+
+/// The scrolling content which is clipped.
+#[dynamic]
+static STYLE_SCROLL_CONTENT: StyleHandle = StyleHandle::build(|ss| {
+    ss.position(ui::PositionType::Absolute)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .min_width(ui::Val::Percent(100.))
+});
+
+#[dynamic]
+static STYLE_ROW_EVEN: StyleHandle = StyleHandle::build(|ss| {
+    ss.height(ROW_HEIGHT)
+        .flex_shrink(0.)
+        .padding_left(4)
+        .background_color("#1c1c22")
+});
+
+#[dynamic]
+static STYLE_ROW_ODD: StyleHandle = StyleHandle::build(|ss| {
+    ss.height(ROW_HEIGHT)
+        .flex_shrink(0.)
+        .padding_left(4)
+        .background_color("#22222a")
+});
+
+fn setup_view_root(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+    commands.spawn(ViewHandle::new(root_presenter, ()));
+}
+
+fn root_presenter(mut cx: Cx) -> impl View {
+    let id_scroll_area = cx.create_entity();
+    Element::new().styled(STYLE_MAIN.clone()).children((
+        format!("{} rows", ROW_COUNT),
+        RefElement::new(id_scroll_area)
+            .with_memo(
+                |mut e| {
+                    e.insert((
+                        ScrollArea::default(),
+                        On::<ScrollWheel>::listener_component_mut::<ScrollArea>(
+                            move |ev, scrolling| {
+                                ev.stop_propagation();
+                                scrolling.scroll_by(-ev.delta.x, -ev.delta.y);
+                            },
+                        ),
+                    ));
+                },
+                (),
+            )
+            .styled(STYLE_SCROLL_REGION.clone())
+            .children(
+                Element::new()
+                    .insert(ScrollContent)
+                    .styled(STYLE_SCROLL_CONTENT.clone())
+                    .children(rows.bind(id_scroll_area)),
+            ),
+    ))
+}
+
+/// Presenter for the rows of the list. This reruns whenever the scroll area is scrolled or
+/// resized, and only builds the rows which are visible.
+fn rows(cx: Cx<Entity>) -> impl View {
+    let list = VirtualList::new(ROW_COUNT, ROW_HEIGHT, |index| {
+        Element::new()
+            .styled(if index % 2 == 0 {
+                STYLE_ROW_EVEN.clone()
+            } else {
+                STYLE_ROW_ODD.clone()
+            })
+            .children(format!("Row {}", index))
+    })
+    .overscan(8);
+    match cx.use_component::<ScrollArea>(*cx.props) {
+        Some(scroll_area) => list.scroll_area(scroll_area),
+        None => list,
+    }
+}
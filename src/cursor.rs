@@ -1,4 +1,10 @@
-//! Cursor definitions (not done yet)
+//! Cursor definitions, and the arbitration of which cursor is displayed.
+
+use crate::{hover::ignore_picking, HoverState};
+use bevy::{
+    prelude::*,
+    window::{CursorIcon, PrimaryWindow},
+};
 
 /// 2D Cursor type - subset of standard CSS cursor types
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -48,3 +54,411 @@ pub enum Cursor {
     /// Magnifying Glass with Minus
     ZoomOut,
 }
+
+impl Cursor {
+    /// The window cursor icon for this cursor, or `None` if the cursor should be hidden.
+    fn icon(self) -> Option<CursorIcon> {
+        Some(match self {
+            Cursor::None => return None,
+            // Custom images are drawn by `update_cursor`; this is only used if there is no image.
+            Cursor::CustomImage | Cursor::Default => CursorIcon::Default,
+            Cursor::Pointer => CursorIcon::Pointer,
+            Cursor::Wait => CursorIcon::Wait,
+            Cursor::Crosshair => CursorIcon::Crosshair,
+            Cursor::Text => CursorIcon::Text,
+            Cursor::VerticalText => CursorIcon::VerticalText,
+            Cursor::Move => CursorIcon::Move,
+            Cursor::NotAllowed => CursorIcon::NotAllowed,
+            Cursor::Grab => CursorIcon::Grab,
+            Cursor::ColResize => CursorIcon::ColResize,
+            Cursor::RowResize => CursorIcon::RowResize,
+            Cursor::ZoomIn => CursorIcon::ZoomIn,
+            Cursor::ZoomOut => CursorIcon::ZoomOut,
+        })
+    }
+}
+
+/// The priority of a cursor request. When there are several requests, the one with the highest
+/// priority is displayed; among requests with the same priority, the most recent one wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CursorPriority {
+    /// The cursor of the element under the pointer, as set by its style.
+    Hover,
+    /// A cursor which should be kept for the duration of an interaction, such as a drag, even
+    /// if the pointer leaves the element.
+    Drag,
+}
+
+/// Identifies a request made with [`CursorStack::push`], so that it can be removed later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CursorRequestId(u32);
+
+/// Resource which decides which cursor is displayed in the primary window. Cursors set by the
+/// `cursor` style property are requested at [`CursorPriority::Hover`] while the pointer is over
+/// the element, and each [`CursorLock`] component is a request at [`CursorPriority::Drag`].
+/// Other requests can be pushed and popped explicitly.
+///
+/// While there are no requests, the window cursor is left alone, so that the application can set
+/// it itself.
+#[derive(Resource, Default)]
+pub struct CursorStack {
+    requests: Vec<(CursorRequestId, CursorPriority, Cursor)>,
+    next_id: u32,
+    /// The window cursor icon and visibility from before the first request, which are restored
+    /// once there are no more requests.
+    app_cursor: Option<(CursorIcon, bool)>,
+}
+
+impl CursorStack {
+    /// Request that `cursor` be displayed. The request remains until it is popped.
+    pub fn push(&mut self, priority: CursorPriority, cursor: Cursor) -> CursorRequestId {
+        let id = CursorRequestId(self.next_id);
+        self.next_id = self.next_id.wrapping_add(1);
+        self.requests.push((id, priority, cursor));
+        id
+    }
+
+    /// Remove a request. Returns false if there was no such request.
+    pub fn pop(&mut self, id: CursorRequestId) -> bool {
+        let len = self.requests.len();
+        self.requests.retain(|(req, _, _)| *req != id);
+        self.requests.len() != len
+    }
+
+    /// Returns the cursor of the highest priority request, if there are any requests.
+    pub fn top(&self) -> Option<Cursor> {
+        self.resolve(None, None)
+    }
+
+    /// Returns the cursor to display, given the cursor requested by the hovered element and by
+    /// any [`CursorLock`].
+    fn resolve(&self, hover: Option<Cursor>, lock: Option<Cursor>) -> Option<Cursor> {
+        // `max_by_key` returns the last of several equal elements, so later requests win.
+        hover
+            .map(|cursor| (CursorPriority::Hover, cursor))
+            .into_iter()
+            .chain(
+                self.requests
+                    .iter()
+                    .map(|(_, priority, cursor)| (*priority, *cursor)),
+            )
+            .chain(lock.map(|cursor| (CursorPriority::Drag, cursor)))
+            .max_by_key(|(priority, _)| *priority)
+            .map(|(_, cursor)| cursor)
+    }
+}
+
+/// Component which keeps the given cursor displayed for as long as it is present, regardless
+/// of what is under the pointer. Widgets insert this when a drag starts, and remove it when the
+/// drag ends or is cancelled.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct CursorLock(pub Cursor);
+
+/// The cursor of an element, as set by the `cursor`, `cursor_image` and `cursor_offset` style
+/// properties.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub(crate) struct StyleCursor {
+    pub(crate) cursor: Cursor,
+    pub(crate) image: Option<Handle<Image>>,
+    /// The position of the hotspot within the image.
+    pub(crate) offset: IVec2,
+}
+
+/// Marker for the node which draws a custom image cursor. Not all platforms support setting
+/// the window cursor to an arbitrary image, so instead the OS cursor is hidden and an image
+/// node follows the pointer.
+#[derive(Component)]
+pub(crate) struct SoftwareCursor;
+
+/// Apply the cursor chosen by the [`CursorStack`] to the primary window.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_cursor(
+    mut commands: Commands,
+    mut stack: ResMut<CursorStack>,
+    hover_state: Option<Res<HoverState>>,
+    parents: Query<&Parent>,
+    style_cursors: Query<&StyleCursor>,
+    locks: Query<&CursorLock>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut software_cursor: Query<(Entity, &mut Style, &mut UiImage), With<SoftwareCursor>>,
+) {
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+
+    // Find the cursor of the topmost hovered element, or of its nearest ancestor which has one.
+    let hover = hover_state
+        .as_ref()
+        .and_then(|hover_state| hover_state.topmost())
+        .and_then(|entity| {
+            std::iter::once(entity)
+                .chain(parents.iter_ancestors(entity))
+                .find_map(|e| style_cursors.get(e).ok())
+        });
+    let lock = locks.iter().last().map(|lock| lock.0);
+
+    let cursor = stack.resolve(hover.map(|style| style.cursor), lock);
+
+    // Custom images come from the hovered element's style, so a custom cursor requested in
+    // some other way falls back to the default cursor.
+    let image = match (cursor, hover) {
+        (
+            Some(Cursor::CustomImage),
+            Some(StyleCursor {
+                image: Some(image),
+                offset,
+                ..
+            }),
+        ) => window
+            .cursor_position()
+            .map(|position| (image, position - offset.as_vec2())),
+        _ => None,
+    };
+    match (image, software_cursor.get_single_mut()) {
+        (Some((image, position)), Ok((_, mut style, mut ui_image))) => {
+            let (left, top) = (Val::Px(position.x), Val::Px(position.y));
+            if style.left != left || style.top != top {
+                style.left = left;
+                style.top = top;
+            }
+            if ui_image.texture != *image {
+                ui_image.texture = image.clone();
+            }
+        }
+        (Some((image, position)), Err(_)) => {
+            commands.spawn((
+                ImageBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(position.x),
+                        top: Val::Px(position.y),
+                        ..default()
+                    },
+                    image: UiImage::new(image.clone()),
+                    z_index: ZIndex::Global(i32::MAX),
+                    ..default()
+                },
+                ignore_picking(),
+                SoftwareCursor,
+                Name::new("SoftwareCursor"),
+            ));
+        }
+        (None, Ok((entity, _, _))) => {
+            commands.entity(entity).despawn();
+        }
+        (None, Err(_)) => {}
+    }
+
+    let (icon, visible) = match cursor {
+        // Nothing wants a cursor: restore the application's cursor if we replaced it.
+        None => match stack.app_cursor.take() {
+            Some(app_cursor) => app_cursor,
+            None => return,
+        },
+        Some(cursor) => {
+            if stack.app_cursor.is_none() {
+                stack.app_cursor = Some((window.cursor.icon, window.cursor.visible));
+            }
+            match cursor.icon() {
+                Some(icon) if image.is_none() => (icon, true),
+                _ => (window.cursor.icon, false),
+            }
+        }
+    };
+    if window.cursor.icon != icon || window.cursor.visible != visible {
+        window.cursor.icon = icon;
+        window.cursor.visible = visible;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn test_cursor_stack() {
+        let mut stack = CursorStack::default();
+        assert_eq!(stack.top(), None);
+        let hover = stack.push(CursorPriority::Hover, Cursor::Pointer);
+        let drag = stack.push(CursorPriority::Drag, Cursor::ColResize);
+        stack.push(CursorPriority::Hover, Cursor::Text);
+        // Higher priority wins, even over later requests.
+        assert_eq!(stack.top(), Some(Cursor::ColResize));
+        assert!(stack.pop(drag));
+        assert!(!stack.pop(drag));
+        // Among equal priorities, the latest request wins.
+        assert_eq!(stack.top(), Some(Cursor::Text));
+        assert_eq!(stack.resolve(Some(Cursor::Move), None), Some(Cursor::Text));
+        assert_eq!(
+            stack.resolve(Some(Cursor::Move), Some(Cursor::RowResize)),
+            Some(Cursor::RowResize)
+        );
+        stack.pop(hover);
+        assert_eq!(stack.top(), Some(Cursor::Text));
+    }
+
+    #[test]
+    fn test_cursor_lock() {
+        let mut world = World::new();
+        world.init_resource::<CursorStack>();
+        world.init_resource::<HoverState>();
+        let window = world.spawn((Window::default(), PrimaryWindow)).id();
+        let splitter = world
+            .spawn(StyleCursor {
+                cursor: Cursor::ColResize,
+                image: None,
+                offset: IVec2::ZERO,
+            })
+            .id();
+        let handle = world.spawn_empty().id();
+        world.entity_mut(splitter).add_child(handle);
+        let hover = |world: &mut World, entity: Option<Entity>| {
+            world.resource_mut::<HoverState>().set_hovered(entity);
+            world.run_system_once(update_cursor);
+            world.get::<Window>(window).unwrap().cursor.icon
+        };
+
+        // The cursor is inherited from the hovered element's ancestors.
+        assert_eq!(hover(&mut world, Some(handle)), CursorIcon::ColResize);
+        assert_eq!(hover(&mut world, None), CursorIcon::Default);
+
+        // During a drag, the cursor is kept when the pointer leaves the element.
+        world
+            .entity_mut(splitter)
+            .insert(CursorLock(Cursor::ColResize));
+        assert_eq!(hover(&mut world, None), CursorIcon::ColResize);
+        world.entity_mut(splitter).remove::<CursorLock>();
+        assert_eq!(hover(&mut world, None), CursorIcon::Default);
+
+        // Hiding the cursor.
+        let id = world
+            .resource_mut::<CursorStack>()
+            .push(CursorPriority::Drag, Cursor::None);
+        hover(&mut world, Some(handle));
+        assert!(!world.get::<Window>(window).unwrap().cursor.visible);
+        world.resource_mut::<CursorStack>().pop(id);
+        assert_eq!(hover(&mut world, Some(handle)), CursorIcon::ColResize);
+        assert!(world.get::<Window>(window).unwrap().cursor.visible);
+    }
+
+    #[test]
+    fn test_app_cursor() {
+        let mut world = World::new();
+        world.init_resource::<CursorStack>();
+        world.init_resource::<HoverState>();
+        let window = world.spawn((Window::default(), PrimaryWindow)).id();
+        let splitter = world
+            .spawn(StyleCursor {
+                cursor: Cursor::ColResize,
+                image: None,
+                offset: IVec2::ZERO,
+            })
+            .id();
+        let hover = |world: &mut World, entity: Option<Entity>| {
+            world.resource_mut::<HoverState>().set_hovered(entity);
+            world.run_system_once(update_cursor);
+            world.get::<Window>(window).unwrap().cursor.icon
+        };
+
+        // A cursor set by the application is left alone while nothing requests one.
+        world.get_mut::<Window>(window).unwrap().cursor.icon = CursorIcon::Wait;
+        assert_eq!(hover(&mut world, None), CursorIcon::Wait);
+        world.get_mut::<Window>(window).unwrap().cursor.visible = false;
+        assert_eq!(hover(&mut world, None), CursorIcon::Wait);
+        assert!(!world.get::<Window>(window).unwrap().cursor.visible);
+
+        // It is replaced while hovering, and restored afterwards.
+        assert_eq!(hover(&mut world, Some(splitter)), CursorIcon::ColResize);
+        assert!(world.get::<Window>(window).unwrap().cursor.visible);
+        assert_eq!(hover(&mut world, None), CursorIcon::Wait);
+        assert!(!world.get::<Window>(window).unwrap().cursor.visible);
+
+        // Changes made by the application after that are kept.
+        world.get_mut::<Window>(window).unwrap().cursor.icon = CursorIcon::Move;
+        assert_eq!(hover(&mut world, None), CursorIcon::Move);
+    }
+
+    #[test]
+    fn test_topmost_hover() {
+        let mut world = World::new();
+        world.init_resource::<CursorStack>();
+        world.init_resource::<HoverState>();
+        let window = world.spawn((Window::default(), PrimaryWindow)).id();
+        let style_cursor = |cursor| StyleCursor {
+            cursor,
+            image: None,
+            offset: IVec2::ZERO,
+        };
+        // A menu floating over a deeply nested text field.
+        let menu = world.spawn(style_cursor(Cursor::Pointer)).id();
+        let field = world.spawn(style_cursor(Cursor::Text)).id();
+        let parent = world.spawn_empty().add_child(field).id();
+        world.spawn_empty().add_child(parent);
+
+        // The topmost entity wins, regardless of its depth in the hierarchy.
+        world
+            .resource_mut::<HoverState>()
+            .set_hovered([menu, field]);
+        world.run_system_once(update_cursor);
+        let icon = world.get::<Window>(window).unwrap().cursor.icon;
+        assert_eq!(icon, CursorIcon::Pointer);
+        world
+            .resource_mut::<HoverState>()
+            .set_hovered([field, menu]);
+        world.run_system_once(update_cursor);
+        let icon = world.get::<Window>(window).unwrap().cursor.icon;
+        assert_eq!(icon, CursorIcon::Text);
+    }
+
+    #[test]
+    fn test_software_cursor() {
+        let mut world = World::new();
+        world.init_resource::<CursorStack>();
+        world.init_resource::<HoverState>();
+        let mut window = Window::default();
+        window.set_cursor_position(Some(Vec2::new(100., 50.)));
+        let window = world.spawn((window, PrimaryWindow)).id();
+        let image = Handle::weak_from_u128(1);
+        let viewport = world
+            .spawn(StyleCursor {
+                cursor: Cursor::CustomImage,
+                image: Some(image.clone()),
+                offset: IVec2::new(8, 8),
+            })
+            .id();
+        let hover = |world: &mut World, entity: Option<Entity>| {
+            world.resource_mut::<HoverState>().set_hovered(entity);
+            world.run_system_once(update_cursor);
+            let mut query = world.query_filtered::<(&Style, &UiImage), With<SoftwareCursor>>();
+            query
+                .get_single(world)
+                .ok()
+                .map(|(style, ui_image)| (style.left, style.top, ui_image.texture.clone()))
+        };
+
+        // The image is drawn with its hotspot at the pointer, and the OS cursor is hidden.
+        assert_eq!(
+            hover(&mut world, Some(viewport)),
+            Some((Val::Px(92.), Val::Px(42.), image.clone()))
+        );
+        assert!(!world.get::<Window>(window).unwrap().cursor.visible);
+
+        // The image follows the pointer.
+        world
+            .get_mut::<Window>(window)
+            .unwrap()
+            .set_cursor_position(Some(Vec2::new(20., 30.)));
+        assert_eq!(
+            hover(&mut world, Some(viewport)),
+            Some((Val::Px(12.), Val::Px(22.), image))
+        );
+
+        // Switching back to a standard cursor removes the image.
+        assert_eq!(hover(&mut world, None), None);
+        let cursor = &world.get::<Window>(window).unwrap().cursor;
+        assert!(cursor.visible);
+        assert_eq!(cursor.icon, CursorIcon::Default);
+    }
+}
@@ -0,0 +1,152 @@
+//! Tools for inspecting the live view hierarchy.
+
+use std::fmt::Write;
+
+use bevy::prelude::*;
+
+use crate::{
+    tracked_resources::TrackedResources, tracking::TrackedComponents, ElementClasses, ViewHandle,
+};
+
+/// Return a printable description of the tree of presenters rooted at `root`. For each
+/// presenter this includes the presenter function, the presenter entity, the resources and
+/// components it is tracking, the shape of its output [`NodeSpan`](crate::NodeSpan), and the
+/// class names of its output entities.
+pub fn dump_view_tree(world: &World, root: Entity) -> String {
+    let mut out = String::new();
+    dump_presenter(world, root, 0, &mut out);
+    out
+}
+
+fn dump_presenter(world: &World, entity: Entity, depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    let Some(entt) = world.get_entity(entity) else {
+        let _ = writeln!(out, "{}<missing {:?}>", indent, entity);
+        return;
+    };
+    let Some(handle) = entt.get::<ViewHandle>() else {
+        let _ = writeln!(out, "{}<not a presenter {:?}>", indent, entity);
+        return;
+    };
+
+    // The presenter state is locked while it is being rendered.
+    let Ok(inner) = handle.inner.try_lock() else {
+        let _ = writeln!(out, "{}<locked> {:?}", indent, entity);
+        return;
+    };
+    let nodes = inner.nodes();
+    let _ = writeln!(
+        out,
+        "{}{} {:?} nodes: {:?}",
+        indent,
+        inner.presenter_name(),
+        entity,
+        nodes
+    );
+    drop(inner);
+
+    if let Some(resources) = entt.get::<TrackedResources>() {
+        for resource in resources.data.iter() {
+            let _ = writeln!(out, "{}  tracks {}", indent, resource.type_name());
+        }
+    }
+    if let Some(components) = entt.get::<TrackedComponents>() {
+        for (tracked, cid) in components.data.iter() {
+            let name = world
+                .components()
+                .get_info(*cid)
+                .map(|info| info.name())
+                .unwrap_or("<unknown>");
+            let _ = writeln!(out, "{}  tracks {} on {:?}", indent, name, tracked);
+        }
+    }
+    for node in nodes.iter() {
+        if let Some(classes) = world.get::<ElementClasses>(node) {
+            let mut names: Vec<&str> = classes.0.iter().map(|s| s.as_str()).collect();
+            names.sort_unstable();
+            let _ = writeln!(out, "{}  {:?} classes: {}", indent, node, names.join(" "));
+        }
+    }
+
+    if let Some(children) = entt.get::<Children>() {
+        for child in children.iter() {
+            if world.get::<ViewHandle>(*child).is_some() {
+                dump_presenter(world, *child, depth + 1, out);
+            }
+        }
+    }
+}
+
+/// Plugin which logs the view tree of every root presenter when a key is pressed.
+pub struct DebugDumpPlugin {
+    /// The key which triggers the dump. The default is F12.
+    pub key: KeyCode,
+}
+
+impl Default for DebugDumpPlugin {
+    fn default() -> Self {
+        Self { key: KeyCode::F12 }
+    }
+}
+
+#[derive(Resource)]
+struct DebugDumpKey(KeyCode);
+
+impl Plugin for DebugDumpPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(DebugDumpKey(self.key))
+            .add_systems(Update, dump_on_key);
+    }
+}
+
+fn dump_on_key(world: &mut World) {
+    let key = world.resource::<DebugDumpKey>().0;
+    let pressed = world
+        .get_resource::<ButtonInput<KeyCode>>()
+        .is_some_and(|input| input.just_pressed(key));
+    if !pressed {
+        return;
+    }
+    let mut roots = world.query_filtered::<Entity, (With<ViewHandle>, Without<Parent>)>();
+    let roots: Vec<Entity> = roots.iter(world).collect();
+    for root in roots {
+        info!("View tree:\n{}", dump_view_tree(world, root));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{plugin::render_views, Cx, Element, PresenterFn, View};
+
+    #[derive(Resource, Default)]
+    struct Label(String);
+
+    fn parent(cx: Cx) -> impl View {
+        let label = cx.use_resource::<Label>().0.clone();
+        Element::new()
+            .class_names("panel")
+            .children((label, child.bind(())))
+    }
+
+    fn child(_cx: Cx) -> impl View {
+        "child"
+    }
+
+    #[test]
+    fn test_dump_view_tree() {
+        let mut world = World::new();
+        world.init_resource::<Label>();
+        let root = world.spawn(ViewHandle::new(parent, ())).id();
+        render_views(&mut world);
+        let dump = dump_view_tree(&world, root);
+        let lines: Vec<&str> = dump.lines().collect();
+        assert!(lines[0].contains("debug::tests::parent"));
+        assert!(lines[0].contains(&format!("{:?}", root)));
+        assert!(lines[1].contains("TrackedResource<"));
+        assert!(lines[1].contains("Label"));
+        assert!(lines[2].ends_with("classes: panel"));
+        assert!(lines[3].starts_with("  "));
+        assert!(lines[3].contains("debug::tests::child"));
+    }
+}
@@ -0,0 +1,232 @@
+//! Per-frame diagnostics for the reactive layer.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+    utils::Instant,
+};
+
+use crate::{
+    plugin::QuillSchedule,
+    style::{AnimatedLayout, AnimatedTransform},
+    QuillSet, ScrollArea,
+};
+
+/// Plugin which records [`Diagnostics`] for Quill each frame: how many presenters were rebuilt,
+/// how many views had their children re-attached, how many computed styles were applied, how
+/// many entities are being animated, and how long each [`QuillSet`] took.
+///
+/// The counters are only maintained when this plugin is added, and it must be added alongside
+/// [`QuillPlugin`](crate::QuillPlugin). The values can be read from
+/// [`DiagnosticsStore`](bevy::diagnostic::DiagnosticsStore), or logged with
+/// `LogDiagnosticsPlugin`.
+pub struct QuillDiagnosticsPlugin;
+
+impl QuillDiagnosticsPlugin {
+    /// Number of presenters rebuilt this frame.
+    pub const PRESENTERS_BUILT: DiagnosticPath =
+        DiagnosticPath::const_new("quill/presenters_built");
+    /// Number of views whose children were re-attached this frame.
+    pub const VIEWS_ATTACHED: DiagnosticPath = DiagnosticPath::const_new("quill/views_attached");
+    /// Number of computed styles applied to entities this frame.
+    pub const STYLES_COMPUTED: DiagnosticPath = DiagnosticPath::const_new("quill/styles_computed");
+    /// Number of entities with a style transition or smooth scroll in progress.
+    pub const ACTIVE_ANIMATIONS: DiagnosticPath =
+        DiagnosticPath::const_new("quill/active_animations");
+    /// Time spent in [`QuillSet::BuildViews`], in milliseconds.
+    pub const BUILD_VIEWS_TIME: DiagnosticPath =
+        DiagnosticPath::const_new("quill/build_views_time");
+    /// Time spent in [`QuillSet::AttachChildren`], in milliseconds.
+    pub const ATTACH_CHILDREN_TIME: DiagnosticPath =
+        DiagnosticPath::const_new("quill/attach_children_time");
+    /// Time spent in [`QuillSet::ComputeStyles`], in milliseconds.
+    pub const COMPUTE_STYLES_TIME: DiagnosticPath =
+        DiagnosticPath::const_new("quill/compute_styles_time");
+    /// Time spent in [`QuillSet::Animate`], in milliseconds.
+    pub const ANIMATE_TIME: DiagnosticPath = DiagnosticPath::const_new("quill/animate_time");
+}
+
+/// The timing diagnostics, in the order of the sets they measure.
+const SET_TIMES: [DiagnosticPath; 4] = [
+    QuillDiagnosticsPlugin::BUILD_VIEWS_TIME,
+    QuillDiagnosticsPlugin::ATTACH_CHILDREN_TIME,
+    QuillDiagnosticsPlugin::COMPUTE_STYLES_TIME,
+    QuillDiagnosticsPlugin::ANIMATE_TIME,
+];
+
+/// Counters which are incremented by the Quill systems. These are atomic so that they can be
+/// updated through a shared reference to the world.
+#[derive(Resource, Default)]
+pub(crate) struct QuillCounters {
+    pub(crate) presenters_built: AtomicUsize,
+    pub(crate) views_attached: AtomicUsize,
+    pub(crate) styles_computed: AtomicUsize,
+}
+
+impl QuillCounters {
+    /// Add `count` to a counter, if the counters exist.
+    pub(crate) fn add(
+        world: &World,
+        counter: impl FnOnce(&QuillCounters) -> &AtomicUsize,
+        count: usize,
+    ) {
+        if let Some(counters) = world.get_resource::<QuillCounters>() {
+            counter(counters).fetch_add(count, Ordering::Relaxed);
+        }
+    }
+}
+
+/// The time at which each set started, and at which the last one ended.
+#[derive(Resource, Default)]
+struct SetTimings([Option<Instant>; 5]);
+
+fn mark<const I: usize>(mut timings: ResMut<SetTimings>) {
+    timings.0[I] = Some(Instant::now());
+}
+
+fn record_diagnostics(
+    mut diagnostics: Diagnostics,
+    counters: Res<QuillCounters>,
+    mut timings: ResMut<SetTimings>,
+    transforms: Query<&AnimatedTransform>,
+    layouts: Query<&AnimatedLayout>,
+    scroll_areas: Query<&ScrollArea>,
+) {
+    let take = |counter: &AtomicUsize| counter.swap(0, Ordering::Relaxed) as f64;
+    diagnostics.add_measurement(&QuillDiagnosticsPlugin::PRESENTERS_BUILT, || {
+        take(&counters.presenters_built)
+    });
+    diagnostics.add_measurement(&QuillDiagnosticsPlugin::VIEWS_ATTACHED, || {
+        take(&counters.views_attached)
+    });
+    diagnostics.add_measurement(&QuillDiagnosticsPlugin::STYLES_COMPUTED, || {
+        take(&counters.styles_computed)
+    });
+    diagnostics.add_measurement(&QuillDiagnosticsPlugin::ACTIVE_ANIMATIONS, || {
+        let transforms = transforms.iter().filter(|at| at.state.clock < 1.).count();
+        let layouts = layouts
+            .iter()
+            .filter(|al| al.0.values().any(|prop| prop.state.clock < 1.))
+            .count();
+        let scrolls = scroll_areas
+            .iter()
+            .filter(|sa| sa.animation.is_some() || sa.spring.is_some() || sa.velocity != Vec2::ZERO)
+            .count();
+        (transforms + layouts + scrolls) as f64
+    });
+
+    let times = std::mem::take(&mut timings.0);
+    for (i, path) in SET_TIMES.iter().enumerate() {
+        if let (Some(start), Some(end)) = (times[i], times[i + 1]) {
+            diagnostics.add_measurement(path, || end.duration_since(start).as_secs_f64() * 1000.);
+        }
+    }
+}
+
+impl Plugin for QuillDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<QuillCounters>()
+            .init_resource::<SetTimings>()
+            .register_diagnostic(Diagnostic::new(Self::PRESENTERS_BUILT))
+            .register_diagnostic(Diagnostic::new(Self::VIEWS_ATTACHED))
+            .register_diagnostic(Diagnostic::new(Self::STYLES_COMPUTED))
+            .register_diagnostic(Diagnostic::new(Self::ACTIVE_ANIMATIONS));
+        for path in SET_TIMES {
+            app.register_diagnostic(Diagnostic::new(path).with_suffix("ms"));
+        }
+    }
+
+    // The systems are added here rather than in `build`, so that the schedule chosen for
+    // `QuillPlugin` is known regardless of the order in which the plugins were added.
+    fn finish(&self, app: &mut App) {
+        let Some(QuillSchedule(schedule)) = app.world.get_resource::<QuillSchedule>().copied()
+        else {
+            warn!("QuillDiagnosticsPlugin requires QuillPlugin");
+            return;
+        };
+        app.add_systems(
+            schedule,
+            (
+                mark::<0>.before(QuillSet::BuildViews),
+                mark::<1>
+                    .after(QuillSet::BuildViews)
+                    .before(QuillSet::AttachChildren),
+                mark::<2>
+                    .after(QuillSet::AttachChildren)
+                    .before(QuillSet::ComputeStyles),
+                mark::<3>
+                    .after(QuillSet::ComputeStyles)
+                    .before(QuillSet::Animate),
+                (mark::<4>, record_diagnostics)
+                    .chain()
+                    .after(QuillSet::Animate),
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        a11y::Focus,
+        diagnostic::DiagnosticsStore,
+        input::{keyboard::KeyboardInput, mouse::MouseWheel},
+    };
+
+    use super::*;
+    use crate::{Cx, Element, QuillPlugin, View, ViewHandle};
+
+    fn value(app: &App, path: &DiagnosticPath) -> Option<f64> {
+        app.world
+            .resource::<DiagnosticsStore>()
+            .get(path)
+            .and_then(|d| d.measurement())
+            .map(|m| m.value)
+    }
+
+    fn root_presenter(_cx: Cx) -> impl View {
+        Element::new().children("Hello")
+    }
+
+    #[test]
+    fn test_counters() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .add_plugins((QuillPlugin::default(), QuillDiagnosticsPlugin))
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<Focus>()
+            .add_event::<MouseWheel>()
+            .add_event::<KeyboardInput>();
+        app.finish();
+        app.cleanup();
+
+        app.world.spawn(ViewHandle::new(root_presenter, ()));
+        app.update();
+        assert_eq!(
+            value(&app, &QuillDiagnosticsPlugin::PRESENTERS_BUILT),
+            Some(1.)
+        );
+        assert_eq!(
+            value(&app, &QuillDiagnosticsPlugin::VIEWS_ATTACHED),
+            Some(1.)
+        );
+        assert!(value(&app, &QuillDiagnosticsPlugin::BUILD_VIEWS_TIME).is_some());
+
+        // Nothing changed, so nothing is rebuilt.
+        app.update();
+        assert_eq!(
+            value(&app, &QuillDiagnosticsPlugin::PRESENTERS_BUILT),
+            Some(0.)
+        );
+        assert_eq!(
+            value(&app, &QuillDiagnosticsPlugin::VIEWS_ATTACHED),
+            Some(0.)
+        );
+        assert_eq!(
+            value(&app, &QuillDiagnosticsPlugin::ACTIVE_ANIMATIONS),
+            Some(0.)
+        );
+    }
+}
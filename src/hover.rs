@@ -0,0 +1,74 @@
+//! Hover state, used for `:hover` selectors and for routing scroll wheel events.
+
+use bevy::{prelude::*, utils::HashSet};
+
+/// Resource containing the entities which the mouse pointer is currently over. This includes
+/// every entity under the pointer, not just the topmost one, but not their ancestors.
+///
+/// With the `picking` feature, this is filled in from bevy_mod_picking's hover map each frame.
+/// Without it, an application can fill it in from whatever picking backend it uses, using
+/// [`HoverState::set_hovered`] so that the depth order is kept.
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct HoverState {
+    /// The hovered entities.
+    pub hovered: HashSet<Entity>,
+
+    /// The hovered entities in picking depth order, topmost first.
+    pub depth_order: Vec<Entity>,
+}
+
+impl HoverState {
+    /// True if the pointer is over the given entity.
+    pub fn is_hovered(&self, entity: Entity) -> bool {
+        self.hovered.contains(&entity)
+    }
+
+    /// The topmost hovered entity, if any.
+    pub fn topmost(&self) -> Option<Entity> {
+        self.depth_order.first().copied()
+    }
+
+    /// Replace the hovered entities. `entities` should be in depth order, topmost first.
+    pub fn set_hovered(&mut self, entities: impl IntoIterator<Item = Entity>) {
+        self.depth_order = entities.into_iter().collect();
+        self.hovered = self.depth_order.iter().copied().collect();
+    }
+}
+
+/// The hover state as of the last time styles were updated, used to detect which elements need
+/// their styles recomputed.
+#[derive(Resource, Default)]
+pub(crate) struct PreviousHoverState(pub(crate) HashSet<Entity>);
+
+/// Components which stop an entity from being hovered or blocking the entities below it, for
+/// overlays such as portal containers. Without the `picking` feature, this is empty.
+#[cfg(feature = "picking")]
+pub(crate) fn ignore_picking() -> impl Bundle {
+    bevy_mod_picking::prelude::Pickable::IGNORE
+}
+
+#[cfg(not(feature = "picking"))]
+pub(crate) fn ignore_picking() -> impl Bundle {}
+
+/// Copy the entities hovered by the mouse from the picking hover map into [`HoverState`].
+#[cfg(feature = "picking")]
+pub(crate) fn sync_hover_state(
+    hover_map: Option<Res<bevy_mod_picking::focus::HoverMap>>,
+    mut hover_state: ResMut<HoverState>,
+) {
+    let Some(hover_map) = hover_map else {
+        return;
+    };
+    let mut hovered: Vec<_> = hover_map
+        .get(&bevy_mod_picking::pointer::PointerId::Mouse)
+        .map(|hovered| hovered.iter().collect())
+        .unwrap_or_default();
+    hovered.sort_by(|(_, a), (_, b)| a.depth.total_cmp(&b.depth));
+    if !hovered
+        .iter()
+        .map(|(entity, _)| **entity)
+        .eq(hover_state.depth_order.iter().copied())
+    {
+        hover_state.set_hovered(hovered.into_iter().map(|(entity, _)| *entity));
+    }
+}
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use bevy::{
+    a11y::Focus,
+    input::{keyboard::KeyboardInput, ButtonState},
+    prelude::*,
+};
+
+type KeyHandler = Arc<dyn Fn(Entity, &mut World) + Send + Sync>;
+
+/// Component which maps key presses to handlers. When a key is pressed, the handler for that
+/// key is run on the entity which has keyboard focus; if the focus entity has no handler for
+/// the key, then its ancestors are searched. The handler is passed the entity it is bound to.
+/// Normally this is added via [`View::on_key`].
+///
+/// [`View::on_key`]: crate::View::on_key
+#[derive(Component, Default)]
+pub struct KeyBindings(Vec<(KeyCode, KeyHandler)>);
+
+impl KeyBindings {
+    /// Set the handler for a key, replacing any previous handler for that key.
+    pub fn bind(&mut self, key: KeyCode, handler: KeyHandler) {
+        match self.0.iter_mut().find(|(k, _)| *k == key) {
+            Some(binding) => binding.1 = handler,
+            None => self.0.push((key, handler)),
+        }
+    }
+
+    fn get(&self, key: KeyCode) -> Option<&KeyHandler> {
+        self.0.iter().find(|(k, _)| *k == key).map(|(_, h)| h)
+    }
+}
+
+pub(crate) fn handle_key_events(
+    mut key_evr: EventReader<KeyboardInput>,
+    focus: Res<Focus>,
+    bindings: Query<&KeyBindings>,
+    parents: Query<&Parent>,
+    mut commands: Commands,
+) {
+    for ev in key_evr.read() {
+        if ev.state != ButtonState::Pressed {
+            continue;
+        }
+        let mut entity = focus.0;
+        while let Some(ent) = entity {
+            if let Some(handler) = bindings.get(ent).ok().and_then(|b| b.get(ev.key_code)) {
+                let handler = handler.clone();
+                commands.add(move |world: &mut World| handler(ent, world));
+                break;
+            }
+            // Search up
+            entity = parents.get(ent).ok().map(|parent| parent.get());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, input::keyboard::Key};
+
+    use super::*;
+    use crate::{BuildContext, Element, View};
+
+    #[derive(Resource, Default)]
+    struct Clicked(usize);
+
+    fn press(world: &mut World, key_code: KeyCode, logical_key: Key) {
+        world.send_event(KeyboardInput {
+            key_code,
+            logical_key,
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        });
+        world.run_system_once(handle_key_events);
+        world.resource_mut::<Events<KeyboardInput>>().clear();
+    }
+
+    #[test]
+    fn test_on_key() {
+        let mut world = World::new();
+        world.init_resource::<Events<KeyboardInput>>();
+        world.init_resource::<Clicked>();
+        world.init_resource::<Focus>();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let button = Element::new().on_key(KeyCode::Enter, |_, world: &mut World| {
+            world.resource_mut::<Clicked>().0 += 1;
+        });
+        let state = button.build(&mut bc);
+
+        // No effect unless the button has focus.
+        press(&mut world, KeyCode::Enter, Key::Enter);
+        assert_eq!(world.resource::<Clicked>().0, 0);
+
+        world.resource_mut::<Focus>().0 = Some(state);
+        press(&mut world, KeyCode::Enter, Key::Enter);
+        assert_eq!(world.resource::<Clicked>().0, 1);
+
+        // Unbound keys are ignored.
+        press(&mut world, KeyCode::Escape, Key::Escape);
+        assert_eq!(world.resource::<Clicked>().0, 1);
+    }
+}
@@ -4,21 +4,32 @@
 
 #![warn(missing_docs)]
 mod cursor;
+pub mod debug;
+mod diagnostics;
+mod hover;
+mod keyboard;
 mod node_span;
 mod plugin;
 mod scrolling;
 mod style;
+#[cfg(test)]
+mod test_utils;
 mod view;
+mod window;
 
-pub use cursor::Cursor;
-pub use node_span::NodeSpan;
+pub use cursor::{Cursor, CursorLock, CursorPriority, CursorRequestId, CursorStack};
+pub use diagnostics::QuillDiagnosticsPlugin;
+pub use hover::HoverState;
+pub use keyboard::KeyBindings;
+pub use node_span::{NodeSpan, NodeSpanIter};
 #[doc(inline)]
 pub use prelude::*;
 pub use scrolling::*;
+pub use window::{target_window, NodeWindow, WindowInfo};
 
 /// Common imports
 pub mod prelude {
-    pub use crate::plugin::QuillPlugin;
+    pub use crate::plugin::{QuillPlugin, QuillSet};
     pub use crate::style::*;
     pub use crate::view::*;
 }
@@ -2,7 +2,7 @@ use bevy::prelude::*;
 
 /// Hierarchical data structure representing an ordered sequence of entities. This is the
 /// rendered output of a `View`. This gets flattened before attaching to the parent UiNode.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub enum NodeSpan {
     /// Means that nothing was rendered. This can represent either an initial state
     /// before the first render, or a conditional render operation.
@@ -25,6 +25,83 @@ impl NodeSpan {
         }
     }
 
+    /// Returns the number of actual entities contained in this span. Same as [`count`].
+    ///
+    /// [`count`]: NodeSpan::count
+    pub fn len(&self) -> usize {
+        self.count()
+    }
+
+    /// Returns true if this span contains no entities.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_quill::NodeSpan;
+    /// assert!(NodeSpan::Empty.is_empty());
+    /// assert!(NodeSpan::Fragment(Box::new([NodeSpan::Empty])).is_empty());
+    /// assert!(!NodeSpan::Node(Entity::from_raw(1)).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Empty => true,
+            Self::Node(_) => false,
+            Self::Fragment(nodes) => nodes.iter().all(|node| node.is_empty()),
+        }
+    }
+
+    /// Returns an iterator over the entities in this span, in depth-first order.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_quill::NodeSpan;
+    /// let (a, b) = (Entity::from_raw(1), Entity::from_raw(2));
+    /// let span = NodeSpan::Fragment(Box::new([
+    ///     NodeSpan::Node(a),
+    ///     NodeSpan::Fragment(Box::new([NodeSpan::Empty, NodeSpan::Node(b)])),
+    /// ]));
+    /// assert_eq!(span.iter().collect::<Vec<_>>(), vec![a, b]);
+    /// ```
+    pub fn iter(&self) -> NodeSpanIter<'_> {
+        NodeSpanIter {
+            stack: vec![std::slice::from_ref(self).iter()],
+        }
+    }
+
+    /// Returns an iterator over the entities in this span. Same as [`iter`].
+    ///
+    /// [`iter`]: NodeSpan::iter
+    pub fn iter_entities(&self) -> NodeSpanIter<'_> {
+        self.iter()
+    }
+
+    /// Returns the first entity in this span, if any.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_quill::NodeSpan;
+    /// let a = Entity::from_raw(1);
+    /// let span = NodeSpan::Fragment(Box::new([NodeSpan::Empty, NodeSpan::Node(a)]));
+    /// assert_eq!(span.first_entity(), Some(a));
+    /// assert_eq!(NodeSpan::Empty.first_entity(), None);
+    /// ```
+    pub fn first_entity(&self) -> Option<Entity> {
+        self.iter().next()
+    }
+
+    /// Returns true if `entity` is contained in this span.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_quill::NodeSpan;
+    /// let (a, b) = (Entity::from_raw(1), Entity::from_raw(2));
+    /// let span = NodeSpan::Fragment(Box::new([NodeSpan::Node(a)]));
+    /// assert!(span.contains(a));
+    /// assert!(!span.contains(b));
+    /// ```
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.iter().any(|e| e == entity)
+    }
+
     /// Flattens the list of entities into a vector.
     pub fn flatten(&self, out: &mut Vec<Entity>) {
         match self {
@@ -34,6 +111,19 @@ impl NodeSpan {
         }
     }
 
+    /// Collapse any nested fragments into a single fragment containing only nodes, in the
+    /// same order. Empty spans are removed.
+    pub fn flattened(self) -> NodeSpan {
+        match self {
+            Self::Fragment(ref nodes) if nodes.iter().any(|n| !matches!(n, Self::Node(_))) => {
+                let mut out = Vec::with_capacity(self.count());
+                self.flatten(&mut out);
+                Self::Fragment(out.into_iter().map(Self::Node).collect())
+            }
+            _ => self,
+        }
+    }
+
     /// Despawn all entities held.
     pub(crate) fn despawn(&self, world: &mut World) {
         match self {
@@ -62,6 +152,48 @@ impl NodeSpan {
     // }
 }
 
+/// Prints the shape of the span: fragments are printed as lists, and empty spans as `()`.
+impl std::fmt::Debug for NodeSpan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "()"),
+            Self::Node(entity) => write!(f, "{:?}", entity),
+            Self::Fragment(nodes) => f.debug_list().entries(nodes.iter()).finish(),
+        }
+    }
+}
+
+/// Iterator over the entities in a [`NodeSpan`], returned by [`NodeSpan::iter`].
+pub struct NodeSpanIter<'a> {
+    stack: Vec<std::slice::Iter<'a, NodeSpan>>,
+}
+
+impl<'a> Iterator for NodeSpanIter<'a> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.stack.last_mut()?.next() {
+                None => {
+                    self.stack.pop();
+                }
+                Some(NodeSpan::Empty) => {}
+                Some(NodeSpan::Node(entity)) => return Some(*entity),
+                Some(NodeSpan::Fragment(nodes)) => self.stack.push(nodes.iter()),
+            }
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a NodeSpan {
+    type Item = Entity;
+    type IntoIter = NodeSpanIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl PartialEq for NodeSpan {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -80,3 +212,82 @@ impl Default for NodeSpan {
         Self::Empty
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flattened() {
+        let mut world = World::new();
+        let e: Vec<Entity> = (0..4).map(|_| world.spawn_empty().id()).collect();
+        let span = NodeSpan::Fragment(Box::new([
+            NodeSpan::Node(e[0]),
+            NodeSpan::Fragment(Box::new([
+                NodeSpan::Empty,
+                NodeSpan::Fragment(Box::new([NodeSpan::Node(e[1]), NodeSpan::Node(e[2])])),
+            ])),
+            NodeSpan::Empty,
+            NodeSpan::Node(e[3]),
+        ]));
+        assert_eq!(
+            span.flattened(),
+            NodeSpan::Fragment(e.iter().map(|e| NodeSpan::Node(*e)).collect())
+        );
+
+        // Non-fragments are unchanged.
+        assert_eq!(NodeSpan::Node(e[0]).flattened(), NodeSpan::Node(e[0]));
+        assert_eq!(NodeSpan::Empty.flattened(), NodeSpan::Empty);
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut world = World::new();
+        let e: Vec<Entity> = (0..3).map(|_| world.spawn_empty().id()).collect();
+        let span = NodeSpan::Fragment(Box::new([
+            NodeSpan::Fragment(Box::new([NodeSpan::Empty])),
+            NodeSpan::Node(e[0]),
+            NodeSpan::Fragment(Box::new([
+                NodeSpan::Fragment(Box::new([NodeSpan::Node(e[1])])),
+                NodeSpan::Node(e[2]),
+            ])),
+        ]));
+        assert_eq!(span.iter().collect::<Vec<_>>(), e);
+        assert_eq!(span.len(), 3);
+        assert!(!span.is_empty());
+        assert_eq!(span.first_entity(), Some(e[0]));
+        assert!(span.contains(e[2]));
+        assert_eq!(
+            format!("{:?}", span),
+            format!("[[()], {:?}, [[{:?}], {:?}]]", e[0], e[1], e[2])
+        );
+    }
+
+    #[test]
+    fn test_iter_matches_flatten() {
+        let mut world = World::new();
+        let e: Vec<Entity> = (0..5).map(|_| world.spawn_empty().id()).collect();
+        let spans = [
+            NodeSpan::Empty,
+            NodeSpan::Node(e[0]),
+            NodeSpan::Fragment(Box::new([])),
+            NodeSpan::Fragment(Box::new([
+                NodeSpan::Fragment(Box::new([NodeSpan::Empty, NodeSpan::Empty])),
+                NodeSpan::Node(e[0]),
+                NodeSpan::Fragment(Box::new([
+                    NodeSpan::Node(e[1]),
+                    NodeSpan::Fragment(Box::new([NodeSpan::Node(e[2]), NodeSpan::Node(e[3])])),
+                ])),
+                NodeSpan::Empty,
+                NodeSpan::Fragment(Box::new([NodeSpan::Node(e[4])])),
+            ])),
+        ];
+        for (span, count) in spans.iter().zip([0, 1, 0, 5]) {
+            let mut flat = Vec::new();
+            span.flatten(&mut flat);
+            assert_eq!(span.iter().collect::<Vec<_>>(), flat);
+            assert_eq!(span.into_iter().count(), count);
+            assert_eq!(span.count(), count);
+        }
+    }
+}
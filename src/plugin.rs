@@ -1,50 +1,198 @@
-use bevy::{prelude::*, utils::HashSet};
-use bevy_mod_picking::prelude::EventListenerPlugin;
+use bevy::{
+    ecs::schedule::{InternedScheduleLabel, ScheduleLabel},
+    prelude::*,
+    ui::UiSystem,
+    utils::HashSet,
+};
+#[cfg(feature = "picking")]
+use bevy_mod_picking::{events::pointer_events, focus::update_focus, prelude::EventListenerPlugin};
 
 use crate::{
-    animate_bg_colors, animate_border_colors, animate_layout, animate_transforms,
-    handle_scroll_events,
-    presenter_state::{PresenterGraphChanged, PresenterStateChanged},
+    animate_bg_colors, animate_border_colors, animate_layout, animate_scroll_positions,
+    animate_transforms,
+    atom::{update_debounced_atoms, DeferredAtomWrites},
+    cursor::update_cursor,
+    derived::update_derived_atoms,
+    diagnostics::QuillCounters,
+    enter_exit::enter_exit_state_machine,
+    handle_scroll_events, handle_scroll_keys,
+    hover::{HoverState, PreviousHoverState},
+    interval::update_intervals,
+    keyboard::handle_key_events,
+    presenter_state::{
+        raze_removed_views, PresenterGraphChanged, PresenterStateChanged, ViewHandleRegistry,
+    },
+    resource_field::sync_resource_fields,
+    root_camera::update_root_cameras,
+    run_scroll_handlers, send_scroll_changed,
+    task::poll_tasks,
     tracked_resources::TrackedResources,
     tracking::TrackedComponents,
     update::{update_styles, PreviousFocus},
-    update_scroll_positions, BuildContext, ScrollWheel, ViewHandle,
+    update_scroll_geometry, update_scroll_momentum, update_scroll_positions,
+    update_scrollbar_auto_hide,
+    view_classes_atom::sync_class_names,
+    window::update_window_info,
+    BuildContext, CursorStack, ScrollChanged, ScrollConfig, ScrollWheel, ViewHandle,
 };
 
+/// System sets for the systems added by [`QuillPlugin`], which run in this order, in the
+/// schedule chosen with [`QuillPlugin::in_schedule`].
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuillSet {
+    /// Presenters whose dependencies have changed are rebuilt, and views which have been
+    /// removed are razed. Input which affects presenters, such as scrolling, is also handled
+    /// here, before the presenters run. Entities spawned by presenters exist after this set,
+    /// but may not have been attached to their parents yet.
+    BuildViews,
+    /// Child entities are attached to their parents wherever the shape of the view hierarchy
+    /// has changed.
+    AttachChildren,
+    /// Computed styles are updated from style sheets, class names and hover state.
+    ComputeStyles,
+    /// Style transitions and smooth scrolling are animated, and the window cursor is updated.
+    Animate,
+}
+
 /// Plugin which initializes the Quill library.
-pub struct QuillPlugin;
+///
+/// By default the Quill systems run in [`Update`]; use [`QuillPlugin::in_schedule`] to run them
+/// elsewhere. The systems which measure scrolling regions run in [`PostUpdate`] regardless,
+/// since they must be ordered with respect to UI layout.
+pub struct QuillPlugin {
+    schedule: InternedScheduleLabel,
+}
+
+impl Default for QuillPlugin {
+    fn default() -> Self {
+        Self {
+            schedule: Update.intern(),
+        }
+    }
+}
+
+impl QuillPlugin {
+    /// Run the Quill systems in `schedule` rather than in [`Update`].
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = schedule.intern();
+        self
+    }
+}
+
+/// The schedule which the Quill systems run in, so that other plugins can add systems alongside
+/// them.
+#[derive(Resource, Clone, Copy)]
+pub(crate) struct QuillSchedule(pub(crate) InternedScheduleLabel);
 
 impl Plugin for QuillPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<PreviousFocus>()
+        app.insert_resource(QuillSchedule(self.schedule))
+            .init_resource::<PreviousFocus>()
+            .init_resource::<CursorStack>()
+            .init_resource::<ScrollConfig>()
+            .init_resource::<DeferredAtomWrites>()
+            .init_resource::<HoverState>()
+            .init_resource::<PreviousHoverState>()
+            .configure_sets(
+                self.schedule,
+                (
+                    QuillSet::BuildViews,
+                    QuillSet::AttachChildren,
+                    QuillSet::ComputeStyles,
+                    QuillSet::Animate,
+                )
+                    .chain()
+                    .before(UiSystem::Layout),
+            )
             .add_systems(
-                Update,
+                self.schedule,
                 (
-                    (render_views, update_styles).chain(),
-                    animate_transforms,
-                    animate_bg_colors,
-                    animate_border_colors,
-                    animate_layout,
-                    update_scroll_positions,
-                    handle_scroll_events,
+                    (
+                        (handle_scroll_events, handle_scroll_keys, handle_key_events),
+                        update_window_info,
+                        update_debounced_atoms,
+                        update_intervals,
+                        poll_tasks,
+                        enter_exit_state_machine,
+                        raze_removed_views,
+                        build_views,
+                    )
+                        .chain()
+                        .in_set(QuillSet::BuildViews),
+                    (attach_views, update_root_cameras)
+                        .chain()
+                        .in_set(QuillSet::AttachChildren),
+                    update_styles.in_set(QuillSet::ComputeStyles),
+                    (
+                        animate_transforms,
+                        animate_bg_colors,
+                        animate_border_colors,
+                        animate_layout,
+                        animate_scroll_positions,
+                        update_scroll_momentum,
+                        update_cursor,
+                    )
+                        .in_set(QuillSet::Animate),
                 ),
             )
-            .add_plugins(EventListenerPlugin::<ScrollWheel>::default())
-            .add_event::<ScrollWheel>();
+            .add_systems(
+                PostUpdate,
+                (
+                    update_scroll_positions
+                        .after(QuillSet::Animate)
+                        .before(UiSystem::Layout),
+                    (
+                        update_scroll_geometry,
+                        send_scroll_changed,
+                        (run_scroll_handlers, update_scrollbar_auto_hide),
+                    )
+                        .chain()
+                        .after(UiSystem::Layout),
+                ),
+            )
+            .add_event::<ScrollWheel>()
+            .add_event::<ScrollChanged>();
+
+        #[cfg(feature = "picking")]
+        app.add_systems(
+            self.schedule,
+            crate::hover::sync_hover_state
+                .in_set(QuillSet::BuildViews)
+                .before(handle_scroll_events),
+        )
+        .add_systems(
+            PreUpdate,
+            crate::view_disabled::block_disabled_hovers
+                .after(update_focus)
+                .before(pointer_events),
+        )
+        .add_plugins(EventListenerPlugin::<ScrollWheel>::default())
+        .add_plugins(EventListenerPlugin::<ScrollChanged>::default());
     }
 }
 
 const MAX_DIVERGENCE_CT: usize = 30;
 
+/// Rebuild presenters which have changed, and then attach their children.
+#[cfg(test)]
+pub(crate) fn render_views(world: &mut World) {
+    build_views(world);
+    attach_views(world);
+}
+
 // Updating views needs to be split in 3 phases for borrowing issues
 // Phase 1: Identify which ViewRoot Entity needs to re-render
 // Phase 2: Use Option::take() to remove the ViewRoot::handle from the World. Use the taken handle
 //          and call AnyViewState::build() on it. Since the handle isn't part of the World we can
 //          freely pass a mutable reference to the World.
-fn render_views(world: &mut World) {
+// Phase 3 is done by `attach_views`.
+fn build_views(world: &mut World) {
+    // Apply any state changes which were made outside of a world context.
+    apply_pending_changes(world);
+
     let mut divergence_ct: usize = 0;
     let mut prev_change_ct: usize = 0;
-    let this_run = world.change_tick();
+    let mut build_ct: usize = 0;
 
     let mut v = HashSet::new();
 
@@ -57,23 +205,7 @@ fn render_views(world: &mut World) {
     }
 
     // Scan changed components
-    let mut q = world.query::<(Entity, &mut TrackedComponents)>();
-    for (e, tracked_components) in q.iter(world) {
-        if !v.contains(&e)
-            && tracked_components.data.iter().any(|(cent, cid)| {
-                world
-                    .get_entity(*cent)
-                    .map(|ent| {
-                        ent.get_change_ticks_by_id(*cid)
-                            .map(|ct| ct.is_changed(tracked_components.tick, this_run))
-                            .unwrap_or(false)
-                    })
-                    .unwrap_or(false)
-            })
-        {
-            v.insert(e);
-        }
-    }
+    scan_changed_components(world, &mut v);
 
     // force build every view that just got spawned
     let mut qf = world.query_filtered::<Entity, Added<ViewHandle>>();
@@ -91,10 +223,6 @@ fn render_views(world: &mut World) {
             v.insert(e);
         }
 
-        for e in v.iter() {
-            world.entity_mut(*e).remove::<PresenterStateChanged>();
-        }
-
         // Most of the time changes will converge, that is, the number of changed presenters
         // decreases each time through the loop. A "divergence" is when that fails to happen.
         // We tolerate a maximum number of divergences before giving up.
@@ -102,15 +230,30 @@ fn render_views(world: &mut World) {
         if change_ct >= prev_change_ct {
             divergence_ct += 1;
             if divergence_ct > MAX_DIVERGENCE_CT {
-                panic!("Reactions failed to converge, num changes: {}", change_ct);
+                // Leave the remaining changes for the next frame, so that a cycle doesn't
+                // hang the app.
+                warn!("Reactions failed to converge, num changes: {}", change_ct);
+                for e in v.iter() {
+                    if let Some(mut entt) = world.get_entity_mut(*e) {
+                        entt.insert(PresenterStateChanged);
+                    }
+                }
+                break;
             }
         }
         prev_change_ct = change_ct;
 
+        for e in v.iter() {
+            world.entity_mut(*e).remove::<PresenterStateChanged>();
+        }
+
         // phase 2
         if change_ct > 0 {
             for e in v.drain() {
-                let mut entt = world.entity_mut(e);
+                // The presenter may have been razed by its parent earlier in this pass.
+                let Some(mut entt) = world.get_entity_mut(e) else {
+                    continue;
+                };
                 // Clear tracking lists for presenters to be re-rendered.
                 if let Some(mut tracked_resources) = entt.get_mut::<TrackedResources>() {
                     tracked_resources.data.clear();
@@ -124,15 +267,34 @@ fn render_views(world: &mut World) {
                     continue;
                 };
                 let inner = view_handle.inner.clone();
+                world
+                    .get_resource_or_insert_with(ViewHandleRegistry::default)
+                    .0
+                    .entry(e)
+                    .or_insert_with(|| inner.clone());
+                // Advance the tick, so that atoms written by this presenter are seen as changes
+                // by presenters which were built earlier in the pass.
+                world.increment_change_tick();
                 let mut ec = BuildContext::new(world, e);
                 inner.lock().unwrap().build(&mut ec, e);
+                build_ct += 1;
             }
         } else {
             break;
         }
+
+        // Presenters may have written atoms which are read by other presenters; rebuild those
+        // in another pass, rather than waiting for the next frame.
+        apply_pending_changes(world);
+        scan_changed_components(world, &mut v);
     }
 
-    // phase 3
+    QuillCounters::add(world, |c| &c.presenters_built, build_ct);
+}
+
+// Phase 3: Attach the children of presenters whose output has changed shape.
+fn attach_views(world: &mut World) {
+    let mut attach_ct: usize = 0;
     loop {
         let mut qf = world.query_filtered::<Entity, With<PresenterGraphChanged>>();
         let changed_entities: Vec<Entity> = qf.iter(world).collect();
@@ -150,6 +312,150 @@ fn render_views(world: &mut World) {
             let inner = view_handle.inner.clone();
             let mut bc = BuildContext::new(world, e);
             inner.lock().unwrap().attach(&mut bc, e);
+            attach_ct += 1;
+        }
+    }
+    QuillCounters::add(world, |c| &c.views_attached, attach_ct);
+}
+
+/// Apply atom writes which were deferred, and propagate atom changes to the things which
+/// depend on them other than presenters.
+fn apply_pending_changes(world: &mut World) {
+    DeferredAtomWrites::apply(world);
+    sync_resource_fields(world);
+    sync_class_names(world);
+    update_derived_atoms(world);
+}
+
+/// Add to `changed` the presenters which have read components that have changed since the
+/// presenter was last built.
+fn scan_changed_components(world: &mut World, changed: &mut HashSet<Entity>) {
+    let this_run = world.change_tick();
+    let mut q = world.query::<(Entity, &TrackedComponents)>();
+    for (e, tracked_components) in q.iter(world) {
+        if !changed.contains(&e)
+            && tracked_components.data.iter().any(|(cent, cid)| {
+                world
+                    .get_entity(*cent)
+                    .map(|ent| {
+                        ent.get_change_ticks_by_id(*cid)
+                            .map(|ct| ct.is_changed(tracked_components.tick, this_run))
+                            .unwrap_or(false)
+                    })
+                    .unwrap_or(false)
+            })
+        {
+            changed.insert(e);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::texts, AtomHandle, AtomMethods, Cx, Fragment, PresenterFn, View};
+
+    #[derive(Resource, Default)]
+    struct Counter(i32);
+
+    fn root(mut cx: Cx) -> impl View {
+        let count = cx.create_atom_init(|| 0);
+        Fragment::new((reader.bind(count), writer.bind(count)))
+    }
+
+    fn writer(cx: Cx<AtomHandle<i32>>) -> impl View {
+        let value = cx.use_resource::<Counter>().0;
+        cx.bc.world.set_atom(*cx.props, value);
+    }
+
+    fn reader(cx: Cx<AtomHandle<i32>>) -> impl View {
+        format!("{}", cx.read_atom(*cx.props))
+    }
+
+    #[test]
+    fn test_cascade_settles_in_one_update() {
+        let mut app = App::new();
+        app.init_resource::<Counter>();
+        app.add_systems(Update, render_views);
+        app.world.spawn(ViewHandle::new(root, ()));
+        app.update();
+        assert_eq!(texts(&mut app.world), vec!["0"]);
+
+        // Writer updates the atom, and the reader re-renders in the same update.
+        app.world.resource_mut::<Counter>().0 = 1;
+        app.update();
+        assert_eq!(texts(&mut app.world), vec!["1"]);
+        app.world.resource_mut::<Counter>().0 = 2;
+        app.update();
+        assert_eq!(texts(&mut app.world), vec!["2"]);
+    }
+
+    /// Presenter which asks to be rebuilt every time it is built.
+    fn restless(cx: Cx) -> impl View {
+        cx.bc.world.resource_mut::<Counter>().0 += 1;
+        cx.bc
+            .world
+            .entity_mut(cx.bc.entity)
+            .insert(PresenterStateChanged);
+    }
+
+    #[test]
+    fn test_divergent_presenter_rebuilt_next_frame() {
+        let mut app = App::new();
+        app.init_resource::<Counter>();
+        app.add_systems(Update, render_views);
+        app.world.spawn(ViewHandle::new(restless, ()));
+        app.update();
+        let builds = app.world.resource::<Counter>().0;
+        assert!(builds > 1);
+
+        // The cycle is cut short, but the presenter is still rebuilt on the next frame.
+        app.update();
+        assert!(app.world.resource::<Counter>().0 > builds);
+    }
+
+    #[derive(Resource, Default)]
+    struct Seen {
+        before: Vec<String>,
+        after: Vec<String>,
+    }
+
+    fn texts_in(texts: &Query<&Text>) -> Vec<String> {
+        texts.iter().map(|t| t.sections[0].value.clone()).collect()
+    }
+
+    fn hello(_cx: Cx) -> impl View {
+        "Hello"
+    }
+
+    #[test]
+    fn test_quill_sets() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<Seen>()
+            .init_resource::<bevy::a11y::Focus>()
+            .add_event::<bevy::input::mouse::MouseWheel>()
+            .add_event::<bevy::input::keyboard::KeyboardInput>()
+            .add_plugins(QuillPlugin::default().in_schedule(PostUpdate))
+            .add_systems(
+                PostUpdate,
+                (
+                    (|texts: Query<&Text>, mut seen: ResMut<Seen>| {
+                        seen.before = texts_in(&texts);
+                    })
+                    .before(QuillSet::BuildViews),
+                    (|texts: Query<&Text>, mut seen: ResMut<Seen>| {
+                        seen.after = texts_in(&texts);
+                    })
+                    .after(QuillSet::BuildViews),
+                ),
+            );
+        app.world.spawn(ViewHandle::new(hello, ()));
+        app.update();
+        // A system ordered after `BuildViews` sees the entities built in the same update.
+        let seen = app.world.resource::<Seen>();
+        assert!(seen.before.is_empty());
+        assert_eq!(seen.after, vec!["Hello"]);
+    }
+}
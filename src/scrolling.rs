@@ -1,19 +1,94 @@
-use bevy::{input::mouse::MouseWheel, prelude::*, ui};
-use bevy_mod_picking::{focus::HoverMap, pointer::PointerId, prelude::EntityEvent};
+use std::sync::Arc;
 
-/// Mouse wheel entity event
-#[derive(Clone, Event, EntityEvent)]
+use bevy::{
+    a11y::Focus,
+    ecs::system::Command,
+    input::{
+        keyboard::KeyboardInput,
+        mouse::{MouseScrollUnit, MouseWheel},
+        ButtonState,
+    },
+    prelude::*,
+    ui,
+    utils::{HashMap, HashSet},
+};
+#[cfg(feature = "picking")]
+use bevy_mod_picking::prelude::EntityEvent;
+
+use crate::{style::TransitionState, timing, ElementClasses, HoverState, Transition};
+
+/// Mouse wheel entity event. This event bubbles up the entity hierarchy from the hovered
+/// entity; a listener which consumes the event, such as a scrolling region, should call
+/// `stop_propagation()` so that enclosing scrolling regions don't also scroll.
+///
+/// The delta is always in pixels: deltas which the device reports in lines are converted
+/// using [`ScrollConfig::line_height`]. Vertical deltas are sent as horizontal deltas while
+/// Shift is held, or when the nearest enclosing [`ScrollArea`] can only scroll horizontally.
+#[derive(Clone, Event)]
+#[cfg_attr(feature = "picking", derive(EntityEvent), can_bubble)]
 pub struct ScrollWheel {
     /// Event target
-    #[target]
+    #[cfg_attr(feature = "picking", target)]
     pub target: Entity,
 
-    /// Mouse wheel delta
+    /// Mouse wheel delta, in pixels.
     pub delta: Vec2,
+
+    /// The unit which the device reported the delta in.
+    pub unit: MouseScrollUnit,
 }
 
-/// Component that enables scrolling on an element
-#[derive(Component, Default)]
+/// Resource which configures how mouse wheel events are converted into [`ScrollWheel`] events.
+#[derive(Resource)]
+pub struct ScrollConfig {
+    /// The number of pixels to scroll for each line reported by the mouse wheel.
+    pub line_height: f32,
+
+    /// How quickly a scroll which continues with momentum after a drag slows down. The velocity
+    /// is reduced by this fraction of itself each second, so higher values stop sooner.
+    pub friction: f32,
+}
+
+impl Default for ScrollConfig {
+    fn default() -> Self {
+        Self {
+            line_height: 20.,
+            friction: 4.,
+        }
+    }
+}
+
+/// Entity event which is sent to a [`ScrollArea`] when its scroll position changes. At most one
+/// event is sent per scroll area per frame, and only when the position actually changed. This
+/// event does not bubble.
+#[derive(Clone, Event)]
+#[cfg_attr(feature = "picking", derive(EntityEvent))]
+pub struct ScrollChanged {
+    /// The scroll area entity.
+    #[cfg_attr(feature = "picking", target)]
+    pub target: Entity,
+
+    /// The new horizontal scroll position.
+    pub left: f32,
+
+    /// The new vertical scroll position.
+    pub top: f32,
+}
+
+type ScrollHandlerFn = Arc<dyn Fn(Entity, Vec2, &mut World) + Send + Sync>;
+
+/// Component which runs a handler when the scroll position of a [`ScrollArea`] changes, each
+/// time a [`ScrollChanged`] event is sent to it. The handler is passed the scroll area entity
+/// and the new position. Normally this is added via [`View::on_scroll`].
+///
+/// [`View::on_scroll`]: crate::View::on_scroll
+#[derive(Component, Clone)]
+pub struct ScrollHandler(pub(crate) ScrollHandlerFn);
+
+/// Component that enables scrolling on an element. The initial scroll position can be set
+/// when constructing the component; it is kept until the scroll area has been laid out, and
+/// clamped to the size of the content after that.
+#[derive(Component)]
 pub struct ScrollArea {
     /// Scroll amount along X-axis
     pub scroll_left: f32,
@@ -32,28 +107,373 @@ pub struct ScrollArea {
 
     /// Entity id of the Y scrollbar
     pub id_scrollbar_y: Option<Entity>,
+
+    /// How far, in pixels, each scrollbar thumb can move along its track. This is measured
+    /// after layout, and used by [`ScrollArea::drag_thumb`].
+    pub thumb_travel: Vec2,
+
+    /// The animated scroll which is in progress, if any.
+    pub animation: Option<ScrollAnimation>,
+
+    /// Velocity of the momentum scroll in progress, in pixels per second. This is set when a
+    /// drag is released by [`ScrollArea::end_drag`], or can be set directly to fling the
+    /// content, and decays according to [`ScrollConfig::friction`].
+    pub velocity: Vec2,
+
+    /// Measures the velocity of a drag in progress.
+    pub drag_velocity: DragVelocity,
+
+    /// Whether [`ScrollArea::scroll_by`] can scroll past the ends of the content, against
+    /// increasing resistance, after which the content springs back into place. When false,
+    /// which is the default, scrolling stops at the ends of the content.
+    pub bounce: bool,
+
+    /// How far, in pixels, a bounce has displaced the content past the ends of its scroll
+    /// range. The scroll position itself always stays within range.
+    pub overscroll: Vec2,
+
+    /// The spring back from an overscroll which is in progress, if any.
+    pub spring: Option<ScrollAnimation>,
+
+    /// How far the arrow keys scroll, when the scroll area has [`ScrollKeyboardInput`].
+    pub line_height: f32,
+
+    /// How much of the previous page remains visible after PageUp or PageDown, so that the
+    /// reader keeps their place. PageUp and PageDown always scroll by at least `line_height`.
+    pub page_overlap: f32,
+}
+
+impl Default for ScrollArea {
+    fn default() -> Self {
+        Self {
+            scroll_left: 0.,
+            scroll_top: 0.,
+            content_size: Vec2::ZERO,
+            visible_size: Vec2::ZERO,
+            id_scrollbar_x: None,
+            id_scrollbar_y: None,
+            thumb_travel: Vec2::ZERO,
+            animation: None,
+            velocity: Vec2::ZERO,
+            drag_velocity: DragVelocity::default(),
+            bounce: false,
+            overscroll: Vec2::ZERO,
+            spring: None,
+            line_height: 20.,
+            page_overlap: 0.,
+        }
+    }
+}
+
+/// Measures how fast a drag is scrolling a [`ScrollArea`], so that the scroll can continue
+/// with momentum when the drag is released. See [`ScrollArea::begin_drag`].
+#[derive(Default)]
+pub struct DragVelocity {
+    active: bool,
+    last: Vec2,
+    velocity: Vec2,
+}
+
+/// Momentum scrolls stop once they are slower than this, in pixels per second.
+const MIN_VELOCITY: f32 = 10.;
+
+/// The furthest that a bounce can displace the content, as a fraction of the visible size.
+const MAX_OVERSCROLL: f32 = 0.25;
+
+/// How long, in seconds, the content takes to spring back after a bounce.
+const BOUNCE_DURATION: f32 = 0.3;
+
+/// The state of an animated scroll, started by [`ScrollArea::scroll_to_animated`].
+pub struct ScrollAnimation {
+    state: TransitionState,
+    origin: Vec2,
+    target: Vec2,
 }
 
 impl ScrollArea {
-    /// Offset the current scroll position by the given values.
+    /// Offset the current scroll position by the given values. This cancels any animated or
+    /// momentum scroll which is in progress.
+    ///
+    /// If [`bounce`](ScrollArea::bounce) is enabled, scrolling past the ends of the content
+    /// displaces it by [`overscroll`](ScrollArea::overscroll) instead.
     pub fn scroll_by(&mut self, dx: f32, dy: f32) {
-        // Apply max constraint first, then min - don't use clamp() here.
-        self.scroll_left = (self.scroll_left + dx)
-            .min(self.content_size.x - self.visible_size.x)
-            .max(0.);
-        self.scroll_top = (self.scroll_top + dy)
-            .min(self.content_size.y - self.visible_size.y)
-            .max(0.);
+        self.animation = None;
+        self.velocity = Vec2::ZERO;
+        let mut delta = Vec2::new(dx, dy);
+        if self.bounce {
+            // Scrolling back towards the content takes up the overscroll first.
+            let taken = Vec2::new(
+                take_overscroll(self.overscroll.x, delta.x),
+                take_overscroll(self.overscroll.y, delta.y),
+            );
+            self.overscroll += taken;
+            delta -= taken;
+        }
+        let target = Vec2::new(self.scroll_left, self.scroll_top) + delta;
+        let pos = self.clamp_position(target.x, target.y);
+        self.scroll_left = pos.x;
+        self.scroll_top = pos.y;
+        if self.bounce {
+            self.overscroll_by(target - pos);
+        }
     }
 
-    /// Scroll to the given scroll position (values clamped).
+    /// Scroll to the given scroll position (values clamped). This cancels any animated or
+    /// momentum scroll which is in progress.
     pub fn scroll_to(&mut self, x: f32, y: f32) {
+        self.animation = None;
+        self.velocity = Vec2::ZERO;
+        let pos = self.clamp_position(x, y);
+        self.scroll_left = pos.x;
+        self.scroll_top = pos.y;
+    }
+
+    /// Smoothly scroll to the given scroll position (values clamped) over `duration` seconds.
+    pub fn scroll_to_animated(&mut self, x: f32, y: f32, duration: f32) {
+        if duration <= 0. {
+            self.scroll_to(x, y);
+            return;
+        }
+        self.velocity = Vec2::ZERO;
+        self.animation = Some(ScrollAnimation {
+            state: TransitionState {
+                transition: Transition {
+                    duration,
+                    timing: timing::EASE_OUT,
+                    ..default()
+                },
+                clock: 0.,
+            },
+            origin: Vec2::new(self.scroll_left, self.scroll_top),
+            target: self.clamp_position(x, y),
+        });
+    }
+
+    /// Smoothly offset the scroll position by the given values over `duration` seconds. If an
+    /// animated scroll is already in progress, the offset is added to its target, so that
+    /// repeated calls, such as from mouse wheel events, accumulate.
+    pub fn scroll_by_animated(&mut self, dx: f32, dy: f32, duration: f32) {
+        let from = match self.animation {
+            Some(ref animation) => animation.target,
+            None => Vec2::new(self.scroll_left, self.scroll_top),
+        };
+        self.scroll_to_animated(from.x + dx, from.y + dy, duration);
+    }
+
+    /// Returns true if the content is wider than the visible area, so that it can be scrolled
+    /// horizontally.
+    pub fn overflow_x(&self) -> bool {
+        self.content_size.x > self.visible_size.x
+    }
+
+    /// Returns true if the content is taller than the visible area, so that it can be scrolled
+    /// vertically.
+    pub fn overflow_y(&self) -> bool {
+        self.content_size.y > self.visible_size.y
+    }
+
+    /// Scroll to the position which corresponds to dragging a scrollbar thumb by `delta_px`
+    /// pixels, where `origin` is the scroll position along the same axis when the drag started.
+    pub fn drag_thumb(&mut self, vertical: bool, origin: f32, delta_px: f32) {
+        let (travel, range) = if vertical {
+            (
+                self.thumb_travel.y,
+                self.content_size.y - self.visible_size.y,
+            )
+        } else {
+            (
+                self.thumb_travel.x,
+                self.content_size.x - self.visible_size.x,
+            )
+        };
+        let pos = if travel > 0. && range > 0. {
+            origin + delta_px * range / travel
+        } else {
+            origin
+        };
+        if vertical {
+            self.scroll_to(self.scroll_left, pos);
+        } else {
+            self.scroll_to(pos, self.scroll_top);
+        }
+    }
+
+    /// Start measuring the velocity of a drag, such as of a scrollbar thumb, which scrolls this
+    /// area. This stops any momentum scroll which is in progress.
+    pub fn begin_drag(&mut self) {
+        self.velocity = Vec2::ZERO;
+        self.drag_velocity = DragVelocity {
+            active: true,
+            last: Vec2::new(self.scroll_left, self.scroll_top),
+            velocity: Vec2::ZERO,
+        };
+    }
+
+    /// Stop measuring the velocity of a drag. If `fling` is true, scrolling continues at the
+    /// velocity of the drag and slows down gradually; this should be false when the drag was
+    /// cancelled.
+    pub fn end_drag(&mut self, fling: bool) {
+        if fling && self.drag_velocity.active {
+            self.velocity = self.drag_velocity.velocity;
+        }
+        self.drag_velocity = DragVelocity::default();
+    }
+
+    /// Displace the content past the ends of its scroll range by `excess` pixels, against a
+    /// resistance which increases with the overscroll, and start springing back.
+    fn overscroll_by(&mut self, excess: Vec2) {
+        let limit = self.visible_size * MAX_OVERSCROLL;
+        let resist = |over: f32, excess: f32, limit: f32| {
+            if limit > 0. {
+                (over + excess * 0.5 * (1. - over.abs() / limit)).clamp(-limit, limit)
+            } else {
+                0.
+            }
+        };
+        self.overscroll = Vec2::new(
+            resist(self.overscroll.x, excess.x, limit.x),
+            resist(self.overscroll.y, excess.y, limit.y),
+        );
+        self.spring = if self.overscroll != Vec2::ZERO {
+            Some(ScrollAnimation {
+                state: TransitionState {
+                    transition: Transition {
+                        duration: BOUNCE_DURATION,
+                        timing: timing::EASE_OUT,
+                        ..default()
+                    },
+                    clock: 0.,
+                },
+                origin: self.overscroll,
+                target: Vec2::ZERO,
+            })
+        } else {
+            None
+        };
+    }
+
+    /// Clamp a scroll position to the range allowed by the content and visible sizes.
+    fn clamp_position(&self, x: f32, y: f32) -> Vec2 {
         // Apply max constraint first, then min - don't use clamp() here.
-        self.scroll_left = x.min(self.content_size.x - self.visible_size.x).max(0.);
-        self.scroll_top = y.min(self.content_size.y - self.visible_size.y).max(0.);
+        Vec2::new(
+            x.min(self.content_size.x - self.visible_size.x).max(0.),
+            y.min(self.content_size.y - self.visible_size.y).max(0.),
+        )
+    }
+
+    /// Scroll by the minimum amount needed to make `rect` fully visible, where `rect` is
+    /// relative to the top-left corner of the scrolling content. If `rect` is larger than the
+    /// visible area, its top-left corner is aligned with the visible area, unless the visible
+    /// area already lies within it.
+    pub fn ensure_visible(&mut self, rect: Rect) {
+        self.scroll_to(
+            visible_position(
+                self.scroll_left,
+                self.visible_size.x,
+                rect.min.x,
+                rect.max.x,
+            ),
+            visible_position(self.scroll_top, self.visible_size.y, rect.min.y, rect.max.y),
+        );
     }
 }
 
+/// The part of a scroll delta, along one axis, which moves an overscrolled area back towards
+/// its content.
+fn take_overscroll(over: f32, delta: f32) -> f32 {
+    if over * delta < 0. {
+        delta.clamp(-over.abs(), over.abs())
+    } else {
+        0.
+    }
+}
+
+/// Returns the scroll position along one axis which makes the span `min..max` visible.
+fn visible_position(scroll: f32, visible: f32, min: f32, max: f32) -> f32 {
+    if max - min > visible {
+        if scroll >= min && scroll + visible <= max {
+            scroll
+        } else {
+            min
+        }
+    } else if min < scroll {
+        min
+    } else if max > scroll + visible {
+        max - visible
+    } else {
+        scroll
+    }
+}
+
+/// A [`Command`] which scrolls each [`ScrollArea`] that encloses `target`, innermost first, by
+/// the minimum amount needed to make the target visible.
+pub struct ScrollIntoView {
+    /// The entity to make visible.
+    pub target: Entity,
+
+    /// Extra space to leave around the target.
+    pub margin: f32,
+}
+
+impl Command for ScrollIntoView {
+    fn apply(self, world: &mut World) {
+        let Some(target_rect) = logical_rect(world, self.target) else {
+            return;
+        };
+        let mut rect = target_rect.inset(self.margin);
+        let mut entity = self.target;
+        while let Some(parent) = world.get::<Parent>(entity).map(Parent::get) {
+            // Only scroll areas which the target is within the content of.
+            if world.get::<ScrollArea>(parent).is_none()
+                || world.get::<ScrollContent>(entity).is_none()
+            {
+                entity = parent;
+                continue;
+            }
+            let (Some(content_rect), Some(area_rect)) =
+                (logical_rect(world, entity), logical_rect(world, parent))
+            else {
+                return;
+            };
+            let mut scrolling = world.get_mut::<ScrollArea>(parent).unwrap();
+            let prev = Vec2::new(scrolling.scroll_left, scrolling.scroll_top);
+            scrolling.ensure_visible(Rect::from_corners(
+                rect.min - content_rect.min,
+                rect.max - content_rect.min,
+            ));
+            let delta = Vec2::new(scrolling.scroll_left, scrolling.scroll_top) - prev;
+
+            // Layout isn't recalculated until later, so the positions of the target and the
+            // content are still the unscrolled ones. Account for the new scroll position
+            // before making what's visible of the target visible in the enclosing areas.
+            let viewport =
+                Rect::from_corners(area_rect.min, area_rect.min + scrolling.visible_size);
+            rect = Rect::from_corners(rect.min - delta, rect.max - delta).intersect(viewport);
+            entity = parent;
+        }
+    }
+}
+
+/// Returns the layout rectangle of an entity, in logical pixels.
+fn logical_rect(world: &World, entity: Entity) -> Option<Rect> {
+    let node = world.get::<Node>(entity)?;
+    let transform = world.get::<GlobalTransform>(entity)?;
+    Some(node.logical_rect(transform))
+}
+
+/// Marker component which lets a [`ScrollArea`] be scrolled with the keyboard while it, or one
+/// of its descendants, has keyboard focus. The arrow keys scroll by
+/// [`line_height`](ScrollArea::line_height), PageUp and PageDown scroll by the visible height
+/// less [`page_overlap`](ScrollArea::page_overlap), and Home and End scroll to the top and
+/// bottom.
+#[derive(Component, Default)]
+pub struct ScrollKeyboardInput;
+
+/// Marker component which prevents keyboard scrolling while this entity has keyboard focus.
+/// This is used by widgets such as text inputs which handle the navigation keys themselves.
+#[derive(Component, Default)]
+pub struct ScrollKeyboardOptOut;
+
 /// Marker component indicating this entity is the scrolling content area.
 #[derive(Component, Default)]
 pub struct ScrollContent;
@@ -69,27 +489,182 @@ pub struct ScrollBar {
 
     /// Minimum thumb size.
     pub min_thumb_size: f32,
+
+    /// Whether the scrollbar is hidden, with `Display::None`, when the content fits within the
+    /// scroll area along its axis. This lets a grid or flex layout collapse the space for it.
+    pub hide_when_fits: bool,
 }
 
 /// Marker component indicating this entity is a scrollbar thumb.
 #[derive(Component)]
 pub struct ScrollBarThumb;
 
-#[allow(clippy::type_complexity)]
+/// Class which [`ScrollBarAutoHide`] adds to a scrollbar while it should be shown.
+pub const CLS_SCROLLING: &str = "scrolling";
+
+/// Component which auto-hides a [`ScrollBar`]. The scrollbar is given the
+/// [`scrolling`](CLS_SCROLLING) class while its scroll area is being scrolled or is hovered, and
+/// the class is removed once it has been idle for `delay` seconds. The scrollbar's style is
+/// expected to fade it out, for example with a background color transition, when it doesn't
+/// have the class.
+#[derive(Component)]
+pub struct ScrollBarAutoHide {
+    /// How long, in seconds, the scrollbar stays visible after scrolling stops.
+    pub delay: f32,
+
+    /// Time remaining until the scrollbar is hidden.
+    remaining: f32,
+}
+
+impl ScrollBarAutoHide {
+    /// Construct a new auto-hide component with the given delay in seconds.
+    pub fn new(delay: f32) -> Self {
+        Self {
+            delay,
+            remaining: 0.,
+        }
+    }
+}
+
+/// Advance any animated scrolls which are in progress.
+pub(crate) fn animate_scroll_positions(mut query: Query<&mut ScrollArea>, time: Res<Time>) {
+    for mut scrolling in query.iter_mut() {
+        if let Some(spring) = scrolling.spring.as_mut() {
+            spring.state.advance(time.delta_seconds());
+            let over = spring.origin.lerp(spring.target, spring.state.t());
+            if spring.state.clock >= 1. {
+                scrolling.spring = None;
+            }
+            scrolling.overscroll = over;
+        }
+        if scrolling.animation.is_none() {
+            continue;
+        }
+        let scrolling = scrolling.as_mut();
+        let animation = scrolling.animation.as_mut().unwrap();
+        animation.state.advance(time.delta_seconds());
+        let pos = animation.origin.lerp(animation.target, animation.state.t());
+        if animation.state.clock >= 1. {
+            scrolling.animation = None;
+        }
+        // The content may have changed size since the animation started.
+        let pos = scrolling.clamp_position(pos.x, pos.y);
+        scrolling.scroll_left = pos.x;
+        scrolling.scroll_top = pos.y;
+    }
+}
+
+/// Measure the velocity of drags in progress, and advance momentum scrolls, slowing them by the
+/// configured friction. A momentum scroll stops along an axis when it reaches the end of the
+/// content.
+pub(crate) fn update_scroll_momentum(
+    mut query: Query<&mut ScrollArea>,
+    config: Res<ScrollConfig>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_seconds();
+    if delta <= 0. {
+        return;
+    }
+    for mut scrolling in query.iter_mut() {
+        let pos = Vec2::new(scrolling.scroll_left, scrolling.scroll_top);
+        if scrolling.drag_velocity.active {
+            // Smooth the samples, since drag events don't arrive at an even rate.
+            let tracker = &mut scrolling.drag_velocity;
+            let sample = (pos - tracker.last) / delta;
+            tracker.velocity = tracker.velocity.lerp(sample, 0.5);
+            tracker.last = pos;
+            continue;
+        }
+        if scrolling.velocity == Vec2::ZERO {
+            continue;
+        }
+        let scrolling = scrolling.as_mut();
+        let target = pos + scrolling.velocity * delta;
+        let next = scrolling.clamp_position(target.x, target.y);
+        // Momentum which runs into the end of the content stops, or with bounce enabled,
+        // carries the content past the end.
+        let stopped = Vec2::new(
+            if next.x != target.x {
+                scrolling.velocity.x
+            } else {
+                0.
+            },
+            if next.y != target.y {
+                scrolling.velocity.y
+            } else {
+                0.
+            },
+        );
+        scrolling.velocity -= stopped;
+        scrolling.scroll_left = next.x;
+        scrolling.scroll_top = next.y;
+        if scrolling.bounce && stopped != Vec2::ZERO {
+            scrolling.overscroll_by(stopped * BOUNCE_DURATION);
+        }
+        scrolling.velocity *= (1. - config.friction * delta).max(0.);
+        if scrolling.velocity.length() < MIN_VELOCITY {
+            scrolling.velocity = Vec2::ZERO;
+        }
+    }
+}
+
+/// Apply the scroll position of each scroll area to its content. This runs just before layout,
+/// so that scrolling done by any system during the frame is displayed in the same frame.
 pub(crate) fn update_scroll_positions(
+    mut query: Query<(&mut ScrollArea, &Children)>,
+    mut query_content: Query<&mut Style, (With<ScrollContent>, Without<ScrollArea>)>,
+) {
+    for (mut scrolling, children) in query.iter_mut() {
+        // Until the scroll area has been laid out, nothing is visible, so don't discard the
+        // initial scroll position by clamping it.
+        if scrolling.visible_size != Vec2::ZERO {
+            let pos = scrolling.clamp_position(scrolling.scroll_left, scrolling.scroll_top);
+            if pos != Vec2::new(scrolling.scroll_left, scrolling.scroll_top) {
+                scrolling.scroll_left = pos.x;
+                scrolling.scroll_top = pos.y;
+            }
+        }
+        if let Some(child) = children.iter().find(|c| query_content.contains(**c)) {
+            let mut style = query_content.get_mut(*child).unwrap();
+            set_content_offset(&mut style, &scrolling);
+        }
+    }
+}
+
+/// Measure each scroll area and its content after layout, clamp the scroll position if the
+/// content has shrunk, and update the size and position of the scrollbar thumbs to match.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_scroll_geometry(
     mut query: Query<(&Node, &mut ScrollArea, &GlobalTransform, &Children)>,
     mut query_content: Query<
         (&Node, &mut Style, &GlobalTransform),
         (With<ScrollContent>, Without<ScrollArea>),
     >,
-    query_scrollbar: Query<(&ScrollBar, &Children)>,
+    mut query_scrollbar: Query<
+        (
+            Entity,
+            &ScrollBar,
+            &Node,
+            &Children,
+            &mut Style,
+            Option<&HiddenScrollBar>,
+        ),
+        (Without<ScrollBarThumb>, Without<ScrollContent>),
+    >,
     mut query_scrollbar_thumb: Query<&mut Style, (With<ScrollBarThumb>, Without<ScrollContent>)>,
+    mut commands: Commands,
 ) {
     for (node, mut scrolling, gt, children) in query.iter_mut() {
-        // Measure size and update scroll width and height
-        let scroll_size = node.logical_rect(gt);
-        scrolling.visible_size.x = scroll_size.width();
-        scrolling.visible_size.y = scroll_size.height();
+        // Measure size and update scroll width and height. The scroll area is only written
+        // when something changed, so that presenters which track it don't rebuild every frame.
+        let visible_size = node.logical_rect(gt).size();
+        if scrolling.visible_size != visible_size {
+            scrolling.visible_size = visible_size;
+        }
+        // Until the scroll area has been laid out, nothing is visible, so don't discard the
+        // initial scroll position by clamping it.
+        let laid_out = scrolling.visible_size != Vec2::ZERO;
 
         // Measure size of content
         if let Some(child) = children
@@ -97,93 +672,1237 @@ pub(crate) fn update_scroll_positions(
             .find(|chid| query_content.get(**chid).is_ok())
         {
             let (content, mut style, content_gt) = query_content.get_mut(*child).unwrap();
-            let content_size = content.logical_rect(content_gt);
-            scrolling.content_size.x = content_size.width();
-            scrolling.content_size.y = content_size.height();
-
-            scrolling.scroll_left = scrolling
-                .scroll_left
-                .min(scrolling.content_size.x - scrolling.visible_size.x)
-                .max(0.);
-            scrolling.scroll_top = scrolling
-                .scroll_top
-                .min(scrolling.content_size.y - scrolling.visible_size.y)
-                .max(0.);
-
-            style.left = ui::Val::Px(-scrolling.scroll_left);
-            style.top = ui::Val::Px(-scrolling.scroll_top);
-        } else {
-            scrolling.content_size.x = 0.;
-            scrolling.content_size.y = 0.;
-        }
-
-        // Adjust horizontal scrollbar
-        if let Some(sid) = scrolling.id_scrollbar_x {
-            if let Ok((scrollbar, children)) = query_scrollbar.get(sid) {
-                if let Some(child_id) = children.first() {
-                    if let Ok(mut style) = query_scrollbar_thumb.get_mut(*child_id) {
-                        // Thumb should be equal to proportion of scroll width / content width.
-                        // Thumb should be no smaller than min size, and no bigger than full size.
-                        let thumb_size = (scrolling.visible_size.x / scrolling.content_size.x)
-                            .max(scrollbar.min_thumb_size / scrolling.visible_size.x)
-                            .min(1.);
-                        let range = scrolling.content_size.x - scrolling.visible_size.x;
-                        let scroll_pos = if range > 0. {
-                            scrolling.scroll_left * (1. - thumb_size) / range
-                        } else {
-                            0.
-                        };
-                        style.left = ui::Val::Percent(scroll_pos * 100.);
-                        style.width = ui::Val::Percent(thumb_size * 100.);
-                    }
+            let content_size = content.logical_rect(content_gt).size();
+            if scrolling.content_size != content_size {
+                scrolling.content_size = content_size;
+            }
+
+            // If the content has shrunk, the scroll position may be out of range.
+            if laid_out {
+                let pos = scrolling.clamp_position(scrolling.scroll_left, scrolling.scroll_top);
+                if pos != Vec2::new(scrolling.scroll_left, scrolling.scroll_top) {
+                    scrolling.scroll_left = pos.x;
+                    scrolling.scroll_top = pos.y;
+                    set_content_offset(&mut style, &scrolling);
                 }
             }
+        } else if scrolling.content_size != Vec2::ZERO {
+            scrolling.content_size = Vec2::ZERO;
         }
 
-        // Adjust vertical scrollbar
-        if let Some(sid) = scrolling.id_scrollbar_y {
-            if let Ok((scrollbar, children)) = query_scrollbar.get(sid) {
-                if let Some(child_id) = children.first() {
-                    if let Ok(mut style) = query_scrollbar_thumb.get_mut(*child_id) {
-                        let thumb_size = (scrolling.visible_size.y / scrolling.content_size.y)
-                            .max(scrollbar.min_thumb_size / scrolling.visible_size.y)
-                            .min(1.);
-                        let range = scrolling.content_size.y - scrolling.visible_size.y;
-                        let scroll_pos = if range > 0. {
-                            scrolling.scroll_top * (1. - thumb_size) / range
-                        } else {
-                            0.
-                        };
-                        style.top = ui::Val::Percent(scroll_pos * 100.);
-                        style.height = ui::Val::Percent(thumb_size * 100.);
-                    }
+        // Adjust scrollbars
+        let mut thumb_travel = scrolling.thumb_travel;
+        for (sid, vertical) in [
+            (scrolling.id_scrollbar_x, false),
+            (scrolling.id_scrollbar_y, true),
+        ] {
+            let Some(Ok((bar_id, scrollbar, bar_node, children, mut bar_style, hidden))) =
+                sid.map(|sid| query_scrollbar.get_mut(sid))
+            else {
+                continue;
+            };
+            let (visible, content, scroll, track) = if vertical {
+                (
+                    scrolling.visible_size.y,
+                    scrolling.content_size.y,
+                    scrolling.scroll_top,
+                    bar_node.size().y,
+                )
+            } else {
+                (
+                    scrolling.visible_size.x,
+                    scrolling.content_size.x,
+                    scrolling.scroll_left,
+                    bar_node.size().x,
+                )
+            };
+            if scrollbar.hide_when_fits {
+                set_display(
+                    &mut commands,
+                    bar_id,
+                    &mut bar_style,
+                    hidden,
+                    content > visible,
+                );
+            }
+            let (thumb_pos, thumb_size) =
+                thumb_geometry(visible, content, scroll, track, scrollbar.min_thumb_size);
+            let travel = track * (1. - thumb_size);
+            if vertical {
+                thumb_travel.y = travel;
+            } else {
+                thumb_travel.x = travel;
+            }
+            let Some(Ok(mut style)) = children
+                .first()
+                .map(|thumb| query_scrollbar_thumb.get_mut(*thumb))
+            else {
+                continue;
+            };
+            let (pos, size) = (
+                ui::Val::Percent(thumb_pos * 100.),
+                ui::Val::Percent(thumb_size * 100.),
+            );
+            if vertical {
+                if style.top != pos || style.height != size {
+                    style.top = pos;
+                    style.height = size;
                 }
+            } else if style.left != pos || style.width != size {
+                style.left = pos;
+                style.width = size;
             }
         }
+        if scrolling.thumb_travel != thumb_travel {
+            scrolling.thumb_travel = thumb_travel;
+        }
+    }
+}
+
+/// Returns the position and length of a scrollbar thumb, as fractions of the length of the
+/// track. The thumb is as long, relative to the track, as the visible area is relative to the
+/// content, but no shorter than `min_thumb_size` pixels.
+fn thumb_geometry(
+    visible: f32,
+    content: f32,
+    scroll: f32,
+    track: f32,
+    min_thumb_size: f32,
+) -> (f32, f32) {
+    if content <= visible {
+        return (0., 1.);
+    }
+    let min_size = if track > 0. {
+        min_thumb_size / track
+    } else {
+        0.
+    };
+    let size = (visible / content).max(min_size).min(1.);
+    let pos = (scroll / (content - visible)).clamp(0., 1.) * (1. - size);
+    (pos, size)
+}
+
+/// Offset the scrolling content by the scroll position, without triggering change detection if
+/// it is unchanged.
+fn set_content_offset(style: &mut Mut<Style>, scrolling: &ScrollArea) {
+    let (left, top) = (
+        ui::Val::Px(-scrolling.scroll_left - scrolling.overscroll.x),
+        ui::Val::Px(-scrolling.scroll_top - scrolling.overscroll.y),
+    );
+    if style.left != left || style.top != top {
+        style.left = left;
+        style.top = top;
+    }
+}
+
+/// Send a [`ScrollChanged`] event for each scroll area whose position has changed since the
+/// previous frame.
+pub(crate) fn send_scroll_changed(
+    query: Query<(Entity, &ScrollArea)>,
+    mut removed: RemovedComponents<ScrollArea>,
+    mut writer: EventWriter<ScrollChanged>,
+    mut positions: Local<HashMap<Entity, Vec2>>,
+) {
+    for entity in removed.read() {
+        positions.remove(&entity);
+    }
+    for (entity, scrolling) in query.iter() {
+        let pos = Vec2::new(scrolling.scroll_left, scrolling.scroll_top);
+        if positions.insert(entity, pos).unwrap_or_default() != pos {
+            writer.send(ScrollChanged {
+                target: entity,
+                left: pos.x,
+                top: pos.y,
+            });
+        }
+    }
+}
+
+/// Run the [`ScrollHandler`] of each scroll area which was sent a [`ScrollChanged`] event.
+pub(crate) fn run_scroll_handlers(
+    mut reader: EventReader<ScrollChanged>,
+    handlers: Query<&ScrollHandler>,
+    mut commands: Commands,
+) {
+    for ev in reader.read() {
+        if let Ok(ScrollHandler(handler)) = handlers.get(ev.target) {
+            let handler = handler.clone();
+            let (target, pos) = (ev.target, Vec2::new(ev.left, ev.top));
+            commands.add(move |world: &mut World| handler(target, pos, world));
+        }
+    }
+}
+
+/// Add or remove the [`scrolling`](CLS_SCROLLING) class on auto-hiding scrollbars, depending on
+/// how long it has been since their scroll area was scrolled or hovered.
+pub(crate) fn update_scrollbar_auto_hide(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &ScrollBar,
+        &mut ScrollBarAutoHide,
+        Option<&mut ElementClasses>,
+    )>,
+    mut reader: EventReader<ScrollChanged>,
+    hover_state: Res<HoverState>,
+    parents: Query<&Parent>,
+    time: Res<Time>,
+) {
+    let scrolled: HashSet<Entity> = reader.read().map(|ev| ev.target).collect();
+    // Hovering a descendant of the scroll area or scrollbar also counts as hovering it.
+    let hovered: HashSet<Entity> = hover_state
+        .hovered
+        .iter()
+        .flat_map(|e| std::iter::once(*e).chain(parents.iter_ancestors(*e)))
+        .collect();
+    for (entity, scrollbar, mut auto_hide, classes) in query.iter_mut() {
+        let area = scrollbar.id_scroll_area;
+        if scrolled.contains(&area) || hovered.contains(&area) || hovered.contains(&entity) {
+            auto_hide.remaining = auto_hide.delay;
+        } else {
+            auto_hide.remaining = (auto_hide.remaining - time.delta_seconds()).max(0.);
+        }
+        let visible = auto_hide.remaining > 0.;
+        match classes {
+            // Only write the classes when they change, so that styles aren't recomputed.
+            Some(mut classes) if classes.0.contains(CLS_SCROLLING) != visible => {
+                if visible {
+                    classes.add_class(CLS_SCROLLING);
+                } else {
+                    classes.remove_class(CLS_SCROLLING);
+                }
+            }
+            Some(_) => (),
+            None if visible => {
+                commands
+                    .entity(entity)
+                    .insert(ElementClasses([CLS_SCROLLING.to_string()].into()));
+            }
+            None => (),
+        }
+    }
+}
+
+/// Records the styled `display` of a scrollbar which has been hidden because its content fits,
+/// so that it can be restored when the scrollbar is shown again.
+#[derive(Component)]
+pub(crate) struct HiddenScrollBar(ui::Display);
+
+/// Show or hide a scrollbar, without triggering change detection if it is unchanged.
+fn set_display(
+    commands: &mut Commands,
+    entity: Entity,
+    style: &mut Mut<Style>,
+    hidden: Option<&HiddenScrollBar>,
+    visible: bool,
+) {
+    if visible {
+        if let Some(HiddenScrollBar(display)) = hidden {
+            if style.display == ui::Display::None {
+                style.display = *display;
+            }
+            commands.entity(entity).remove::<HiddenScrollBar>();
+        }
+    } else if style.display != ui::Display::None {
+        // Restyling the scrollbar writes its styled display back, so save it each time.
+        commands
+            .entity(entity)
+            .insert(HiddenScrollBar(style.display));
+        style.display = ui::Display::None;
+    }
+}
+
+pub(crate) fn handle_scroll_keys(
+    mut key_evr: EventReader<KeyboardInput>,
+    focus: Res<Focus>,
+    opt_out: Query<(), With<ScrollKeyboardOptOut>>,
+    mut query: Query<&mut ScrollArea, With<ScrollKeyboardInput>>,
+    parents: Query<&Parent>,
+) {
+    let Some(focus) = focus.0 else {
+        key_evr.clear();
+        return;
+    };
+    if opt_out.contains(focus) {
+        key_evr.clear();
+        return;
+    }
+    // Find the innermost keyboard-scrollable area containing the focus.
+    let Some(target) = std::iter::once(focus)
+        .chain(parents.iter_ancestors(focus))
+        .find(|entity| query.contains(*entity))
+    else {
+        key_evr.clear();
+        return;
+    };
+    let mut scrolling = query.get_mut(target).unwrap();
+    for ev in key_evr.read() {
+        if ev.state != ButtonState::Pressed {
+            continue;
+        }
+        let line = scrolling.line_height;
+        let page = (scrolling.visible_size.y - scrolling.page_overlap).max(line);
+        match ev.key_code {
+            KeyCode::ArrowUp => scrolling.scroll_by(0., -line),
+            KeyCode::ArrowDown => scrolling.scroll_by(0., line),
+            KeyCode::ArrowLeft => scrolling.scroll_by(-line, 0.),
+            KeyCode::ArrowRight => scrolling.scroll_by(line, 0.),
+            KeyCode::PageUp => scrolling.scroll_by(0., -page),
+            KeyCode::PageDown => scrolling.scroll_by(0., page),
+            KeyCode::Home => {
+                let left = scrolling.scroll_left;
+                scrolling.scroll_to(left, 0.);
+            }
+            KeyCode::End => {
+                let left = scrolling.scroll_left;
+                let bottom = scrolling.content_size.y;
+                scrolling.scroll_to(left, bottom);
+            }
+            _ => {}
+        }
     }
 }
 
 pub(crate) fn handle_scroll_events(
     mut scroll_evr: EventReader<MouseWheel>,
     mut writer: EventWriter<ScrollWheel>,
-    hover_map: Res<HoverMap>,
+    hover_state: Res<HoverState>,
+    config: Res<ScrollConfig>,
+    keys: Res<ButtonInput<KeyCode>>,
+    parents: Query<&Parent>,
+    scroll_areas: Query<&ScrollArea>,
 ) {
-    if let Some(hover) = hover_map.get(&PointerId::Mouse) {
-        use bevy::input::mouse::MouseScrollUnit;
+    if scroll_evr.is_empty() {
+        return;
+    }
+    let hover = &hover_state.hovered;
+    if hover.is_empty() {
+        // Discard the events, so that they aren't delivered once something is hovered.
+        scroll_evr.clear();
+    } else {
+        // Since the event bubbles, don't send it to hovered entities which are ancestors of
+        // other hovered entities, otherwise they would receive it more than once.
+        let targets: Vec<Entity> = hover
+            .iter()
+            .filter(|k| {
+                !hover
+                    .iter()
+                    .any(|other| parents.iter_ancestors(*other).any(|a| a == **k))
+            })
+            .copied()
+            .collect();
+        let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
         for ev in scroll_evr.read() {
-            match ev.unit {
-                MouseScrollUnit::Line => {
-                    // Ignore for now.
-                }
-                MouseScrollUnit::Pixel => {
-                    for k in hover.keys() {
-                        writer.send(ScrollWheel {
-                            target: *k,
-                            delta: Vec2 { x: ev.x, y: ev.y },
-                        });
+            let delta = match ev.unit {
+                MouseScrollUnit::Line => Vec2::new(ev.x, ev.y) * config.line_height,
+                MouseScrollUnit::Pixel => Vec2::new(ev.x, ev.y),
+            };
+            for target in targets.iter() {
+                // Devices which can scroll horizontally report it themselves, so only route
+                // purely vertical deltas to the x axis.
+                let horizontal = delta.x == 0.
+                    && (shift
+                        || std::iter::once(*target)
+                            .chain(parents.iter_ancestors(*target))
+                            .find_map(|e| scroll_areas.get(e).ok())
+                            .is_some_and(|area| area.overflow_x() && !area.overflow_y()));
+                writer.send(ScrollWheel {
+                    target: *target,
+                    delta: if horizontal {
+                        Vec2::new(delta.y, 0.)
+                    } else {
+                        delta
+                    },
+                    unit: ev.unit,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "picking")]
+    use bevy_mod_picking::prelude::{EventListenerPlugin, ListenerInput, On};
+
+    use super::*;
+
+    /// Spawn a scroll area which scrolls in response to [`ScrollWheel`] events, and stops their
+    /// propagation if `stop` is true.
+    fn scroll_area(world: &mut World, stop: bool) -> Entity {
+        let area = world
+            .spawn(ScrollArea {
+                content_size: Vec2::new(100., 1000.),
+                visible_size: Vec2::new(100., 100.),
+                ..default()
+            })
+            .id();
+        #[cfg(feature = "picking")]
+        world
+            .entity_mut(area)
+            .insert(On::<ScrollWheel>::listener_component_mut::<ScrollArea>(
+                move |ev: &mut ListenerInput<ScrollWheel>, scrolling| {
+                    if stop {
+                        ev.stop_propagation();
                     }
-                }
+                    scrolling.scroll_by(-ev.delta.x, -ev.delta.y);
+                },
+            ));
+        #[cfg(not(feature = "picking"))]
+        let _ = stop;
+        area
+    }
+
+    #[cfg(feature = "picking")]
+    fn scroll_nested(stop: bool) -> (f32, f32) {
+        let mut app = App::new();
+        app.add_plugins(EventListenerPlugin::<ScrollWheel>::default());
+        let outer = scroll_area(&mut app.world, stop);
+        let inner = scroll_area(&mut app.world, stop);
+        let content = app.world.spawn_empty().id();
+        app.world.entity_mut(outer).add_child(inner);
+        app.world.entity_mut(inner).add_child(content);
+
+        app.world.send_event(ScrollWheel {
+            target: content,
+            delta: Vec2::new(0., -10.),
+            unit: MouseScrollUnit::Pixel,
+        });
+        app.update();
+        (
+            app.world.get::<ScrollArea>(outer).unwrap().scroll_top,
+            app.world.get::<ScrollArea>(inner).unwrap().scroll_top,
+        )
+    }
+
+    /// Set the laid out size of a node, which is otherwise only written by the layout system.
+    fn set_size(world: &mut World, entity: Entity, size: Vec2) {
+        use bevy::reflect::Struct;
+
+        let mut node = world.get_mut::<Node>(entity).unwrap();
+        *node
+            .field_mut("calculated_size")
+            .unwrap()
+            .downcast_mut::<Vec2>()
+            .unwrap() = size;
+    }
+
+    #[test]
+    fn test_ensure_visible() {
+        let mut scrolling = ScrollArea {
+            scroll_top: 100.,
+            content_size: Vec2::new(100., 1000.),
+            visible_size: Vec2::new(100., 100.),
+            ..default()
+        };
+        // Already visible.
+        scrolling.ensure_visible(Rect::new(0., 150., 10., 200.));
+        assert_eq!(scrolling.scroll_top, 100.);
+        // Below the visible area.
+        scrolling.ensure_visible(Rect::new(0., 250., 10., 300.));
+        assert_eq!(scrolling.scroll_top, 200.);
+        // Above the visible area.
+        scrolling.ensure_visible(Rect::new(0., 50., 10., 60.));
+        assert_eq!(scrolling.scroll_top, 50.);
+        // Larger than the visible area.
+        scrolling.ensure_visible(Rect::new(0., 400., 10., 600.));
+        assert_eq!(scrolling.scroll_top, 400.);
+        scrolling.ensure_visible(Rect::new(0., 350., 10., 600.));
+        assert_eq!(scrolling.scroll_top, 400.);
+        // Clamped to the content.
+        scrolling.ensure_visible(Rect::new(0., 990., 10., 1010.));
+        assert_eq!(scrolling.scroll_top, 900.);
+        assert_eq!(scrolling.scroll_left, 0.);
+    }
+
+    #[test]
+    fn test_ensure_visible_horizontal() {
+        let mut scrolling = ScrollArea {
+            scroll_left: 100.,
+            scroll_top: 50.,
+            content_size: Vec2::new(1000., 200.),
+            visible_size: Vec2::new(100., 100.),
+            ..default()
+        };
+        // Already visible.
+        scrolling.ensure_visible(Rect::new(120., 60., 180., 70.));
+        assert_eq!(scrolling.scroll_left, 100.);
+        // Right of the visible area.
+        scrolling.ensure_visible(Rect::new(250., 60., 300., 70.));
+        assert_eq!(scrolling.scroll_left, 200.);
+        // Left of the visible area.
+        scrolling.ensure_visible(Rect::new(20., 60., 40., 70.));
+        assert_eq!(scrolling.scroll_left, 20.);
+        // Larger than the visible area.
+        scrolling.ensure_visible(Rect::new(300., 60., 600., 70.));
+        assert_eq!(scrolling.scroll_left, 300.);
+        // The vertical position was never changed.
+        assert_eq!(scrolling.scroll_top, 50.);
+    }
+
+    #[test]
+    fn test_momentum() {
+        use bevy::ecs::system::RunSystemOnce;
+        use std::time::Duration;
+
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<ScrollConfig>();
+        let entity = world
+            .spawn(ScrollArea {
+                content_size: Vec2::new(100., 1000.),
+                visible_size: Vec2::new(100., 100.),
+                velocity: Vec2::new(0., 500.),
+                ..default()
+            })
+            .id();
+        let step = |world: &mut World| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(20));
+            world.run_system_once(update_scroll_momentum);
+            world.get::<ScrollArea>(entity).unwrap().scroll_top
+        };
+
+        // The scroll advances, slowing down each frame.
+        let first = step(&mut world);
+        let second = step(&mut world);
+        assert!(first > 0.);
+        assert!(second - first > 0. && second - first < first);
+
+        // And then halts.
+        let mut top = second;
+        for _ in 0..200 {
+            top = step(&mut world);
+        }
+        let scrolling = world.get::<ScrollArea>(entity).unwrap();
+        assert_eq!(scrolling.velocity, Vec2::ZERO);
+        assert!(top > second && top < 900.);
+        assert_eq!(step(&mut world), top);
+
+        // Momentum stops at the end of the content.
+        world.get_mut::<ScrollArea>(entity).unwrap().velocity = Vec2::new(0., 100000.);
+        assert_eq!(step(&mut world), 900.);
+        assert_eq!(
+            world.get::<ScrollArea>(entity).unwrap().velocity,
+            Vec2::ZERO
+        );
+
+        // Releasing a drag continues at the speed of the drag.
+        world
+            .get_mut::<ScrollArea>(entity)
+            .unwrap()
+            .scroll_to(0., 0.);
+        world.get_mut::<ScrollArea>(entity).unwrap().begin_drag();
+        for i in 1..=5 {
+            world
+                .get_mut::<ScrollArea>(entity)
+                .unwrap()
+                .scroll_to(0., i as f32 * 10.);
+            step(&mut world);
+        }
+        let mut scrolling = world.get_mut::<ScrollArea>(entity).unwrap();
+        scrolling.end_drag(true);
+        assert!(scrolling.velocity.y > 0.);
+        assert!(step(&mut world) > 50.);
+
+        // A cancelled drag doesn't.
+        let mut scrolling = world.get_mut::<ScrollArea>(entity).unwrap();
+        scrolling.begin_drag();
+        scrolling.scroll_to(0., 200.);
+        step(&mut world);
+        let mut scrolling = world.get_mut::<ScrollArea>(entity).unwrap();
+        scrolling.end_drag(false);
+        assert_eq!(scrolling.velocity, Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_scroll_animated() {
+        use bevy::ecs::system::RunSystemOnce;
+        use std::time::Duration;
+
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let entity = world
+            .spawn(ScrollArea {
+                content_size: Vec2::new(100., 1000.),
+                visible_size: Vec2::new(100., 100.),
+                ..default()
+            })
+            .id();
+        let step = |world: &mut World, ms: u64| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(ms));
+            world.run_system_once(animate_scroll_positions);
+            world.get::<ScrollArea>(entity).unwrap().scroll_top
+        };
+
+        world
+            .get_mut::<ScrollArea>(entity)
+            .unwrap()
+            .scroll_to_animated(0., 200., 0.2);
+        let mid = step(&mut world, 100);
+        assert!(mid > 0. && mid < 200.);
+        assert_eq!(step(&mut world, 100), 200.);
+        assert!(world.get::<ScrollArea>(entity).unwrap().animation.is_none());
+
+        // Animated offsets accumulate into the target.
+        let mut scrolling = world.get_mut::<ScrollArea>(entity).unwrap();
+        scrolling.scroll_by_animated(0., 100., 0.2);
+        scrolling.scroll_by_animated(0., 100., 0.2);
+        assert_eq!(step(&mut world, 200), 400.);
+
+        // Targets are clamped to the content.
+        let mut scrolling = world.get_mut::<ScrollArea>(entity).unwrap();
+        scrolling.scroll_to_animated(0., 2000., 0.2);
+        assert_eq!(step(&mut world, 200), 900.);
+
+        // Scrolling directly cancels the animation.
+        let mut scrolling = world.get_mut::<ScrollArea>(entity).unwrap();
+        scrolling.scroll_to_animated(0., 0., 0.2);
+        step(&mut world, 100);
+        world
+            .get_mut::<ScrollArea>(entity)
+            .unwrap()
+            .scroll_by(0., 10.);
+        let top = world.get::<ScrollArea>(entity).unwrap().scroll_top;
+        assert_eq!(step(&mut world, 100), top);
+    }
+
+    #[test]
+    fn test_scroll_into_view_nested() {
+        let mut world = World::new();
+        // Nodes aren't laid out here, so they are zero-sized and positioned at their top-left
+        // corners, with the sizes of the scroll areas given by `visible_size`.
+        let spawn = |world: &mut World, y: f32| {
+            world
+                .spawn((Node::default(), GlobalTransform::from_xyz(0., y, 0.)))
+                .id()
+        };
+        let area = |world: &mut World, y: f32| {
+            let entity = spawn(world, y);
+            world.entity_mut(entity).insert(ScrollArea {
+                content_size: Vec2::new(100., 1000.),
+                visible_size: Vec2::new(100., 100.),
+                ..default()
+            });
+            let content = spawn(world, y);
+            world
+                .entity_mut(content)
+                .insert(ScrollContent)
+                .set_parent(entity);
+            (entity, content)
+        };
+        let (outer, outer_content) = area(&mut world, 0.);
+        let (inner, inner_content) = area(&mut world, 500.);
+        world.entity_mut(inner).set_parent(outer_content);
+        let target = spawn(&mut world, 800.);
+        world.entity_mut(target).set_parent(inner_content);
+
+        ScrollIntoView {
+            target,
+            margin: 10.,
+        }
+        .apply(&mut world);
+        assert_eq!(world.get::<ScrollArea>(inner).unwrap().scroll_top, 210.);
+        assert_eq!(world.get::<ScrollArea>(outer).unwrap().scroll_top, 500.);
+    }
+
+    /// Press `key_code`, and return the resulting scroll position of `area`.
+    fn press(world: &mut World, area: Entity, key_code: KeyCode) -> f32 {
+        use bevy::{
+            ecs::system::RunSystemOnce,
+            input::keyboard::{Key, NativeKey},
+        };
+
+        world.send_event(KeyboardInput {
+            key_code,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        });
+        world.run_system_once(handle_scroll_keys);
+        world.resource_mut::<Events<KeyboardInput>>().clear();
+        world.get::<ScrollArea>(area).unwrap().scroll_top
+    }
+
+    #[test]
+    fn test_scroll_keys() {
+        let mut world = World::new();
+        world.init_resource::<Focus>();
+        world.init_resource::<Events<KeyboardInput>>();
+        let area = scroll_area(&mut world, true);
+        world.get_mut::<ScrollArea>(area).unwrap().line_height = 15.;
+        world.entity_mut(area).insert(ScrollKeyboardInput);
+        let child = world.spawn_empty().id();
+        let text_input = world.spawn(ScrollKeyboardOptOut).id();
+        world.entity_mut(area).push_children(&[child, text_input]);
+        let press = |world: &mut World, key_code: KeyCode| press(world, area, key_code);
+
+        // Keys are ignored unless a descendant of the scroll area has focus.
+        assert_eq!(press(&mut world, KeyCode::PageDown), 0.);
+        world.resource_mut::<Focus>().0 = Some(child);
+        assert_eq!(press(&mut world, KeyCode::PageDown), 100.);
+        assert_eq!(press(&mut world, KeyCode::ArrowDown), 115.);
+        assert_eq!(press(&mut world, KeyCode::ArrowUp), 100.);
+        assert_eq!(press(&mut world, KeyCode::End), 900.);
+        assert_eq!(press(&mut world, KeyCode::PageDown), 900.);
+        assert_eq!(press(&mut world, KeyCode::PageUp), 800.);
+        assert_eq!(press(&mut world, KeyCode::Home), 0.);
+
+        // Elements which handle the keys themselves can opt out.
+        world.resource_mut::<Focus>().0 = Some(text_input);
+        assert_eq!(press(&mut world, KeyCode::PageDown), 0.);
+    }
+
+    #[test]
+    fn test_page_down() {
+        let mut world = World::new();
+        world.init_resource::<Events<KeyboardInput>>();
+        let area = scroll_area(&mut world, true);
+        let mut scrolling = world.get_mut::<ScrollArea>(area).unwrap();
+        scrolling.line_height = 15.;
+        scrolling.page_overlap = 20.;
+        world.entity_mut(area).insert(ScrollKeyboardInput);
+        let child = world.spawn_empty().id();
+        world.entity_mut(area).add_child(child);
+        world.insert_resource(Focus(Some(child)));
+        let page_down = |world: &mut World| press(world, area, KeyCode::PageDown);
+
+        // Each page is the visible height, less the overlap.
+        assert_eq!(page_down(&mut world), 80.);
+        assert_eq!(page_down(&mut world), 160.);
+
+        // The last page is clamped to the end of the content.
+        world
+            .get_mut::<ScrollArea>(area)
+            .unwrap()
+            .scroll_to(0., 850.);
+        assert_eq!(page_down(&mut world), 900.);
+        assert_eq!(page_down(&mut world), 900.);
+    }
+
+    #[test]
+    fn test_hide_when_fits() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        let area = world
+            .spawn((Node::default(), GlobalTransform::default()))
+            .id();
+        let content = world
+            .spawn((
+                Node::default(),
+                Style::default(),
+                GlobalTransform::default(),
+                ScrollContent,
+            ))
+            .id();
+        world.entity_mut(area).add_child(content);
+        let scrollbar = |world: &mut World, vertical: bool| {
+            let thumb = world.spawn((Style::default(), ScrollBarThumb)).id();
+            world
+                .spawn((
+                    Node::default(),
+                    Style::default(),
+                    ScrollBar {
+                        vertical,
+                        id_scroll_area: area,
+                        min_thumb_size: 10.,
+                        hide_when_fits: true,
+                    },
+                ))
+                .add_child(thumb)
+                .id()
+        };
+        let bar_x = scrollbar(&mut world, false);
+        let bar_y = scrollbar(&mut world, true);
+        // The styled display is restored when a scrollbar is shown again.
+        world.get_mut::<Style>(bar_y).unwrap().display = ui::Display::Grid;
+        world.entity_mut(area).insert(ScrollArea {
+            id_scrollbar_x: Some(bar_x),
+            id_scrollbar_y: Some(bar_y),
+            ..default()
+        });
+        set_size(&mut world, area, Vec2::new(100., 100.));
+
+        let display = |world: &mut World, content_size: Vec2| {
+            set_size(world, content, content_size);
+            world.run_system_once(update_scroll_geometry);
+            (
+                world.get::<Style>(bar_x).unwrap().display,
+                world.get::<Style>(bar_y).unwrap().display,
+            )
+        };
+
+        // Content which fits exactly doesn't need scrollbars.
+        assert_eq!(
+            display(&mut world, Vec2::new(100., 100.)),
+            (ui::Display::None, ui::Display::None)
+        );
+        assert_eq!(
+            display(&mut world, Vec2::new(100., 101.)),
+            (ui::Display::None, ui::Display::Grid)
+        );
+        assert_eq!(
+            display(&mut world, Vec2::new(101., 50.)),
+            (ui::Display::Flex, ui::Display::None)
+        );
+        assert_eq!(
+            display(&mut world, Vec2::new(99., 99.)),
+            (ui::Display::None, ui::Display::None)
+        );
+    }
+
+    #[test]
+    fn test_scroll_position_round_trip() {
+        use bevy::ecs::system::{RunSystemOnce, SystemId};
+
+        // Spawn a scroll area, which isn't laid out until the next frame.
+        fn spawn(world: &mut World, scroll_top: f32) -> (Entity, Entity) {
+            let area = world
+                .spawn((
+                    Node::default(),
+                    GlobalTransform::default(),
+                    ScrollArea {
+                        scroll_top,
+                        ..default()
+                    },
+                ))
+                .id();
+            let content = world
+                .spawn((
+                    Node::default(),
+                    Style::default(),
+                    GlobalTransform::default(),
+                    ScrollContent,
+                ))
+                .id();
+            world.entity_mut(area).add_child(content);
+            (area, content)
+        }
+
+        fn update(world: &mut World) -> Vec<(f32, f32)> {
+            world.run_system_once(update_scroll_positions);
+            world.run_system_once(update_scroll_geometry);
+            let send = *world.resource::<SendSystem>();
+            world.run_system(send.0).unwrap();
+            world
+                .resource_mut::<Events<ScrollChanged>>()
+                .drain()
+                .map(|ev| (ev.left, ev.top))
+                .collect()
+        }
+
+        // The sending system needs to keep its state between runs.
+        #[derive(Resource, Clone, Copy)]
+        struct SendSystem(SystemId);
+
+        let mut world = World::new();
+        world.init_resource::<Events<ScrollChanged>>();
+        let send = world.register_system(send_scroll_changed);
+        world.insert_resource(SendSystem(send));
+        let (area, content) = spawn(&mut world, 0.);
+        assert!(update(&mut world).is_empty());
+        set_size(&mut world, area, Vec2::new(100., 100.));
+        set_size(&mut world, content, Vec2::new(100., 1000.));
+        assert!(update(&mut world).is_empty());
+
+        world
+            .get_mut::<ScrollArea>(area)
+            .unwrap()
+            .scroll_to(0., 120.);
+        assert_eq!(update(&mut world), vec![(0., 120.)]);
+        // Unchanged positions aren't sent again.
+        assert!(update(&mut world).is_empty());
+
+        // Rebuild the scroll area with the saved position.
+        world.entity_mut(area).despawn_recursive();
+        let (area, content) = spawn(&mut world, 120.);
+        assert_eq!(update(&mut world), vec![(0., 120.)]);
+        assert_eq!(world.get::<ScrollArea>(area).unwrap().scroll_top, 120.);
+        set_size(&mut world, area, Vec2::new(100., 100.));
+        set_size(&mut world, content, Vec2::new(100., 1000.));
+        assert!(update(&mut world).is_empty());
+        assert_eq!(world.get::<ScrollArea>(area).unwrap().scroll_top, 120.);
+        assert_eq!(world.get::<Style>(content).unwrap().top, ui::Val::Px(-120.));
+    }
+
+    #[test]
+    fn test_thumb_geometry() {
+        // Content which fits fills the track.
+        assert_eq!(thumb_geometry(100., 100., 0., 100., 10.), (0., 1.));
+        assert_eq!(thumb_geometry(100., 50., 0., 100., 10.), (0., 1.));
+        // The thumb is proportional to the visible part of the content.
+        assert_eq!(thumb_geometry(100., 400., 0., 100., 10.), (0., 0.25));
+        assert_eq!(thumb_geometry(100., 400., 150., 100., 10.), (0.375, 0.25));
+        assert_eq!(thumb_geometry(100., 400., 300., 100., 10.), (0.75, 0.25));
+        // The minimum size is relative to the track, not the visible area.
+        assert_eq!(thumb_geometry(100., 10000., 0., 200., 20.), (0., 0.1));
+        assert_eq!(thumb_geometry(100., 10000., 9900., 200., 20.), (0.9, 0.1));
+    }
+
+    #[test]
+    fn test_scroll_clamp() {
+        let mut scrolling = ScrollArea {
+            content_size: Vec2::new(300., 1000.),
+            visible_size: Vec2::new(100., 100.),
+            ..default()
+        };
+        // Past the start.
+        scrolling.scroll_by(-50., -50.);
+        assert_eq!((scrolling.scroll_left, scrolling.scroll_top), (0., 0.));
+        scrolling.scroll_to(-10., -10.);
+        assert_eq!((scrolling.scroll_left, scrolling.scroll_top), (0., 0.));
+        // Past the end.
+        scrolling.scroll_by(500., 2000.);
+        assert_eq!((scrolling.scroll_left, scrolling.scroll_top), (200., 900.));
+        scrolling.scroll_to(1000., 1000.);
+        assert_eq!((scrolling.scroll_left, scrolling.scroll_top), (200., 900.));
+        // Content which fits never scrolls, rather than scrolling to a negative position.
+        scrolling.content_size = Vec2::new(50., 50.);
+        scrolling.scroll_by(10., 10.);
+        assert_eq!((scrolling.scroll_left, scrolling.scroll_top), (0., 0.));
+        // Without bounce, there's never any overscroll.
+        assert_eq!(scrolling.overscroll, Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_bounce() {
+        use bevy::ecs::system::RunSystemOnce;
+        use std::time::Duration;
+
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let entity = world
+            .spawn(ScrollArea {
+                content_size: Vec2::new(100., 1000.),
+                visible_size: Vec2::new(100., 100.),
+                bounce: true,
+                ..default()
+            })
+            .id();
+        let scroll_by = |world: &mut World, dy: f32| {
+            let mut scrolling = world.get_mut::<ScrollArea>(entity).unwrap();
+            scrolling.scroll_by(0., dy);
+            (scrolling.scroll_top, scrolling.overscroll.y)
+        };
+
+        // Scrolling past the start displaces the content against resistance, but the scroll
+        // position stays in range.
+        let (top, over) = scroll_by(&mut world, -20.);
+        assert_eq!(top, 0.);
+        assert!(over < 0. && over > -20.);
+        // The displacement is limited.
+        for _ in 0..20 {
+            scroll_by(&mut world, -100.);
+        }
+        assert!(scroll_by(&mut world, -100.).1 >= -25.);
+        // Scrolling back takes up the displacement before moving the content.
+        assert_eq!(scroll_by(&mut world, 10.).0, 0.);
+
+        // The content springs back, and settles in place.
+        let mut over = world.get::<ScrollArea>(entity).unwrap().overscroll.y;
+        for _ in 0..20 {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(20));
+            world.run_system_once(animate_scroll_positions);
+            let next = world.get::<ScrollArea>(entity).unwrap().overscroll.y;
+            assert!(next >= over && next <= 0.);
+            over = next;
+        }
+        let scrolling = world.get::<ScrollArea>(entity).unwrap();
+        assert_eq!(scrolling.overscroll, Vec2::ZERO);
+        assert!(scrolling.spring.is_none());
+        assert_eq!(scrolling.scroll_top, 0.);
+
+        // The same happens at the end.
+        world
+            .get_mut::<ScrollArea>(entity)
+            .unwrap()
+            .scroll_to(0., 900.);
+        let (top, over) = scroll_by(&mut world, 20.);
+        assert_eq!(top, 900.);
+        assert!(over > 0. && over < 20.);
+    }
+
+    #[test]
+    fn test_drag_thumb() {
+        // The content is wide and short, so that mixing up the axes gives the wrong answer.
+        let mut scrolling = ScrollArea {
+            content_size: Vec2::new(1000., 400.),
+            visible_size: Vec2::new(200., 100.),
+            // Thumbs are a fifth and a quarter of 200 and 100 pixel tracks.
+            thumb_travel: Vec2::new(160., 75.),
+            ..default()
+        };
+        // 300 pixels of vertical scrolling over 75 pixels of travel.
+        scrolling.drag_thumb(true, 0., 25.);
+        assert_eq!(scrolling.scroll_top, 100.);
+        scrolling.drag_thumb(true, 100., -10.);
+        assert_eq!(scrolling.scroll_top, 60.);
+        // 800 pixels of horizontal scrolling over 160 pixels of travel.
+        scrolling.drag_thumb(false, 0., 40.);
+        assert_eq!(scrolling.scroll_left, 200.);
+        assert_eq!(scrolling.scroll_top, 60.);
+        // Dragging past the end of the track is clamped.
+        scrolling.drag_thumb(false, 200., 1000.);
+        assert_eq!(scrolling.scroll_left, 800.);
+    }
+
+    #[test]
+    fn test_drag_thumb_events() {
+        use crate::{plugin::render_views, Cx, Element, View, ViewHandle};
+
+        #[derive(Resource, Default)]
+        struct Scrolled(Vec<Vec2>);
+
+        fn presenter(_cx: Cx) -> impl View {
+            Element::new()
+                .insert(ScrollArea {
+                    content_size: Vec2::new(100., 400.),
+                    visible_size: Vec2::new(100., 100.),
+                    thumb_travel: Vec2::new(0., 75.),
+                    ..default()
+                })
+                .on_scroll(|_, pos, world| world.resource_mut::<Scrolled>().0.push(pos))
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Events<ScrollChanged>>();
+        world.init_resource::<Scrolled>();
+        world.spawn(ViewHandle::new(presenter, ()));
+        render_views(&mut world);
+        let area = world
+            .query_filtered::<Entity, With<ScrollArea>>()
+            .single(&world);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((send_scroll_changed, run_scroll_handlers).chain());
+        schedule.run(&mut world);
+        world.resource_mut::<Events<ScrollChanged>>().update();
+
+        // Drag the thumb down the track, moving it several times in each frame.
+        for frame in 1..=5 {
+            for step in 0..3 {
+                let delta = (frame * 3 + step) as f32 * 2.;
+                world
+                    .get_mut::<ScrollArea>(area)
+                    .unwrap()
+                    .drag_thumb(true, 0., delta);
             }
+            schedule.run(&mut world);
+            world.resource_mut::<Events<ScrollChanged>>().update();
         }
+        // A frame in which the thumb doesn't move sends no event.
+        schedule.run(&mut world);
+
+        // One event per frame, each further down than the last.
+        let scrolled = &world.resource::<Scrolled>().0;
+        assert_eq!(scrolled.len(), 5);
+        assert!(scrolled.windows(2).all(|w| w[1].y > w[0].y));
+        assert!(scrolled.iter().all(|pos| pos.x == 0.));
+        assert_eq!(scrolled[4].y, 136.);
+    }
+
+    #[test]
+    fn test_auto_hide() {
+        use std::time::Duration;
+
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<HoverState>();
+        world.init_resource::<Events<ScrollChanged>>();
+        let area = world.spawn(ScrollArea::default()).id();
+        let content = world.spawn_empty().id();
+        world.entity_mut(area).add_child(content);
+        let scrollbar = world
+            .spawn((
+                ScrollBar {
+                    vertical: true,
+                    id_scroll_area: area,
+                    min_thumb_size: 10.,
+                    hide_when_fits: false,
+                },
+                ScrollBarAutoHide::new(0.5),
+            ))
+            .id();
+        // The system needs to keep its event reader between runs.
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_scrollbar_auto_hide);
+        let mut step = |world: &mut World, ms: u64| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(ms));
+            schedule.run(world);
+            world.resource_mut::<Events<ScrollChanged>>().update();
+            world
+                .get::<ElementClasses>(scrollbar)
+                .is_some_and(|classes| classes.0.contains(CLS_SCROLLING))
+        };
+
+        // Hidden until the area is scrolled.
+        assert!(!step(&mut world, 100));
+        world.send_event(ScrollChanged {
+            target: area,
+            left: 0.,
+            top: 10.,
+        });
+        assert!(step(&mut world, 100));
+
+        // Still shown before the delay has passed, then hidden.
+        assert!(step(&mut world, 300));
+        assert!(!step(&mut world, 300));
+
+        // Hovering the content shows it until the pointer leaves.
+        world.resource_mut::<HoverState>().set_hovered([content]);
+        assert!(step(&mut world, 100));
+        assert!(step(&mut world, 1000));
+        world.resource_mut::<HoverState>().set_hovered([]);
+        assert!(step(&mut world, 300));
+        assert!(!step(&mut world, 300));
+    }
+
+    #[test]
+    fn test_content_shrinks() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        let area = world
+            .spawn((
+                Node::default(),
+                GlobalTransform::default(),
+                ScrollArea::default(),
+            ))
+            .id();
+        let content = world
+            .spawn((
+                Node::default(),
+                Style::default(),
+                GlobalTransform::default(),
+                ScrollContent,
+            ))
+            .id();
+        let thumb = world.spawn((Style::default(), ScrollBarThumb)).id();
+        let bar = world
+            .spawn((
+                Node::default(),
+                Style::default(),
+                ScrollBar {
+                    vertical: true,
+                    id_scroll_area: area,
+                    min_thumb_size: 10.,
+                    hide_when_fits: false,
+                },
+            ))
+            .add_child(thumb)
+            .id();
+        world.entity_mut(area).add_child(content);
+        world.get_mut::<ScrollArea>(area).unwrap().id_scrollbar_y = Some(bar);
+        set_size(&mut world, area, Vec2::new(100., 100.));
+        set_size(&mut world, bar, Vec2::new(8., 100.));
+        set_size(&mut world, content, Vec2::new(100., 1000.));
+        world.run_system_once(update_scroll_geometry);
+        world
+            .get_mut::<ScrollArea>(area)
+            .unwrap()
+            .scroll_to(0., 900.);
+        world.run_system_once(update_scroll_positions);
+        world.run_system_once(update_scroll_geometry);
+        let thumb_style = world.get::<Style>(thumb).unwrap();
+        assert_eq!(thumb_style.top, ui::Val::Percent(90.));
+        assert_eq!(thumb_style.height, ui::Val::Percent(10.));
+        assert_eq!(world.get::<ScrollArea>(area).unwrap().thumb_travel.y, 90.);
+
+        // When the content shrinks, the scroll position and thumb are updated after layout.
+        set_size(&mut world, content, Vec2::new(100., 400.));
+        world.run_system_once(update_scroll_geometry);
+        assert_eq!(world.get::<ScrollArea>(area).unwrap().scroll_top, 300.);
+        assert_eq!(world.get::<Style>(content).unwrap().top, ui::Val::Px(-300.));
+        let thumb_style = world.get::<Style>(thumb).unwrap();
+        assert_eq!(thumb_style.top, ui::Val::Percent(75.));
+        assert_eq!(thumb_style.height, ui::Val::Percent(25.));
+    }
+
+    #[test]
+    #[cfg(feature = "picking")]
+    fn test_scroll_stop_propagation() {
+        // Event bubbles to both scroll areas.
+        assert_eq!(scroll_nested(false), (10., 10.));
+        // Inner scroll area consumes the event.
+        assert_eq!(scroll_nested(true), (0., 10.));
+    }
+
+    #[test]
+    #[cfg(feature = "picking")]
+    fn test_wheel_units() {
+        let mut app = App::new();
+        app.add_plugins(EventListenerPlugin::<ScrollWheel>::default())
+            .init_resource::<ScrollConfig>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<HoverState>()
+            .add_event::<MouseWheel>()
+            .add_event::<ScrollWheel>()
+            .add_systems(Update, handle_scroll_events);
+        let area = scroll_area(&mut app.world, true);
+        let content = app.world.spawn_empty().id();
+        app.world.entity_mut(area).add_child(content);
+        app.world
+            .resource_mut::<HoverState>()
+            .set_hovered([content]);
+
+        let wheel = |app: &mut App, unit: MouseScrollUnit, y: f32| {
+            app.world.send_event(MouseWheel {
+                unit,
+                x: 0.,
+                y,
+                window: Entity::PLACEHOLDER,
+            });
+            // The listener sees the event on the following update.
+            app.update();
+            app.update();
+            let scrolling = app.world.get::<ScrollArea>(area).unwrap();
+            (scrolling.scroll_left, scrolling.scroll_top)
+        };
+
+        // Lines are converted to pixels.
+        assert_eq!(wheel(&mut app, MouseScrollUnit::Line, -2.), (0., 40.));
+        assert_eq!(wheel(&mut app, MouseScrollUnit::Pixel, -15.), (0., 55.));
+
+        // Shift scrolls horizontally.
+        app.world.get_mut::<ScrollArea>(area).unwrap().content_size = Vec2::new(1000., 1000.);
+        app.world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ShiftLeft);
+        assert_eq!(wheel(&mut app, MouseScrollUnit::Line, -1.), (20., 55.));
+        app.world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(KeyCode::ShiftLeft);
+
+        // So does a scroll area which can only scroll horizontally.
+        app.world.get_mut::<ScrollArea>(area).unwrap().content_size = Vec2::new(1000., 100.);
+        assert_eq!(wheel(&mut app, MouseScrollUnit::Line, -1.), (40., 0.));
+
+        // Scrolling while nothing is hovered is discarded, rather than delivered on the next
+        // frame if something is hovered by then.
+        app.world
+            .resource_mut::<HoverState>()
+            .set_hovered(std::iter::empty());
+        app.world.send_event(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.,
+            y: -1.,
+            window: Entity::PLACEHOLDER,
+        });
+        app.world.run_schedule(Update);
+        app.world
+            .resource_mut::<HoverState>()
+            .set_hovered([content]);
+        app.update();
+        app.update();
+        let scrolling = app.world.get::<ScrollArea>(area).unwrap();
+        assert_eq!((scrolling.scroll_left, scrolling.scroll_top), (40., 0.));
     }
 }
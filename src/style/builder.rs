@@ -3,12 +3,12 @@
 use bevy::{
     asset::AssetPath,
     log::error,
-    math::Vec3,
+    math::{IVec2, Vec3},
     prelude::Color,
     ui::{self, ZIndex},
 };
 
-use crate::{PointerEvents, StyleProp};
+use crate::{Cursor, PointerEvents, StyleProp};
 
 use super::{selector::Selector, style_props::SelectorList, transition::Transition};
 
@@ -464,6 +464,24 @@ impl StyleBuilder {
         self
     }
 
+    pub fn cursor(&mut self, cursor: Cursor) -> &mut Self {
+        self.props.push(StyleProp::Cursor(cursor));
+        self
+    }
+
+    /// Set the image used when the cursor is [`Cursor::CustomImage`]. Setting an image without
+    /// setting `cursor` also selects the custom image cursor.
+    pub fn cursor_image(&mut self, path: impl Into<AssetPath<'static>>) -> &mut Self {
+        self.props.push(StyleProp::CursorImage(path.into()));
+        self
+    }
+
+    /// Set the position of the hotspot within the cursor image, in pixels from the top left.
+    pub fn cursor_offset(&mut self, offset: IVec2) -> &mut Self {
+        self.props.push(StyleProp::CursorOffset(offset));
+        self
+    }
+
     pub fn font(&mut self, path: Option<AssetPath<'static>>) -> &mut Self {
         self.props.push(StyleProp::Font(path));
         self
@@ -5,7 +5,7 @@ use impl_trait_for_tuples::*;
 
 /// List of class names which are attached to a given UiNode. Style selectors can use these
 /// class names to conditionally apply styles.
-#[derive(Component, Default)]
+#[derive(Component, Default, Clone, PartialEq, Debug)]
 pub struct ElementClasses(pub HashSet<String>);
 
 impl ElementClasses {
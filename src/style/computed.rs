@@ -1,8 +1,13 @@
+#[cfg(feature = "picking")]
 use super::style_props::PointerEvents;
 use super::transition::{
     AnimatedBackgroundColor, AnimatedBorderColor, AnimatedLayout, AnimatedLayoutProp,
     AnimatedTransform, Transition, TransitionProperty, TransitionState,
 };
+use crate::cursor::StyleCursor;
+use crate::diagnostics::QuillCounters;
+use crate::view::show::ShowHidden;
+use crate::Cursor;
 use bevy::asset::AssetPath;
 use bevy::ecs::system::Command;
 use bevy::prelude::*;
@@ -10,6 +15,7 @@ use bevy::text::BreakLineOn;
 use bevy::ui::widget::UiImageSize;
 use bevy::ui::ContentSize;
 use bevy::utils::HashMap;
+#[cfg(feature = "picking")]
 use bevy_mod_picking::prelude::Pickable;
 
 /// A computed style represents the composition of one or more `ElementStyle`s.
@@ -47,7 +53,11 @@ pub struct ComputedStyle {
     pub flip_y: bool,
 
     // Picking properties
-    pub pickable: Option<PointerEvents>,
+    pub pickable: Option<super::style_props::PointerEvents>,
+    pub cursor: Option<Cursor>,
+    pub cursor_image: Option<AssetPath<'static>>,
+    pub cursor_image_handle: Option<Handle<Image>>,
+    pub cursor_offset: Option<IVec2>,
 
     // Transitiions
     pub transitions: Vec<Transition>,
@@ -68,6 +78,7 @@ pub struct UpdateComputedStyle {
 
 impl Command for UpdateComputedStyle {
     fn apply(self, world: &mut World) {
+        QuillCounters::add(world, |c| &c.styles_computed, 1);
         let Some(mut e) = world.get_entity_mut(self.entity) else {
             return;
         };
@@ -147,6 +158,11 @@ impl Command for UpdateComputedStyle {
             }
         }
 
+        // An entity hidden by `Show` stays hidden, but remembers its new styled display.
+        if let Some(mut hidden) = e.get_mut::<ShowHidden>() {
+            hidden.0 = std::mem::replace(&mut next_style.display, Display::None);
+        }
+
         if let Some(mut existing_style) = e.get_mut::<Style>() {
             // Update the existing style
             if !existing_style.eq(&next_style) {
@@ -190,9 +206,41 @@ impl Command for UpdateComputedStyle {
         }
 
         if is_animated_bg_color {
-            match e.get_mut::<AnimatedBackgroundColor>() {
-                Some(_) => todo!(),
-                None => todo!(),
+            // A missing background color is animated as a transparent one.
+            let color = self.computed.background_color.unwrap_or(Color::NONE);
+            let transition = self
+                .computed
+                .transitions
+                .iter()
+                .find(|t| t.property == TransitionProperty::BackgroundColor)
+                .unwrap();
+            match e.get::<AnimatedBackgroundColor>() {
+                Some(ac) => {
+                    if ac.target != color {
+                        let prev_color = e.get::<BackgroundColor>().map_or(color, |bg| bg.0);
+                        e.insert(AnimatedBackgroundColor {
+                            state: TransitionState {
+                                transition: transition.clone(),
+                                clock: 0.,
+                            },
+                            origin: prev_color,
+                            target: color,
+                        });
+                    }
+                }
+                None => {
+                    e.insert((
+                        BackgroundColor(color),
+                        AnimatedBackgroundColor {
+                            state: TransitionState {
+                                transition: transition.clone(),
+                                clock: 0.,
+                            },
+                            origin: color,
+                            target: color,
+                        },
+                    ));
+                }
             }
         } else {
             e.remove::<AnimatedBackgroundColor>();
@@ -328,6 +376,7 @@ impl Command for UpdateComputedStyle {
         }
 
         // Update Pickable
+        #[cfg(feature = "picking")]
         match (self.computed.pickable, e.get_mut::<Pickable>()) {
             (Some(pe), Some(mut pickable)) => {
                 pickable.should_block_lower = pe == PointerEvents::All;
@@ -345,6 +394,27 @@ impl Command for UpdateComputedStyle {
             (None, None) => {}
         }
 
+        // Update cursor
+        let cursor = match (self.computed.cursor, &self.computed.cursor_image_handle) {
+            (None, Some(_)) => Some(Cursor::CustomImage),
+            (cursor, _) => cursor,
+        }
+        .map(|cursor| StyleCursor {
+            cursor,
+            image: self.computed.cursor_image_handle.clone(),
+            offset: self.computed.cursor_offset.unwrap_or_default(),
+        });
+        match (cursor, e.get::<StyleCursor>()) {
+            (Some(cursor), Some(prev)) if *prev == cursor => {}
+            (Some(cursor), _) => {
+                e.insert(cursor);
+            }
+            (None, Some(_)) => {
+                e.remove::<StyleCursor>();
+            }
+            (None, None) => {}
+        }
+
         let mut transform = Transform::default();
         transform.translation = self.computed.translation.unwrap_or(transform.translation);
         transform.scale.x = self.computed.scale_x.unwrap_or(1.);
@@ -17,6 +17,7 @@ pub(crate) use selector::Selector;
 pub(crate) use selector_matcher::SelectorMatcher;
 pub use style_handle::ElementStyles;
 pub use style_handle::StyleHandle;
+pub(crate) use style_handle::TextStyles;
 pub use style_props::PointerEvents;
 pub use style_props::StyleProp;
 pub use style_tuple::StyleTuple;
@@ -25,5 +26,8 @@ pub use transition::animate_border_colors;
 pub use transition::animate_layout;
 pub use transition::animate_transforms;
 pub use transition::timing;
+pub(crate) use transition::AnimatedLayout;
+pub(crate) use transition::AnimatedTransform;
 pub use transition::Transition;
 pub use transition::TransitionProperty;
+pub(crate) use transition::TransitionState;
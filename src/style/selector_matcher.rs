@@ -1,7 +1,5 @@
 use bevy::prelude::*;
-use bevy::{ecs::entity::Entity, utils::HashMap};
-use bevy_mod_picking::backend::HitData;
-use bevy_mod_picking::pointer::PointerId;
+use bevy::{ecs::entity::Entity, utils::HashSet};
 
 use crate::{ElementClasses, Selector};
 
@@ -9,7 +7,7 @@ pub struct SelectorMatcher<'w, 's, 'h> {
     classes_query: &'h Query<'w, 's, Ref<'static, ElementClasses>>,
     parent_query: &'h Query<'w, 's, &'static Parent, (With<Node>, With<Visibility>)>,
     children_query: &'h Query<'w, 's, &'static Children, (With<Node>, With<Visibility>)>,
-    hover_map: &'h HashMap<PointerId, HashMap<Entity, HitData>>,
+    hovered: &'h HashSet<Entity>,
     focus: Option<Entity>,
 }
 
@@ -18,34 +16,31 @@ impl<'w, 's, 'h> SelectorMatcher<'w, 's, 'h> {
         query: &'h Query<'w, 's, Ref<'static, ElementClasses>>,
         parent_query: &'h Query<'w, 's, &'static Parent, (With<Node>, With<Visibility>)>,
         children_query: &'h Query<'w, 's, &'static Children, (With<Node>, With<Visibility>)>,
-        hover_map: &'h HashMap<PointerId, HashMap<Entity, HitData>>,
+        hovered: &'h HashSet<Entity>,
         focus: Option<Entity>,
     ) -> Self {
         Self {
             classes_query: query,
             parent_query,
             children_query,
-            hover_map,
+            hovered,
             focus,
         }
     }
 
-    /// True if the given entity, or an ancestor of it, is in the hover map for PointerId::Mouse.
+    /// True if the given entity, or a descendant of it, is hovered by the mouse.
     ///
     /// This is used to determine whether to apply the :hover pseudo-class.
     pub fn is_hovering(&self, e: &Entity) -> bool {
-        match self.hover_map.get(&PointerId::Mouse) {
-            Some(map) => map.iter().any(|(mut ha, _)| loop {
-                if ha == e {
-                    return true;
-                }
-                match self.parent_query.get(*ha) {
-                    Ok(parent) => ha = parent,
-                    _ => return false,
-                }
-            }),
-            None => false,
-        }
+        self.hovered.iter().any(|mut ha| loop {
+            if ha == e {
+                return true;
+            }
+            match self.parent_query.get(*ha) {
+                Ok(parent) => ha = parent,
+                _ => return false,
+            }
+        })
     }
 
     /// True if the given entity has keyboard focus.
@@ -425,6 +425,13 @@ impl StyleSet {
                 }
 
                 StyleProp::PointerEvents(expr) => {
+                    #[cfg(not(feature = "picking"))]
+                    {
+                        static WARNING: std::sync::Once = std::sync::Once::new();
+                        WARNING.call_once(|| {
+                            warn!("pointer_events has no effect without the `picking` feature")
+                        });
+                    }
                     computed.pickable = Some(*expr);
                 }
 
@@ -436,9 +443,15 @@ impl StyleSet {
                     computed.font_size = Some(*expr);
                 }
 
-                StyleProp::Cursor(_) => todo!(),
-                StyleProp::CursorImage(_) => todo!(),
-                StyleProp::CursorOffset(_) => todo!(),
+                StyleProp::Cursor(expr) => {
+                    computed.cursor = Some(*expr);
+                }
+                StyleProp::CursorImage(path) => {
+                    computed.cursor_image = Some(path.clone());
+                }
+                StyleProp::CursorOffset(offset) => {
+                    computed.cursor_offset = Some(*offset);
+                }
 
                 StyleProp::Scale(expr) => {
                     computed.scale_x = Some(*expr);
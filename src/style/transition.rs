@@ -300,23 +300,25 @@ pub fn animate_transforms(
     }
 }
 
+/// Interpolate between two colors in linear RGBA space.
+fn lerp_color(origin: Color, target: Color, t: f32) -> Color {
+    let origin = Vec4::from_array(origin.as_linear_rgba_f32());
+    let target = Vec4::from_array(target.as_linear_rgba_f32());
+    let [r, g, b, a] = origin.lerp(target, t).to_array();
+    Color::rgba_linear(r, g, b, a)
+}
+
 #[doc(hidden)]
 pub fn animate_bg_colors(
-    mut query: Query<(
-        Entity,
-        Option<&mut BackgroundColor>,
-        &mut AnimatedBackgroundColor,
-    )>,
+    mut query: Query<(&mut BackgroundColor, &mut AnimatedBackgroundColor)>,
     time: Res<Time>,
 ) {
-    #![allow(unused)]
-    for (e, mut bg, mut at) in query.iter_mut() {
-        let t_old = at.state.clock;
+    for (mut bg, mut at) in query.iter_mut() {
+        let clock_old = at.state.clock;
         at.state.advance(time.delta_seconds());
-        let t = at.state.transition.timing.eval(at.state.clock);
-        let origin = at.origin.as_rgba_linear();
-        let target = at.target.as_rgba_linear();
-        todo!("Finish color space interpolation!");
+        if at.state.clock != clock_old {
+            bg.0 = lerp_color(at.origin, at.target, at.state.t());
+        }
     }
 }
 
@@ -345,3 +347,57 @@ pub fn animate_layout(mut query: Query<(&mut Style, &mut AnimatedLayout)>, time:
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::{Command, RunSystemOnce};
+
+    use super::*;
+    use crate::style::{ComputedStyle, UpdateComputedStyle};
+
+    #[test]
+    fn test_animate_bg_color() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let entity = world.spawn(NodeBundle::default()).id();
+        let restyle = |world: &mut World, color: Color| {
+            UpdateComputedStyle {
+                entity,
+                computed: ComputedStyle {
+                    background_color: Some(color),
+                    transitions: vec![Transition {
+                        property: TransitionProperty::BackgroundColor,
+                        duration: 1.,
+                        ..default()
+                    }],
+                    ..default()
+                },
+            }
+            .apply(world);
+        };
+        let step = |world: &mut World, seconds: f32| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(std::time::Duration::from_secs_f32(seconds));
+            world.run_system_once(animate_bg_colors);
+            world
+                .get::<BackgroundColor>(entity)
+                .unwrap()
+                .0
+                .as_linear_rgba_f32()
+        };
+
+        // The initial color is set without a transition.
+        restyle(&mut world, Color::BLACK);
+        assert_eq!(step(&mut world, 0.5), Color::BLACK.as_linear_rgba_f32());
+
+        // A change of color is interpolated in linear space.
+        restyle(&mut world, Color::WHITE);
+        let mid = step(&mut world, 0.5);
+        for channel in &mid[0..3] {
+            assert!((channel - 0.5).abs() < 1e-4, "{:?}", mid);
+        }
+        assert_eq!(mid[3], 1.);
+        assert_eq!(step(&mut world, 0.5), Color::WHITE.as_linear_rgba_f32());
+    }
+}
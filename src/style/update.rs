@@ -3,9 +3,9 @@ use bevy::{
     prelude::*,
     render::texture::{ImageLoaderSettings, ImageSampler},
 };
-use bevy_mod_picking::focus::{HoverMap, PreviousHoverMap};
 
 use crate::{
+    hover::{HoverState, PreviousHoverState},
     style::{ComputedStyle, UpdateComputedStyle},
     ElementClasses, ElementStyles, SelectorMatcher,
 };
@@ -32,8 +32,8 @@ pub(crate) fn update_styles(
     query_element_classes: Query<Ref<'static, ElementClasses>>,
     query_parents: Query<&'static Parent, (With<Node>, With<Visibility>)>,
     query_children: Query<&'static Children, (With<Node>, With<Visibility>)>,
-    hover_map: Res<HoverMap>,
-    hover_map_prev: Res<PreviousHoverMap>,
+    hover_state: Res<HoverState>,
+    mut hover_prev: ResMut<PreviousHoverState>,
     assets: Res<AssetServer>,
     focus: Res<Focus>,
     mut focus_prev: ResMut<PreviousFocus>,
@@ -42,14 +42,14 @@ pub(crate) fn update_styles(
         &query_element_classes,
         &query_parents,
         &query_children,
-        &hover_map.0,
+        &hover_state.hovered,
         focus.0,
     );
     let matcher_prev = SelectorMatcher::new(
         &query_element_classes,
         &query_parents,
         &query_children,
-        &hover_map_prev.0,
+        &hover_prev.0,
         focus_prev.0,
     );
 
@@ -70,6 +70,9 @@ pub(crate) fn update_styles(
     }
 
     focus_prev.0 = focus.0;
+    if hover_prev.0 != hover_state.hovered {
+        hover_prev.0.clone_from(&hover_state.hovered);
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -167,6 +170,8 @@ fn update_element_styles(
                         s.sampler = ImageSampler::linear()
                     })
                 });
+                computed.cursor_image_handle =
+                    computed.cursor_image.as_ref().map(|path| assets.load(path));
 
                 commands.add(UpdateComputedStyle { entity, computed });
             }
@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+/// Returns the text of every `Text` entity in the world, sorted.
+pub(crate) fn texts(world: &mut World) -> Vec<String> {
+    let mut q = world.query::<&Text>();
+    let mut result: Vec<String> = q.iter(world).map(|t| t.sections[0].value.clone()).collect();
+    result.sort();
+    result
+}
@@ -0,0 +1,229 @@
+use std::{any::Any, cell::Cell};
+
+use bevy::ecs::world::World;
+
+use crate::{BuildContext, View};
+
+use crate::node_span::NodeSpan;
+
+/// A view bundled together with its state, so that both can be type-erased.
+struct ViewAndState<V: View> {
+    view: V,
+    state: Option<V::State>,
+}
+
+/// Object-safe shim over [`View`]. Each instance owns its own view state.
+#[doc(hidden)]
+pub trait AnyViewState: Send {
+    fn nodes(&self, bc: &BuildContext) -> NodeSpan;
+    fn build(&mut self, bc: &mut BuildContext);
+    /// Update from the previous view state, taking ownership of the previous state if the
+    /// view types match. Returns false if they don't match.
+    fn update(&mut self, bc: &mut BuildContext, prev: &mut dyn AnyViewState) -> bool;
+    fn assemble(&mut self, bc: &mut BuildContext) -> NodeSpan;
+    fn raze(&mut self, world: &mut World);
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<V: View + 'static> AnyViewState for ViewAndState<V>
+where
+    V::State: 'static,
+{
+    fn nodes(&self, bc: &BuildContext) -> NodeSpan {
+        match self.state {
+            Some(ref state) => self.view.nodes(bc, state),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, bc: &mut BuildContext) {
+        self.state = Some(self.view.build(bc));
+    }
+
+    fn update(&mut self, bc: &mut BuildContext, prev: &mut dyn AnyViewState) -> bool {
+        let Some(prev) = prev.as_any_mut().downcast_mut::<Self>() else {
+            return false;
+        };
+        let mut state = prev.state.take().expect("AnyView has no state");
+        self.view.update(bc, &mut state);
+        self.state = Some(state);
+        true
+    }
+
+    fn assemble(&mut self, bc: &mut BuildContext) -> NodeSpan {
+        match self.state {
+            Some(ref mut state) => self.view.assemble(bc, state),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn raze(&mut self, world: &mut World) {
+        if let Some(ref mut state) = self.state {
+            self.view.raze(world, state);
+        }
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// A type-erased [`View`], which can be used when the type of a view isn't known at compile
+/// time, such as a list of children with differing types. Create one by calling
+/// [`View::boxed`].
+///
+/// Type erasure is not free: each `AnyView` is a separate heap allocation, and all calls are
+/// dynamically dispatched. When updating, if the new view is a different type than the old
+/// one, then the old view is razed and the new one built from scratch.
+///
+/// An `AnyView` hands its view over to the view state when built, so a given instance can
+/// only be built or updated once; a presenter should produce a fresh `AnyView` each run.
+pub struct AnyView {
+    inner: Cell<Option<Box<dyn AnyViewState>>>,
+}
+
+impl AnyView {
+    /// Construct a new [`AnyView`] from a view.
+    pub fn new<V: View + 'static>(view: V) -> Self
+    where
+        V::State: 'static,
+    {
+        Self {
+            inner: Cell::new(Some(Box::new(ViewAndState { view, state: None }))),
+        }
+    }
+
+    fn take(&self) -> Box<dyn AnyViewState> {
+        self.inner.take().expect("AnyView has already been used")
+    }
+}
+
+impl View for AnyView {
+    type State = Box<dyn AnyViewState>;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        state.nodes(bc)
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let mut state = self.take();
+        state.build(bc);
+        state
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        let mut next = self.take();
+        if !next.update(bc, state.as_mut()) {
+            // View type has changed, rebuild from scratch.
+            state.raze(bc.world);
+            next.build(bc);
+            bc.mark_changed_shape();
+        }
+        *state = next;
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        state.assemble(bc)
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        state.raze(world);
+    }
+}
+
+/// A list of type-erased views. Items are matched with the previous list by position.
+impl View for Vec<AnyView> {
+    type State = Vec<Box<dyn AnyViewState>>;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        NodeSpan::Fragment(state.iter().map(|item| item.nodes(bc)).collect())
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        self.iter().map(|item| item.build(bc)).collect()
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        // Raze surplus items
+        if state.len() > self.len() {
+            for mut item in state.drain(self.len()..) {
+                item.raze(bc.world);
+            }
+            bc.mark_changed_shape();
+        }
+
+        for (i, item) in self.iter().enumerate() {
+            if i < state.len() {
+                item.update(bc, &mut state[i]);
+            } else {
+                state.push(item.build(bc));
+                bc.mark_changed_shape();
+            }
+        }
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        NodeSpan::Fragment(state.iter_mut().map(|item| item.assemble(bc)).collect())
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        for item in state.iter_mut() {
+            item.raze(world);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::{Cx, Element, PresenterFn, ViewHandle};
+
+    fn child(_cx: Cx) -> impl View {
+        "child".to_string()
+    }
+
+    #[test]
+    fn test_any_view_list() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let view = vec![
+            "text".to_string().boxed(),
+            Element::new().boxed(),
+            child.bind(()).boxed(),
+        ];
+        let mut state = view.build(&mut bc);
+        assert_eq!(state.len(), 3);
+        // Presenter is built asynchronously, so it has no nodes yet.
+        assert_eq!(view.nodes(&bc, &state).count(), 2);
+        let mut nodes = Vec::new();
+        view.nodes(&bc, &state).flatten(&mut nodes);
+        let text = nodes[0];
+
+        // Same types: nodes are updated in place.
+        let view = vec![
+            "text2".to_string().boxed(),
+            Element::new().boxed(),
+            child.bind(()).boxed(),
+        ];
+        view.update(&mut bc, &mut state);
+        let mut next_nodes = Vec::new();
+        view.nodes(&bc, &state).flatten(&mut next_nodes);
+        assert_eq!(nodes, next_nodes);
+        assert_eq!(
+            bc.world.get::<Text>(text).unwrap().sections[0].value,
+            "text2"
+        );
+
+        // Different types: old view is razed.
+        let view = vec![Element::new().boxed()];
+        view.update(&mut bc, &mut state);
+        assert_eq!(state.len(), 1);
+        assert!(bc.world.get_entity(text).is_none());
+        let mut q = bc.world.query::<&ViewHandle>();
+        assert_eq!(q.iter(bc.world).count(), 0);
+    }
+}
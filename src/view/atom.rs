@@ -1,14 +1,26 @@
-use std::{any::Any, fmt::Debug, marker::PhantomData};
+use std::{
+    any::{Any, TypeId},
+    fmt::Debug,
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
 
-use bevy::ecs::{
-    component::Component,
-    entity::Entity,
-    system::{Commands, Query, SystemParam},
-    world::World,
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        system::{Commands, Local, Query, Res, Resource, SystemParam},
+        world::World,
+    },
+    time::Time,
+    utils::HashMap,
 };
 
 /// A unique key which can be used to read and write an atom.
-#[derive(Copy, Clone, PartialEq)]
 pub struct AtomHandle<T>
 where
     T: Clone + Sync + Send + 'static,
@@ -17,6 +29,26 @@ where
     pub(crate) marker: PhantomData<T>,
 }
 
+impl<T> Copy for AtomHandle<T> where T: Clone + Sync + Send + 'static {}
+
+impl<T> Clone for AtomHandle<T>
+where
+    T: Clone + Sync + Send + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> PartialEq for AtomHandle<T>
+where
+    T: Clone + Sync + Send + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
 impl<T> Debug for AtomHandle<T>
 where
     T: Clone + Sync + Send + 'static,
@@ -29,6 +61,64 @@ where
     }
 }
 
+impl<T> AtomHandle<T>
+where
+    T: Clone + Sync + Send + 'static,
+{
+    /// Return a [`WeakAtomHandle`] referring to the same atom.
+    pub fn downgrade(self) -> WeakAtomHandle<T> {
+        WeakAtomHandle {
+            id: self.id,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// A reference to an atom which may have been deleted. This is intended for handles which
+/// are kept in long-lived closures or resources, where the presenter which owns the atom may
+/// have been razed, or may have released it with
+/// [`Cx::release_unused_entities`](crate::Cx::release_unused_entities).
+pub struct WeakAtomHandle<T>
+where
+    T: Clone + Sync + Send + 'static,
+{
+    id: Entity,
+    marker: PhantomData<T>,
+}
+
+impl<T> Copy for WeakAtomHandle<T> where T: Clone + Sync + Send + 'static {}
+
+impl<T> Clone for WeakAtomHandle<T>
+where
+    T: Clone + Sync + Send + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> PartialEq for WeakAtomHandle<T>
+where
+    T: Clone + Sync + Send + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> WeakAtomHandle<T>
+where
+    T: Clone + Sync + Send + 'static,
+{
+    /// Return a handle to the atom, or `None` if the atom has been deleted.
+    pub fn upgrade(&self, world: &World) -> Option<AtomHandle<T>> {
+        world.get_entity(self.id).map(|_| AtomHandle {
+            id: self.id,
+            marker: PhantomData,
+        })
+    }
+}
+
 #[derive(Component)]
 #[doc(hidden)]
 pub struct AtomCell(pub(crate) Box<dyn Any + Send + Sync + 'static>);
@@ -49,6 +139,62 @@ pub trait AtomMethods {
     fn set_atom<T: Clone + Sync + Send + 'static>(&mut self, handle: AtomHandle<T>, value: T);
 }
 
+/// Trait which adds `global_atom` to [`World`].
+pub trait GlobalAtomApi {
+    /// Return the global atom with the given key, creating it with the value returned by
+    /// `init` if it doesn't exist. Global atoms are not owned by any presenter, so they are
+    /// not deleted when a presenter is razed; they can be used for application-wide state
+    /// which is shared between separate view roots. Panics if the atom exists but has a
+    /// different type.
+    fn global_atom<T: Clone + Sync + Send + 'static>(
+        &mut self,
+        key: &'static str,
+        init: impl FnOnce() -> T,
+    ) -> AtomHandle<T>;
+}
+
+/// Error returned by [`GlobalAtoms::get`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobalAtomError {
+    /// No global atom has been created with the given key.
+    NotFound,
+    /// The global atom with the given key holds a different type.
+    IncorrectType,
+}
+
+impl std::fmt::Display for GlobalAtomError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GlobalAtomError::NotFound => write!(f, "Global atom not found"),
+            GlobalAtomError::IncorrectType => write!(f, "Global atom is incorrect type"),
+        }
+    }
+}
+
+impl std::error::Error for GlobalAtomError {}
+
+/// Registry of global atoms, indexed by key. See [`GlobalAtomApi::global_atom`].
+#[derive(Resource, Default)]
+pub struct GlobalAtoms(HashMap<&'static str, (Entity, TypeId)>);
+
+impl GlobalAtoms {
+    /// Look up a global atom by key. Returns an error if the atom has not been created, or
+    /// if it was created with a type other than `T`.
+    pub fn get<T: Clone + Sync + Send + 'static>(
+        &self,
+        key: &'static str,
+    ) -> Result<AtomHandle<T>, GlobalAtomError> {
+        match self.0.get(key) {
+            Some((id, ty)) if *ty == TypeId::of::<T>() => Ok(AtomHandle {
+                id: *id,
+                marker: PhantomData,
+            }),
+            Some(_) => Err(GlobalAtomError::IncorrectType),
+            None => Err(GlobalAtomError::NotFound),
+        }
+    }
+}
+
 impl AtomMethods for World {
     fn create_atom<T: Clone + Sync + Send + 'static>(&mut self) -> AtomHandle<T> {
         AtomHandle {
@@ -71,6 +217,7 @@ impl AtomMethods for World {
 
     fn set_atom<T: Clone + Sync + Send + 'static>(&mut self, handle: AtomHandle<T>, value: T) {
         let mut entt = self.entity_mut(handle.id);
+        entt.remove::<DebouncedWrite>();
         match entt.get_mut::<AtomCell>() {
             Some(mut cell) => *cell.0.downcast_mut::<T>().expect("Atom is incorrect type") = value,
             None => {
@@ -80,6 +227,68 @@ impl AtomMethods for World {
     }
 }
 
+impl GlobalAtomApi for World {
+    fn global_atom<T: Clone + Sync + Send + 'static>(
+        &mut self,
+        key: &'static str,
+        init: impl FnOnce() -> T,
+    ) -> AtomHandle<T> {
+        match self
+            .get_resource_or_insert_with(GlobalAtoms::default)
+            .get(key)
+        {
+            Ok(handle) => return handle,
+            Err(GlobalAtomError::IncorrectType) => {
+                panic!("Global atom '{}' is incorrect type", key)
+            }
+            Err(GlobalAtomError::NotFound) => {}
+        }
+        let id = self.spawn(AtomCell(Box::new(init()))).id();
+        self.resource_mut::<GlobalAtoms>()
+            .0
+            .insert(key, (id, TypeId::of::<T>()));
+        AtomHandle {
+            id,
+            marker: PhantomData,
+        }
+    }
+}
+
+type DeferredWrite = Box<dyn FnOnce(&mut World) + Send + 'static>;
+
+/// Queue of atom writes which were made without access to the [`World`], such as by the
+/// [`StateSetter`](crate::StateSetter) returned from [`Cx::use_local`](crate::Cx::use_local).
+/// Pending writes are applied at the start of the next render, and between passes of presenter
+/// updates within a render.
+#[derive(Resource, Clone, Default)]
+pub(crate) struct DeferredAtomWrites(Arc<Mutex<Vec<DeferredWrite>>>);
+
+impl DeferredAtomWrites {
+    /// Queue a write of `value` to the atom. The write is ignored if the atom has been
+    /// despawned by the time it is applied.
+    pub(crate) fn set<T: Clone + Sync + Send + 'static>(&self, handle: AtomHandle<T>, value: T) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(Box::new(move |world: &mut World| {
+                if world.get_entity(handle.id).is_some() {
+                    world.set_atom(handle, value);
+                }
+            }));
+    }
+
+    /// Apply all pending writes to the world.
+    pub(crate) fn apply(world: &mut World) {
+        let Some(writes) = world.get_resource::<DeferredAtomWrites>() else {
+            return;
+        };
+        let pending = std::mem::take(&mut *writes.0.lock().unwrap());
+        for write in pending {
+            write(world);
+        }
+    }
+}
+
 /// An injectable parameter that allows reading and writing of atoms. Note that this is not
 /// a reactive context, so reading atom values will not add the atom to a tracking context.
 /// However, writing atom values will trigger reactions for other contexts which have
@@ -90,11 +299,23 @@ pub struct AtomStore<'w, 's> {
     pub query: Query<'w, 's, &'static mut AtomCell>,
     #[doc(hidden)]
     pub commands: Commands<'w, 's>,
+    pub(crate) debounced: Query<'w, 's, &'static mut DebouncedWrite>,
+    pub(crate) reads: Local<'s, AtomReads>,
+}
+
+/// Records the atoms read through an [`AtomStore`], used to track the sources of a
+/// derived atom. Recording is disabled unless explicitly started; the flag lets reads skip
+/// the lock when it is not.
+#[derive(Default)]
+pub(crate) struct AtomReads {
+    recording: AtomicBool,
+    reads: Mutex<Vec<Entity>>,
 }
 
 impl<'w, 's> AtomStore<'w, 's> {
     /// Read the value of an atom. Panics if the atom does not exist.
     pub fn get<T: Clone + Sync + Send + 'static>(&self, handle: AtomHandle<T>) -> T {
+        self.record_read(handle.id);
         let cell = self.query.get(handle.id).expect("Atom does not exist");
         cell.0
             .as_ref()
@@ -105,6 +326,7 @@ impl<'w, 's> AtomStore<'w, 's> {
 
     /// Read the value of an atom. Returns None atom does not exist.
     pub fn try_get<T: Clone + Sync + Send + 'static>(&self, handle: AtomHandle<T>) -> Option<T> {
+        self.record_read(handle.id);
         if let Ok(cell) = self.query.get(handle.id) {
             Some(
                 cell.0
@@ -118,8 +340,10 @@ impl<'w, 's> AtomStore<'w, 's> {
         }
     }
 
-    /// Write the value of an atom. Panics if the atom handle is invalid.
+    /// Write the value of an atom. Panics if the atom handle is invalid. This cancels any
+    /// pending [`set_debounced`](AtomStore::set_debounced) write.
     pub fn set<T: Clone + Sync + Send + 'static>(&mut self, handle: AtomHandle<T>, value: T) {
+        self.cancel_debounced(handle.id);
         match self.query.get_mut(handle.id) {
             Ok(mut cell) => {
                 *cell.0.downcast_mut::<T>().expect("Atom is incorrect type") = value;
@@ -132,12 +356,30 @@ impl<'w, 's> AtomStore<'w, 's> {
         }
     }
 
-    /// Update the value of an atom. Panics if the atom does not exist.
+    /// Write the value of an atom after a delay. If the atom is written again with
+    /// `set_debounced` before the delay has elapsed, the previous value is discarded and the
+    /// delay starts over, so only the final value of a rapid series of writes is stored.
+    /// This is useful for inputs such as sliders or text fields which change every frame.
+    pub fn set_debounced<T: Clone + Sync + Send + 'static>(
+        &mut self,
+        handle: AtomHandle<T>,
+        value: T,
+        delay: Duration,
+    ) {
+        self.commands.entity(handle.id).insert(DebouncedWrite {
+            value: Some(Box::new(value)),
+            remaining: delay,
+        });
+    }
+
+    /// Update the value of an atom. Panics if the atom does not exist. This cancels any
+    /// pending [`set_debounced`](AtomStore::set_debounced) write.
     pub fn update<T: Clone + Sync + Send + 'static, F: FnOnce(T) -> T>(
         &mut self,
         handle: AtomHandle<T>,
         update: F,
     ) {
+        self.cancel_debounced(handle.id);
         let cell = self.query.get(handle.id).expect("Atom does not exist");
         let value = cell
             .0
@@ -149,4 +391,196 @@ impl<'w, 's> AtomStore<'w, 's> {
             .entity(handle.id)
             .insert(AtomCell(Box::new(update(value))));
     }
+
+    /// Discard the pending debounced write to an atom, if any. The write is discarded now, in
+    /// case it expires before this system's commands are applied, and the component is
+    /// removed by a command, so that it also cancels a write queued earlier by this system.
+    fn cancel_debounced(&mut self, id: Entity) {
+        if let Ok(mut write) = self.debounced.get_mut(id) {
+            write.value = None;
+        }
+        if let Some(mut entt) = self.commands.get_entity(id) {
+            entt.remove::<DebouncedWrite>();
+        }
+    }
+
+    /// Begin recording which atoms are read.
+    pub(crate) fn start_recording(&self) {
+        self.reads.reads.lock().unwrap().clear();
+        self.reads.recording.store(true, Ordering::Relaxed);
+    }
+
+    /// Stop recording and return the list of atoms which were read.
+    pub(crate) fn stop_recording(&self) -> Vec<Entity> {
+        self.reads.recording.store(false, Ordering::Relaxed);
+        std::mem::take(&mut *self.reads.reads.lock().unwrap())
+    }
+
+    fn record_read(&self, id: Entity) {
+        if !self.reads.recording.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut reads = self.reads.reads.lock().unwrap();
+        if !reads.contains(&id) {
+            reads.push(id);
+        }
+    }
+}
+
+/// A pending debounced write, stored on the atom entity until the delay has elapsed.
+#[derive(Component)]
+pub(crate) struct DebouncedWrite {
+    value: Option<Box<dyn Any + Send + Sync + 'static>>,
+    remaining: Duration,
+}
+
+/// System which advances the timers of debounced atom writes, and stores the value once
+/// the timer expires.
+pub(crate) fn update_debounced_atoms(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut DebouncedWrite, Option<&mut AtomCell>)>,
+) {
+    for (entity, mut write, cell) in query.iter_mut() {
+        write.remaining = write.remaining.saturating_sub(time.delta());
+        if write.value.is_none() || write.remaining.is_zero() {
+            // A write with no value has been cancelled by a direct write.
+            if let Some(value) = write.value.take() {
+                match cell {
+                    Some(mut cell) => {
+                        assert_eq!(
+                            Any::type_id(&*cell.0),
+                            Any::type_id(&*value),
+                            "Atom is incorrect type"
+                        );
+                        cell.0 = value;
+                    }
+                    None => {
+                        commands.entity(entity).insert(AtomCell(value));
+                    }
+                }
+            }
+            commands.entity(entity).remove::<DebouncedWrite>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::{plugin::render_views, Cx, View, ViewHandle};
+
+    #[test]
+    fn test_set_debounced() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let atom = world.create_atom::<i32>();
+        world.set_atom(atom, 0);
+        let delay = Duration::from_millis(100);
+        let frame = Duration::from_millis(40);
+
+        let step = |world: &mut World, value: Option<i32>| {
+            if let Some(value) = value {
+                world.run_system_once(move |mut store: AtomStore| {
+                    store.set_debounced(atom, value, delay)
+                });
+            }
+            world.resource_mut::<Time>().advance_by(frame);
+            world.run_system_once(update_debounced_atoms);
+            world.get_atom(atom)
+        };
+
+        // Rapid writes keep restarting the timer.
+        assert_eq!(step(&mut world, Some(1)), 0);
+        assert_eq!(step(&mut world, Some(2)), 0);
+        assert_eq!(step(&mut world, Some(3)), 0);
+        assert_eq!(step(&mut world, None), 0);
+        // Only the final value lands once the input has settled.
+        assert_eq!(step(&mut world, None), 3);
+        assert!(world.get::<DebouncedWrite>(atom.id).is_none());
+    }
+
+    #[test]
+    fn test_set_cancels_debounced() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let atom = world.create_atom::<i32>();
+        world.set_atom(atom, 0);
+        let delay = Duration::from_millis(100);
+
+        let expire = |world: &mut World| {
+            world.resource_mut::<Time>().advance_by(delay);
+            world.run_system_once(update_debounced_atoms);
+            world.get_atom(atom)
+        };
+
+        // A direct write replaces a pending debounced one.
+        world.run_system_once(move |mut store: AtomStore| store.set_debounced(atom, 1, delay));
+        world.run_system_once(move |mut store: AtomStore| store.set(atom, 5));
+        assert_eq!(expire(&mut world), 5);
+
+        // Including one queued earlier by the same system.
+        world.run_system_once(move |mut store: AtomStore| {
+            store.set_debounced(atom, 1, delay);
+            store.update(atom, |value| value + 1);
+        });
+        assert_eq!(expire(&mut world), 6);
+
+        // And a write to the world.
+        world.run_system_once(move |mut store: AtomStore| store.set_debounced(atom, 1, delay));
+        world.set_atom(atom, 7);
+        assert_eq!(expire(&mut world), 7);
+        assert!(world.get::<DebouncedWrite>(atom.id).is_none());
+    }
+
+    #[derive(Component, Default)]
+    struct Renders(usize, bool);
+
+    fn panel(mut cx: Cx) -> impl View {
+        let open = cx.use_global_atom("panel_open", || false);
+        let open = cx.read_atom(open);
+        let entity = cx.bc.entity;
+        let mut renders = cx.bc.world.get_mut::<Renders>(entity).unwrap();
+        renders.0 += 1;
+        renders.1 = open;
+    }
+
+    #[test]
+    fn test_global_atom() {
+        let mut world = World::new();
+        let a = world
+            .spawn((ViewHandle::new(panel, ()), Renders::default()))
+            .id();
+        let b = world
+            .spawn((ViewHandle::new(panel, ()), Renders::default()))
+            .id();
+        render_views(&mut world);
+        world.clear_trackers();
+        let open = world.global_atom("panel_open", || false);
+        assert_eq!(world.resource::<GlobalAtoms>().get("panel_open"), Ok(open));
+        assert_eq!(
+            world.resource::<GlobalAtoms>().get::<i32>("panel_open"),
+            Err(GlobalAtomError::IncorrectType)
+        );
+        assert_eq!(
+            world.resource::<GlobalAtoms>().get::<bool>("panel_closed"),
+            Err(GlobalAtomError::NotFound)
+        );
+
+        // Both roots react to the same atom.
+        world.set_atom(open, true);
+        render_views(&mut world);
+        for root in [a, b] {
+            let renders = world.get::<Renders>(root).unwrap();
+            assert_eq!(renders.0, 2);
+            assert!(renders.1);
+        }
+
+        // Razing a presenter does not delete the atom.
+        let handle = world.entity_mut(a).take::<ViewHandle>().unwrap();
+        handle.inner.lock().unwrap().raze(&mut world, a);
+        assert!(world.get_atom(open));
+    }
 }
@@ -6,7 +6,7 @@ use crate::{BuildContext, PresenterFn, View, ViewHandle};
 
 use crate::node_span::NodeSpan;
 
-use super::presenter_state::PresenterStateChanged;
+use super::presenter_state::{PresenterAttachment, PresenterStateChanged};
 
 struct BindState<Marker: 'static, F: PresenterFn<Marker>> {
     presenter: F,
@@ -92,12 +92,15 @@ impl View for Bind {
 
     // Spawn a new presenter entity.
     fn build(&self, parent_ecx: &mut BuildContext) -> Self::State {
-        let entity = parent_ecx
+        let mut entt = parent_ecx
             .world
-            .spawn((self.binding.create_handle(), Name::new("presenter")))
-            .insert(PresenterStateChanged)
-            .set_parent(parent_ecx.entity)
-            .id();
+            .spawn((self.binding.create_handle(), Name::new("presenter")));
+        entt.insert(PresenterStateChanged)
+            .set_parent(parent_ecx.entity);
+        if let Some(parent) = parent_ecx.parent {
+            entt.insert(PresenterAttachment(parent));
+        }
+        let entity = entt.id();
         // Not calling inner build here: will be done asynchronously.
         entity
     }
@@ -117,7 +120,11 @@ impl View for Bind {
     }
 
     fn raze(&self, world: &mut World, state: &mut Self::State) {
-        let mut entt = world.entity_mut(*state);
+        // If the presenter entity was despawned out from under us, then it will be razed by
+        // `raze_removed_views` instead.
+        let Some(mut entt) = world.get_entity_mut(*state) else {
+            return;
+        };
         let Some(handle) = entt.get_mut::<ViewHandle>() else {
             panic!("Bind::raze called without ViewHandle");
         };
@@ -147,3 +154,54 @@ impl PartialEq for Bind {
         self.binding.eq(&*other.binding)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{plugin::render_views, Cx};
+
+    #[derive(Resource, Default)]
+    struct Calls {
+        parent: usize,
+        child: usize,
+    }
+
+    #[derive(Resource, Default)]
+    struct Counters(i32, i32);
+
+    fn parent(cx: Cx) -> impl View {
+        let props = cx.use_resource::<Counters>().0;
+        cx.bc.world.resource_mut::<Calls>().parent += 1;
+        child.bind(props)
+    }
+
+    fn child(cx: Cx<i32>) -> impl View {
+        cx.bc.world.resource_mut::<Calls>().child += 1;
+        format!("{}", cx.props)
+    }
+
+    #[test]
+    fn test_bind_unchanged_props() {
+        let mut world = World::new();
+        world.init_resource::<Calls>();
+        world.init_resource::<Counters>();
+        world.spawn(ViewHandle::new(parent, ()));
+        render_views(&mut world);
+        assert_eq!(world.resource::<Calls>().parent, 1);
+        assert_eq!(world.resource::<Calls>().child, 1);
+        world.clear_trackers();
+
+        // Parent re-renders with the same props: child is not re-invoked.
+        world.resource_mut::<Counters>().1 += 1;
+        render_views(&mut world);
+        assert_eq!(world.resource::<Calls>().parent, 2);
+        assert_eq!(world.resource::<Calls>().child, 1);
+        world.clear_trackers();
+
+        // Props changed: child re-renders.
+        world.resource_mut::<Counters>().0 += 1;
+        render_views(&mut world);
+        assert_eq!(world.resource::<Calls>().parent, 3);
+        assert_eq!(world.resource::<Calls>().child, 2);
+    }
+}
@@ -0,0 +1,147 @@
+use std::marker::PhantomData;
+
+use bevy::ecs::{
+    component::Component,
+    entity::Entity,
+    system::{BoxedSystem, Commands, IntoSystem},
+    world::World,
+};
+
+/// A handle to a one-shot system which was registered by a presenter via
+/// [`Cx::use_callback`](crate::Cx::use_callback). The handle is a lightweight copyable value
+/// which can be captured by event handlers or passed to child presenters as a prop, and
+/// invoked via [`RunCallback`] on either [`World`] or [`Commands`].
+pub struct CallbackHandle<In = ()> {
+    pub(crate) id: Entity,
+    pub(crate) marker: PhantomData<fn(In)>,
+}
+
+impl<In> CallbackHandle<In> {
+    /// Return the id of the entity which holds the registered system.
+    pub fn id(&self) -> Entity {
+        self.id
+    }
+}
+
+impl<In> Copy for CallbackHandle<In> {}
+
+impl<In> Clone for CallbackHandle<In> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<In> PartialEq for CallbackHandle<In> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<In> std::fmt::Debug for CallbackHandle<In> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CallbackHandle")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+/// Marker for the entities which hold callbacks registered with
+/// [`Cx::use_callback`](crate::Cx::use_callback), so that they can be excluded from queries.
+#[derive(Component, Default, Clone, Copy, Debug)]
+pub struct Callback;
+
+/// Component which holds the registered system for a callback.
+#[derive(Component)]
+pub(crate) struct CallbackSystem<In: 'static> {
+    pub(crate) system: Option<BoxedSystem<In, ()>>,
+    pub(crate) initialized: bool,
+}
+
+impl<In: 'static> CallbackSystem<In> {
+    pub(crate) fn new<Marker>(system: impl IntoSystem<In, (), Marker>) -> Self {
+        Self {
+            system: Some(Box::new(IntoSystem::into_system(system))),
+            initialized: false,
+        }
+    }
+}
+
+/// Methods for invoking a callback.
+pub trait RunCallback {
+    /// Run the system registered with the given [`CallbackHandle`], passing it the input value.
+    /// Does nothing if the callback has been despawned.
+    fn run_callback<In: Send + 'static>(&mut self, handle: CallbackHandle<In>, input: In);
+}
+
+impl RunCallback for World {
+    fn run_callback<In: Send + 'static>(&mut self, handle: CallbackHandle<In>, input: In) {
+        let Some(mut entt) = self.get_entity_mut(handle.id) else {
+            return;
+        };
+        let Some(mut cell) = entt.get_mut::<CallbackSystem<In>>() else {
+            panic!("Callback is incorrect type");
+        };
+        // Take the system out of the world while it runs, so that it can borrow the world.
+        let Some(mut system) = cell.system.take() else {
+            panic!("Callback invoked recursively");
+        };
+        let initialized = std::mem::replace(&mut cell.initialized, true);
+        if !initialized {
+            system.initialize(self);
+        }
+        system.run(input, self);
+        system.apply_deferred(self);
+
+        // Put the system back, unless the callback was despawned while running.
+        if let Some(mut entt) = self.get_entity_mut(handle.id) {
+            if let Some(mut cell) = entt.get_mut::<CallbackSystem<In>>() {
+                cell.system = Some(system);
+            }
+        }
+    }
+}
+
+/// Runs the callback when commands are applied. This allows callbacks to be invoked from
+/// ordinary systems, such as event listeners.
+impl<'w, 's> RunCallback for Commands<'w, 's> {
+    fn run_callback<In: Send + 'static>(&mut self, handle: CallbackHandle<In>, input: In) {
+        self.add(move |world: &mut World| world.run_callback(handle, input));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, prelude::*};
+
+    use super::*;
+    use crate::{BuildContext, Cx, TrackingContext};
+
+    #[derive(Resource, Default)]
+    struct Total(i32);
+
+    fn add_to_total(In(amount): In<i32>, mut total: ResMut<Total>) {
+        total.0 += amount;
+    }
+
+    #[test]
+    fn test_run_callback() {
+        let mut world = World::new();
+        world.init_resource::<Total>();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+        let mut tracking = TrackingContext::default();
+        let mut cx = Cx::new(&(), &mut bc, &mut tracking);
+        let callback = cx.use_callback(add_to_total);
+
+        world.run_callback(callback, 3);
+        assert_eq!(world.resource::<Total>().0, 3);
+        world.run_callback(callback, 4);
+        assert_eq!(world.resource::<Total>().0, 7);
+
+        // Invoke from a system via commands.
+        world.run_system_once(move |mut commands: Commands| {
+            commands.run_callback(callback, 5);
+        });
+        assert_eq!(world.resource::<Total>().0, 12);
+    }
+}
@@ -1,14 +1,48 @@
-use std::{cell::RefCell, cmp::Ordering, marker::PhantomData};
+use std::{
+    any::TypeId,
+    cell::RefCell,
+    future::Future,
+    marker::PhantomData,
+    panic::Location,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
-use bevy::prelude::*;
+use bevy::{
+    asset::{AssetPath, LoadState},
+    ecs::{
+        event::ManualEventReader,
+        query::{QueryFilter, ROQueryItem, ReadOnlyQueryData},
+    },
+    prelude::*,
+    tasks::AsyncComputeTaskPool,
+    window::PrimaryWindow,
+};
 
-use crate::{tracked_resources::TrackedResource, BuildContext, ScopedValueKey, TrackingContext};
+use crate::{
+    tracked_resources::{
+        EventCursor, QueryStateCache, TrackedAtomSelector, TrackedEvents, TrackedLoadState,
+        TrackedQuery, TrackedResource,
+    },
+    BuildContext, RefHandle, ScopedValueKey, TrackingContext, WindowInfo,
+};
 
 use super::{
-    atom::{AtomCell, AtomHandle, AtomMethods},
+    atom::{AtomCell, AtomHandle, AtomMethods, AtomStore, DeferredAtomWrites, GlobalAtomApi},
+    callback::{Callback, CallbackHandle, CallbackSystem},
+    derived::DerivedAtom,
+    error_boundary::PanicBoundary,
+    interval::IntervalTimer,
+    local::{LocalState, StateSetter},
+    resource_field::ResourceFieldBinding,
     scoped_values::ScopedValueMap,
+    task::{PendingTask, TaskState},
+    tracking::EffectCleanup,
 };
 
+/// Cleanup function returned by an effect created with [`Cx::use_effect_with_cleanup`].
+pub type CleanupFn = Box<dyn FnOnce(&mut World) + Send + Sync>;
+
 /// Cx is a context parameter that is passed to presenters. It contains the presenter's
 /// properties (passed from the parent presenter), plus other context information needed
 /// in building the view state graph.
@@ -40,6 +74,50 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         self.bc.world.resource::<T>()
     }
 
+    /// Return an atom which is kept in sync with a field of the resource `T`, as selected by
+    /// the `get` and `set` accessors. The resource is added as a dependency of the current
+    /// presenter, and when it changes the atom is updated to match. Values written to the atom
+    /// are copied back into the resource at the start of the next render. This allows a
+    /// widget which edits an atom to be bound directly to application state.
+    ///
+    /// This must be called unconditionally and in the same order each time the presenter runs.
+    #[track_caller]
+    pub fn use_resource_field_atom<T: Resource, V: Clone + PartialEq + Send + Sync + 'static>(
+        &mut self,
+        get: fn(&T) -> V,
+        set: fn(&mut T, V),
+    ) -> AtomHandle<V> {
+        let handle = self.create_atom_handle::<V>();
+        let value = get(self.use_resource::<T>());
+        let mut entt = self.bc.world.entity_mut(handle.id);
+        match entt.get::<AtomCell>() {
+            Some(cell) => {
+                // Only write the atom if it differs, otherwise the write will be copied back.
+                let prev = cell.0.downcast_ref::<V>().expect("Atom is incorrect type");
+                if *prev != value {
+                    entt.insert(AtomCell(Box::new(value)));
+                }
+            }
+            None => {
+                entt.insert(AtomCell(Box::new(value)));
+                let binding = ResourceFieldBinding::new(self.bc.world, handle, get, set);
+                self.bc.world.entity_mut(handle.id).insert(binding);
+            }
+        }
+        handle
+    }
+
+    /// Return a mutable reference to the resource of the given type. Calling this function
+    /// adds the resource as a dependency of the current presenter invocation.
+    ///
+    /// Note that mutating the resource counts as a change, which will cause this presenter
+    /// (and any others which depend on the resource) to be rebuilt. Avoid unconditionally
+    /// mutating the resource, otherwise the presenter will rebuild every frame.
+    pub fn use_resource_mut<T: Resource>(&mut self) -> Mut<'_, T> {
+        self.add_tracked_resource::<T>();
+        self.bc.world.resource_mut::<T>()
+    }
+
     /// Return a reference to the Component `C` on the given entity.
     pub fn use_component<C: Component>(&self, entity: Entity) -> Option<&C> {
         match self.bc.world.get_entity(entity) {
@@ -61,6 +139,178 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         }
     }
 
+    /// Return the events of type `E` which have been sent since the previous time the
+    /// presenter ran. The presenter will re-run whenever new events are sent. Events are not
+    /// consumed, so other readers of the event queue are unaffected. The first time the
+    /// presenter runs, this returns an empty list.
+    ///
+    /// This must be called unconditionally and in the same order each time the presenter runs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the event type has not been registered with `App::add_event`, since nothing
+    /// would clear the event queue.
+    #[track_caller]
+    pub fn use_event<E: Event + Clone>(&mut self) -> Vec<E> {
+        let id = self.create_entity();
+        let world = &mut *self.bc.world;
+        if !world.contains_resource::<Events<E>>() {
+            panic!(
+                "Events<{}> not found: did you forget to call App::add_event?",
+                std::any::type_name::<E>()
+            );
+        }
+        world.resource_scope(|world, events: Mut<Events<E>>| {
+            let mut entt = world.entity_mut(id);
+            let result = match entt.get_mut::<EventCursor<E>>() {
+                Some(mut cursor) => cursor.0.read(&events).cloned().collect(),
+                None => {
+                    let mut reader = ManualEventReader::<E>::default();
+                    reader.clear(&events);
+                    entt.insert(EventCursor(reader));
+                    Vec::new()
+                }
+            };
+            self.tracking
+                .borrow_mut()
+                .resources
+                .push(Box::new(TrackedEvents::new(&events)));
+            result
+        })
+    }
+
+    /// Run a query against the world, and return a list of the query results. The query is
+    /// added to the tracking scope, so the presenter will re-run if the set of matching entities
+    /// changes, or if any component read by the query is modified.
+    ///
+    /// The query state is created once per query type and cached in the world. The tracking
+    /// scope keeps the list of matching entities, and re-runs the query each frame to check for
+    /// changes. The cost of this is proportional to the number of matching entities, so prefer
+    /// narrow filters. Changes to components which only appear in the filter (such as
+    /// `With<T>`) are not tracked, only whether an entity matches; and changes made by the
+    /// presenter itself are not seen until the next frame.
+    pub fn use_query<D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static>(
+        &mut self,
+    ) -> Vec<ROQueryItem<'_, D>> {
+        let state = QueryStateCache::get::<D, F>(self.bc.world);
+        let world: &World = self.bc.world;
+        let (entities, items): (Vec<Entity>, Vec<_>) = {
+            let mut query = state.lock().unwrap();
+            query.update_archetypes(world);
+            query.iter_manual(world).unzip()
+        };
+        self.tracking
+            .borrow_mut()
+            .resources
+            .push(Box::new(TrackedQuery::new(
+                state,
+                entities,
+                world.read_change_tick(),
+            )));
+        items
+    }
+
+    /// Run an asynchronous task on the [`AsyncComputeTaskPool`], returning its current state.
+    /// The task is started the first time the presenter runs, and the presenter will re-run
+    /// once it completes, at which point [`TaskState::Ready`] is returned with the result.
+    /// If `deps` changes, any running task is cancelled and a new one is started by calling
+    /// `future_fn` again.
+    ///
+    /// This must be called unconditionally and in the same order each time the presenter runs.
+    #[track_caller]
+    pub fn use_task<
+        T: Clone + Send + Sync + 'static,
+        D: Clone + PartialEq + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    >(
+        &mut self,
+        future_fn: impl FnOnce() -> Fut,
+        deps: D,
+    ) -> TaskState<T> {
+        let result = self.create_atom_handle::<TaskState<T>>();
+        let deps_handle = self.create_atom_handle::<D>();
+        let changed = match self.bc.world.get::<AtomCell>(deps_handle.id) {
+            Some(cell) => cell.0.downcast_ref::<D>() != Some(&deps),
+            None => true,
+        };
+        if changed {
+            self.bc
+                .world
+                .entity_mut(deps_handle.id)
+                .insert(AtomCell(Box::new(deps)));
+            let task = AsyncComputeTaskPool::get().spawn(future_fn());
+            self.bc.world.entity_mut(result.id).insert((
+                AtomCell(Box::new(TaskState::<T>::Pending)),
+                PendingTask::new(task),
+            ));
+        }
+        self.read_atom(result)
+    }
+
+    /// Return a tick counter which is incremented every `period`, causing the presenter to
+    /// re-run each time. The count starts at zero when the presenter first runs. Changing
+    /// `period` takes effect at the next tick, without resetting the count, and a zero period
+    /// pauses the interval. The interval is stopped when the presenter invocation is razed.
+    ///
+    /// This must be called unconditionally and in the same order each time the presenter runs.
+    #[track_caller]
+    pub fn use_interval(&mut self, period: Duration) -> u64 {
+        let handle = self.create_atom_handle::<u64>();
+        let mut entt = self.bc.world.entity_mut(handle.id);
+        match entt.get_mut::<IntervalTimer>() {
+            Some(mut timer) => timer.period = period,
+            None => {
+                entt.insert((
+                    AtomCell(Box::new(0u64)),
+                    IntervalTimer {
+                        period,
+                        elapsed: Duration::ZERO,
+                    },
+                ));
+            }
+        }
+        self.read_atom(handle)
+    }
+
+    /// Return the logical rectangle of the UI node on the given entity. The entity's `Node`
+    /// and `GlobalTransform` components are added to the tracking scope, so the presenter will
+    /// re-run when the element is resized or moved. Returns an empty rectangle if the entity
+    /// has not been laid out yet.
+    pub fn use_element_rect(&mut self, id: Entity) -> Rect {
+        match (
+            self.use_component::<Node>(id),
+            self.use_component::<GlobalTransform>(id),
+        ) {
+            (Some(node), Some(transform)) => node.logical_rect(transform),
+            _ => Rect::default(),
+        }
+    }
+
+    /// Return the metrics of the primary window. The presenter will re-run whenever the
+    /// window is resized, its scale factor changes, or it gains or loses focus. Since a
+    /// resize can produce a new size every frame, presenters that call this should be kept
+    /// small, passing the values they need down to child presenters as props. Returns default
+    /// values if there is no primary window, or if it has not been measured yet.
+    pub fn use_window(&mut self) -> WindowInfo {
+        let mut query = self
+            .bc
+            .world
+            .query_filtered::<Entity, With<PrimaryWindow>>();
+        match query.get_single(self.bc.world) {
+            Ok(window) => self.use_window_entity(window),
+            Err(_) => WindowInfo::default(),
+        }
+    }
+
+    /// Return the metrics of the given window. This is like [`Cx::use_window`], but can be
+    /// used in applications with multiple windows.
+    pub fn use_window_entity(&mut self, window: Entity) -> WindowInfo {
+        self.bc.world.init_component::<WindowInfo>();
+        self.use_component::<WindowInfo>(window)
+            .copied()
+            .unwrap_or_default()
+    }
+
     /// Return a reference to the Component `C` on the entity that contains the current
     /// presenter invocation.
     pub fn use_view_component<C: Component>(&self) -> Option<&C> {
@@ -68,7 +318,8 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         self.bc.world.entity(self.bc.entity).get::<C>()
     }
 
-    /// Run a function on the view entity. Will only re-run when [`deps`] changes.
+    /// Run a function on the view entity. Will only re-run when `deps` changes.
+    #[track_caller]
     pub fn use_effect<F: FnOnce(EntityWorldMut), D: Clone + PartialEq + Send + Sync + 'static>(
         &mut self,
         effect: F,
@@ -91,6 +342,96 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         }
     }
 
+    /// Run a function on the view entity, like [`Cx::use_effect`], which returns a cleanup
+    /// function. When `deps` changes, the cleanup function from the previous run is called
+    /// before the effect is run again. The last cleanup function is called when the presenter
+    /// is razed.
+    #[track_caller]
+    pub fn use_effect_with_cleanup<
+        F: FnOnce(EntityWorldMut) -> CleanupFn,
+        D: Clone + PartialEq + Send + Sync + 'static,
+    >(
+        &mut self,
+        effect: F,
+        deps: D,
+    ) {
+        let handle = self.create_atom_handle::<D>();
+        let changed = match self.bc.world.get::<AtomCell>(handle.id) {
+            Some(cell) => *cell.0.downcast_ref::<D>().expect("Atom is incorrect type") != deps,
+            None => true,
+        };
+        if changed {
+            EffectCleanup::run(self.bc.world, handle.id);
+            let cleanup = (effect)(self.bc.world.entity_mut(self.bc.entity));
+            self.bc
+                .world
+                .entity_mut(handle.id)
+                .insert((AtomCell(Box::new(deps)), EffectCleanup(cleanup)));
+        }
+    }
+
+    /// Return a value computed by `compute`. The value is cached, and `compute` is only
+    /// called again when `deps` changes.
+    ///
+    /// Like other hooks which allocate presenter-owned state, this must be called
+    /// unconditionally and in the same order each time the presenter runs.
+    #[track_caller]
+    pub fn use_memo<
+        D: Clone + PartialEq + Send + Sync + 'static,
+        T: Clone + Send + Sync + 'static,
+    >(
+        &mut self,
+        deps: D,
+        compute: impl FnOnce() -> T,
+    ) -> T {
+        let handle = self.create_atom_handle::<(D, T)>();
+        let mut entt = self.bc.world.entity_mut(handle.id);
+        match entt.get_mut::<AtomCell>() {
+            Some(mut cell) => {
+                let memo = cell
+                    .0
+                    .downcast_mut::<(D, T)>()
+                    .expect("Atom is incorrect type");
+                if memo.0 != deps {
+                    *memo = (deps, compute());
+                }
+                memo.1.clone()
+            }
+            None => {
+                let value = compute();
+                entt.insert(AtomCell(Box::new((deps, value.clone()))));
+                value
+            }
+        }
+    }
+
+    /// Load an asset from a path, returning its handle and current load state. The presenter
+    /// will re-render whenever the load state changes, so that it can show a placeholder
+    /// until the asset is ready. The handle is cached, so the load is only started once
+    /// unless the path changes.
+    ///
+    /// This must be called unconditionally and in the same order each time the presenter runs.
+    #[track_caller]
+    pub fn use_asset<A: Asset>(
+        &mut self,
+        path: impl Into<AssetPath<'static>>,
+    ) -> (Handle<A>, LoadState) {
+        let path: AssetPath<'static> = path.into();
+        let server = self.bc.world.resource::<AssetServer>().clone();
+        let handle = self.use_memo(path.clone(), || server.load::<A>(path));
+        let state = server
+            .get_load_state(&handle)
+            .unwrap_or(LoadState::NotLoaded);
+        self.tracking
+            .borrow_mut()
+            .resources
+            .push(Box::new(TrackedLoadState::new(
+                handle.id().untyped(),
+                state,
+            )));
+        (handle, state)
+    }
+
     /// Return a reference to the entity that holds the current presenter invocation.
     pub fn use_view_entity(&self) -> EntityRef<'_> {
         self.bc.world.entity(self.bc.entity)
@@ -101,26 +442,42 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         self.bc.world.entity_mut(self.bc.entity)
     }
 
+    /// Despawn, at the end of this render, the owned entities of any hooks which were not
+    /// called during it, including the state of atoms, effects and callbacks. If such a hook
+    /// is called again on a later render, it starts over with new state. Since owned entities
+    /// are assigned to hooks in call order, this only allows the hooks at the end of the
+    /// presenter to be skipped; hooks before them must still be called unconditionally.
+    ///
+    /// Without this, owned entities live until the presenter is razed. Handles to a released
+    /// entity which have been captured in closures or passed to other presenters will be left
+    /// dangling, so they should not outlive the render in which the hook was last called; use
+    /// [`WeakAtomHandle`](crate::WeakAtomHandle) where that can't be guaranteed.
+    pub fn release_unused_entities(&mut self) {
+        self.tracking.borrow_mut().release_unused = true;
+    }
+
     /// Spawn an empty [`Entity`] which is owned by this presenter. The entity will be
     /// despawned when the presenter state is razed.
+    #[track_caller]
     pub fn create_entity(&mut self) -> Entity {
         let mut tracking = self.tracking.borrow_mut();
         let index = tracking.next_entity_index;
         tracking.next_entity_index = index + 1;
-        match index.cmp(&tracking.owned_entities.len()) {
-            Ordering::Less => tracking.owned_entities[index],
-            Ordering::Equal => {
+        tracking.check_hook_location(index, Location::caller());
+        match tracking.owned_entities.get(index) {
+            Some(id) => *id,
+            None => {
                 let id = self.bc.world.spawn_empty().id();
                 tracking.owned_entities.push(id);
                 id
             }
-            Ordering::Greater => panic!("Invalid presenter entity index"),
         }
     }
 
     /// Create an [`AtomHandle`]. This can be used to read and write the content of an atom.
     /// The handle is owned by the current context, and will be deleted when the presenter
     /// invocation is razed.
+    #[track_caller]
     pub fn create_atom<T: Clone + Sync + Send + Default + 'static>(&mut self) -> AtomHandle<T> {
         let handle = self.create_atom_handle::<T>();
         let mut entt = self.bc.world.entity_mut(handle.id);
@@ -136,6 +493,7 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
     /// Create an [`AtomHandle`] with an initial value.
     /// The handle is owned by the current context, and will be deleted when the presenter
     /// invocation is razed.
+    #[track_caller]
     pub fn create_atom_init<T: Clone + Sync + Send + 'static>(
         &mut self,
         init: impl FnOnce() -> T,
@@ -151,6 +509,65 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         handle
     }
 
+    /// Create a [`RefHandle`] which can be passed to a child view or presenter, and filled in
+    /// with its output entity using [`View::ref_out`]. Read the entity with
+    /// [`Cx::read_atom`], which will re-render this presenter once the child has been built.
+    #[track_caller]
+    pub fn use_ref(&mut self) -> RefHandle {
+        self.create_atom::<Option<Entity>>()
+    }
+
+    /// Make this presenter a boundary for panics in its child presenters, and return an atom
+    /// which holds the most recent panic message. This is intended to be read with
+    /// [`Cx::read_atom`] and passed to an [`ErrorBoundary`](crate::ErrorBoundary).
+    ///
+    /// Panics are caught with [`std::panic::catch_unwind`]. Since presenters and their view
+    /// state are not required to be `UnwindSafe`, a presenter which panics is left in an
+    /// unspecified state, and should be discarded, which the `ErrorBoundary` does by razing it.
+    /// Panic messages are still printed by the panic hook.
+    #[track_caller]
+    pub fn use_panic_boundary(&mut self) -> AtomHandle<Option<String>> {
+        let handle = self.create_atom::<Option<String>>();
+        let mut entt = self.bc.world.entity_mut(self.bc.entity);
+        if !entt.contains::<PanicBoundary>() {
+            entt.insert(PanicBoundary(handle));
+        }
+        handle
+    }
+
+    /// Return the global atom with the given key, creating it with the value returned by
+    /// `init` if it doesn't exist. See [`GlobalAtomApi::global_atom`]. Unlike atoms created
+    /// with [`Cx::create_atom`], the atom is not deleted when the presenter is razed.
+    pub fn use_global_atom<T: Clone + Sync + Send + 'static>(
+        &mut self,
+        key: &'static str,
+        init: impl FnOnce() -> T,
+    ) -> AtomHandle<T> {
+        self.bc.world.global_atom(key, init)
+    }
+
+    /// Create a derived atom, whose value is computed from other atoms. The `compute` function
+    /// is re-run whenever any of the atoms that it read (via the [`AtomStore`]) have changed,
+    /// and the result is stored in the atom. Presenters which read the atom are only notified
+    /// if the new result differs from the previous one. The returned handle should be treated
+    /// as read-only, since any value written to it will be overwritten on the next recompute.
+    ///
+    /// As with [`Cx::use_callback`], the compute function is only registered the first time
+    /// the presenter runs. The atom is deleted when the presenter invocation is razed.
+    #[track_caller]
+    pub fn create_derived<T: Clone + PartialEq + Sync + Send + 'static>(
+        &mut self,
+        compute: impl Fn(&AtomStore) -> T + Send + Sync + 'static,
+    ) -> AtomHandle<T> {
+        let handle = self.create_atom_handle::<T>();
+        if !self.bc.world.entity(handle.id).contains::<DerivedAtom>() {
+            let derived = DerivedAtom::new(self.bc.world, compute);
+            self.bc.world.entity_mut(handle.id).insert(derived);
+            DerivedAtom::recompute(self.bc.world, handle.id);
+        }
+        handle
+    }
+
     /// Read the value of an atom. This adds the atom to the tracking list for this
     /// presenter, so that it will re-render when the atom changes.
     pub fn read_atom<T: Clone + Sync + Send + 'static>(&self, handle: AtomHandle<T>) -> T {
@@ -166,6 +583,30 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         self.bc.world.get_atom(handle)
     }
 
+    /// Read a value derived from an atom, such as a single field. Unlike [`Cx::read_atom`],
+    /// the presenter will only re-render when the value returned by `select` changes, rather
+    /// than whenever the atom is written.
+    pub fn read_atom_selector<
+        T: Clone + Sync + Send + 'static,
+        S: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(&T) -> S + Send + Sync + 'static,
+    >(
+        &self,
+        handle: AtomHandle<T>,
+        select: F,
+    ) -> S {
+        let value = select(&self.bc.world.get_atom(handle));
+        self.tracking
+            .borrow_mut()
+            .resources
+            .push(Box::new(TrackedAtomSelector::new(
+                handle.id,
+                select,
+                value.clone(),
+            )));
+        value
+    }
+
     /// Write the value of an atom. Panics if the atom handle is invalid.
     pub fn write_atom<T: Clone + Sync + Send + 'static>(
         &mut self,
@@ -175,6 +616,68 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         self.bc.world.set_atom(handle, value);
     }
 
+    /// Create a piece of local state owned by the current presenter invocation. Returns the
+    /// current value along with a setter which can be captured by event handlers. Reading the
+    /// value adds it to the tracking list, so the presenter will re-render after the setter
+    /// has been called. Writes made via the setter are applied at the start of the next render.
+    ///
+    /// The state is initialized to `T::default()` the first time the presenter runs.
+    #[deprecated(note = "use `Cx::use_local`, whose setter can also read back pending values")]
+    #[track_caller]
+    pub fn use_state<T: Clone + Sync + Send + Default + 'static>(
+        &mut self,
+    ) -> (T, impl Fn(T) + Clone + Send + Sync + 'static) {
+        let handle = self.create_atom::<T>();
+        (self.read_atom(handle), self.state_setter(handle))
+    }
+
+    /// Like [`Cx::use_state`], but initializes the state by calling `init` the first time the
+    /// presenter runs.
+    #[deprecated(note = "use `Cx::use_local`, whose setter can also read back pending values")]
+    #[track_caller]
+    pub fn use_state_init<T: Clone + Sync + Send + 'static>(
+        &mut self,
+        init: impl FnOnce() -> T,
+    ) -> (T, impl Fn(T) + Clone + Send + Sync + 'static) {
+        let handle = self.create_atom_init::<T>(init);
+        (self.read_atom(handle), self.state_setter(handle))
+    }
+
+    /// Create a local state variable owned by the current presenter invocation, initialized
+    /// by calling `init` the first time the presenter runs. Returns the current value, along
+    /// with a [`StateSetter`] which can be cloned and moved into event handlers. Setting a value
+    /// which is different from the current one causes the presenter to re-render.
+    #[track_caller]
+    pub fn use_local<T: Clone + PartialEq + Send + Sync + 'static>(
+        &mut self,
+        init: impl FnOnce() -> T,
+    ) -> (T, StateSetter<T>) {
+        let handle = self.create_atom_handle::<T>();
+        let mut entt = self.bc.world.entity_mut(handle.id);
+        let current = match entt.get::<LocalState<T>>() {
+            Some(local) => local.0.clone(),
+            None => {
+                let value = init();
+                let current = Arc::new(Mutex::new(value.clone()));
+                entt.insert((AtomCell(Box::new(value)), LocalState(current.clone())));
+                current
+            }
+        };
+        let writes = self
+            .bc
+            .world
+            .get_resource_or_insert_with(DeferredAtomWrites::default)
+            .clone();
+        (
+            self.read_atom(handle),
+            StateSetter {
+                handle,
+                current,
+                writes,
+            },
+        )
+    }
+
     /// Create a scoped value. This can be used to pass data to child presenters.
     /// The value is accessible by all child presenters.
     pub fn define_scoped_value<T: Clone + Send + Sync + PartialEq + 'static>(
@@ -227,14 +730,68 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         }
     }
 
-    // / Return an object which can be used to send a message to the current presenter.
-    // pub fn use_callback<In, Marker>(&mut self, sys: impl IntoSystem<In, (), Marker>) {
-    //     todo!()
-    // }
+    /// Retrieve the value of a context variable. If no ancestor has defined the variable, then
+    /// the default value of the key is returned, or `None` if the key has no default.
+    pub fn get_scoped_value_or_default<T: Clone + Send + Sync + 'static>(
+        &self,
+        key: ScopedValueKey<T>,
+    ) -> Option<T> {
+        let default = key.default_value().cloned();
+        self.get_scoped_value(key).or(default)
+    }
+
+    /// Provide a context variable to child presenters. This is the same as
+    /// [`define_scoped_value`](Self::define_scoped_value), and is intended to be paired with
+    /// [`consume`](Self::consume).
+    pub fn provide<T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        key: ScopedValueKey<T>,
+        value: T,
+    ) {
+        self.define_scoped_value(key, value);
+    }
+
+    /// Retrieve the value of a context variable which is required to be present. If no
+    /// ancestor has provided the variable, the default value of the key is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no ancestor has provided the variable and the key has no default.
+    pub fn consume<T: Clone + Send + Sync + 'static>(&self, key: ScopedValueKey<T>) -> T {
+        let name = key.id();
+        match self.get_scoped_value_or_default(key) {
+            Some(value) => value,
+            None => panic!(
+                "Scoped value '{}' was not provided by any ancestor presenter, and has no default",
+                name
+            ),
+        }
+    }
+
+    /// Register a one-shot system and return a [`CallbackHandle`] which can be used to invoke
+    /// it. The system is only registered the first time the presenter runs; on subsequent
+    /// runs the previously registered system is retained and the argument is ignored. The
+    /// system is unregistered when the presenter invocation is razed.
+    #[track_caller]
+    pub fn use_callback<In: 'static, Marker>(
+        &mut self,
+        sys: impl IntoSystem<In, (), Marker>,
+    ) -> CallbackHandle<In> {
+        let id = self.create_entity();
+        let mut entt = self.bc.world.entity_mut(id);
+        if !entt.contains::<CallbackSystem<In>>() {
+            entt.insert((Callback, CallbackSystem::new(sys)));
+        }
+        CallbackHandle {
+            id,
+            marker: PhantomData,
+        }
+    }
 
     /// Create an [`AtomHandle`]. This can be used to read and write the content of an atom.
     /// The handle is owned by the current context, and will be deleted when the presenter
     /// invocation is razed.
+    #[track_caller]
     fn create_atom_handle<T: Clone + Sync + Send + 'static>(&mut self) -> AtomHandle<T> {
         let id = self.create_entity();
         AtomHandle {
@@ -243,11 +800,25 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         }
     }
 
+    fn state_setter<T: Clone + Sync + Send + 'static>(
+        &mut self,
+        handle: AtomHandle<T>,
+    ) -> impl Fn(T) + Clone + Send + Sync + 'static {
+        let writes = self
+            .bc
+            .world
+            .get_resource_or_insert_with(DeferredAtomWrites::default)
+            .clone();
+        move |value: T| writes.set(handle, value)
+    }
+
     fn add_tracked_resource<T: Resource>(&self) {
-        self.tracking
-            .borrow_mut()
-            .resources
-            .push(Box::new(TrackedResource::<T>::new()));
+        let mut tracking = self.tracking.borrow_mut();
+        if tracking.resource_types.insert(TypeId::of::<T>()) {
+            tracking
+                .resources
+                .push(Box::new(TrackedResource::<T>::new()));
+        }
     }
 
     fn add_tracked_component<C: Component>(&self, entity: Entity) {
@@ -259,3 +830,778 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         self.tracking.borrow_mut().components.insert((entity, cid));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use bevy::{
+        asset::{
+            io::{
+                memory::{Dir, MemoryAssetReader},
+                AssetSource, Reader,
+            },
+            AssetLoader, AsyncReadExt, LoadContext,
+        },
+        ecs::system::RunSystemOnce,
+        utils::BoxedFuture,
+    };
+
+    use crate::{
+        plugin::render_views, task::poll_tasks, tracked_resources::TrackedResources,
+        tracking::OwnedEntities, window::update_window_info, If, PresenterFn, View, ViewHandle,
+        WeakAtomHandle,
+    };
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_use_state() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let mut tracking = TrackingContext::default();
+        let mut cx = Cx::new(&(), &mut bc, &mut tracking);
+        let (value, set_value) = cx.use_state::<i32>();
+        assert_eq!(value, 0);
+        assert_eq!(tracking.components.len(), 1);
+        set_value(value + 1);
+
+        // Setter does not take effect until writes are applied.
+        DeferredAtomWrites::apply(bc.world);
+
+        // Re-run the presenter with the same owned entities.
+        let mut tracking = TrackingContext {
+            owned_entities: tracking.owned_entities,
+            ..default()
+        };
+        let mut cx = Cx::new(&(), &mut bc, &mut tracking);
+        let (value, _) = cx.use_state::<i32>();
+        assert_eq!(value, 1);
+
+        let (value, _) = cx.use_state_init(|| "init".to_string());
+        assert_eq!(value, "init");
+    }
+
+    #[test]
+    fn test_use_local() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let mut tracking = TrackingContext::default();
+        let mut cx = Cx::new(&(), &mut bc, &mut tracking);
+        let (value, setter) = cx.use_local(|| 10);
+        assert_eq!(value, 10);
+        let setter2 = setter.clone();
+        setter2.update(|v| v + 1);
+        assert_eq!(setter.get(), 11);
+        DeferredAtomWrites::apply(bc.world);
+
+        let mut tracking = TrackingContext {
+            owned_entities: tracking.owned_entities,
+            ..default()
+        };
+        let mut cx = Cx::new(&(), &mut bc, &mut tracking);
+        let (value, _) = cx.use_local(|| 0);
+        assert_eq!(value, 11);
+    }
+
+    #[test]
+    fn test_use_memo() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+        let mut owned_entities = Vec::new();
+        let mut calls = 0;
+
+        for deps in [1, 1, 1, 2] {
+            let mut tracking = TrackingContext {
+                owned_entities,
+                ..default()
+            };
+            let mut cx = Cx::new(&(), &mut bc, &mut tracking);
+            let value = cx.use_memo(deps, || {
+                calls += 1;
+                format!("value {}", deps)
+            });
+            assert_eq!(value, format!("value {}", deps));
+            owned_entities = tracking.owned_entities;
+            if deps == 1 {
+                assert_eq!(calls, 1);
+            }
+        }
+        assert_eq!(calls, 2);
+    }
+
+    #[derive(Resource, Default)]
+    struct Counter(i32);
+
+    #[test]
+    fn test_use_resource_mut() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let entity = world.spawn_empty().id();
+        world.clear_trackers();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let mut tracking = TrackingContext::default();
+        let mut cx = Cx::new(&(), &mut bc, &mut tracking);
+        assert_eq!(cx.use_resource_mut::<Counter>().0, 0);
+        assert!(!tracking.resources[0].is_changed(bc.world));
+
+        let mut cx = Cx::new(&(), &mut bc, &mut tracking);
+        cx.use_resource_mut::<Counter>().0 += 1;
+        assert_eq!(bc.world.resource::<Counter>().0, 1);
+        assert!(tracking.resources[0].is_changed(bc.world));
+    }
+
+    #[derive(Resource, Default)]
+    struct MeasuredRect(Rect);
+
+    fn measure(mut cx: Cx<Entity>) -> impl View {
+        let rect = cx.use_element_rect(*cx.props);
+        cx.bc.world.resource_mut::<MeasuredRect>().0 = rect;
+    }
+
+    /// A `Node` with the given size, as if it had been laid out.
+    fn sized_node(size: Vec2) -> Node {
+        use bevy::reflect::Struct;
+        let mut node = Node::default();
+        *node
+            .field_mut("calculated_size")
+            .unwrap()
+            .downcast_mut::<Vec2>()
+            .unwrap() = size;
+        node
+    }
+
+    #[test]
+    fn test_use_element_rect() {
+        let mut world = World::new();
+        world.init_resource::<MeasuredRect>();
+        world.init_component::<GlobalTransform>();
+        let target = world.spawn(Node::default()).id();
+        world.spawn(ViewHandle::new(measure, target));
+
+        // Not laid out yet.
+        render_views(&mut world);
+        assert_eq!(world.resource::<MeasuredRect>().0, Rect::default());
+        world.clear_trackers();
+
+        world.entity_mut(target).insert((
+            sized_node(Vec2::new(100., 50.)),
+            GlobalTransform::from_xyz(10., 20., 0.),
+        ));
+        render_views(&mut world);
+        assert_eq!(
+            world.resource::<MeasuredRect>().0,
+            Rect::from_center_size(Vec2::new(10., 20.), Vec2::new(100., 50.))
+        );
+        world.clear_trackers();
+
+        // Moving the element re-runs the presenter.
+        *world.get_mut::<GlobalTransform>(target).unwrap() =
+            GlobalTransform::from_xyz(30., 40., 0.);
+        render_views(&mut world);
+        assert_eq!(
+            world.resource::<MeasuredRect>().0,
+            Rect::from_center_size(Vec2::new(30., 40.), Vec2::new(100., 50.))
+        );
+        world.clear_trackers();
+
+        // So does resizing it.
+        *world.get_mut::<Node>(target).unwrap() = sized_node(Vec2::new(60., 80.));
+        render_views(&mut world);
+        assert_eq!(
+            world.resource::<MeasuredRect>().0,
+            Rect::from_center_size(Vec2::new(30., 40.), Vec2::new(60., 80.))
+        );
+    }
+
+    #[derive(Resource, Default)]
+    struct MeasuredWindow(WindowInfo, usize);
+
+    fn measure_window(mut cx: Cx) -> impl View {
+        let info = cx.use_window();
+        let mut measured = cx.bc.world.resource_mut::<MeasuredWindow>();
+        measured.0 = info;
+        measured.1 += 1;
+    }
+
+    #[test]
+    fn test_use_window() {
+        let mut world = World::new();
+        world.init_resource::<MeasuredWindow>();
+        let window = world.spawn((Window::default(), PrimaryWindow)).id();
+        world.spawn(ViewHandle::new(measure_window, ()));
+
+        world.run_system_once(update_window_info);
+        render_views(&mut world);
+        let measured = world.resource::<MeasuredWindow>();
+        assert_eq!(measured.0.logical_size, Vec2::new(1280., 720.));
+        assert_eq!(measured.1, 1);
+        world.clear_trackers();
+
+        // Unrelated changes to the window don't re-run the presenter.
+        world
+            .get_mut::<Window>(window)
+            .unwrap()
+            .set_cursor_position(Some(Vec2::new(10., 10.)));
+        world.run_system_once(update_window_info);
+        render_views(&mut world);
+        assert_eq!(world.resource::<MeasuredWindow>().1, 1);
+        world.clear_trackers();
+
+        world
+            .get_mut::<Window>(window)
+            .unwrap()
+            .resolution
+            .set(800., 600.);
+        world.run_system_once(update_window_info);
+        render_views(&mut world);
+        let measured = world.resource::<MeasuredWindow>();
+        assert_eq!(measured.0.logical_size, Vec2::new(800., 600.));
+        assert_eq!(measured.1, 2);
+    }
+
+    #[derive(Asset, TypePath)]
+    struct Blob(Vec<u8>);
+
+    #[derive(Default)]
+    struct BlobLoader;
+
+    impl AssetLoader for BlobLoader {
+        type Asset = Blob;
+        type Settings = ();
+        type Error = std::io::Error;
+
+        fn load<'a>(
+            &'a self,
+            reader: &'a mut Reader,
+            _settings: &'a (),
+            _load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<Blob, std::io::Error>> {
+            Box::pin(async move {
+                let mut bytes = Vec::new();
+                reader.read_to_end(&mut bytes).await?;
+                Ok(Blob(bytes))
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["blob"]
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct LoadedBlob(Vec<(LoadState, Option<usize>)>);
+
+    fn load_blob(mut cx: Cx) -> impl View {
+        let (handle, state) = cx.use_asset::<Blob>("mem://data.blob");
+        let len = cx
+            .bc
+            .world
+            .resource::<Assets<Blob>>()
+            .get(&handle)
+            .map(|blob| blob.0.len());
+        cx.bc
+            .world
+            .resource_mut::<LoadedBlob>()
+            .0
+            .push((state, len));
+    }
+
+    #[test]
+    fn test_use_asset() {
+        let dir = Dir::default();
+        dir.insert_asset(Path::new("data.blob"), vec![1u8, 2, 3]);
+        let mut app = App::new();
+        app.register_asset_source(
+            "mem",
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        );
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Blob>()
+            .init_asset_loader::<BlobLoader>()
+            .init_resource::<LoadedBlob>()
+            .add_systems(Update, render_views);
+        app.world.spawn(ViewHandle::new(load_blob, ()));
+
+        app.update();
+        assert_eq!(
+            app.world.resource::<LoadedBlob>().0,
+            vec![(LoadState::Loading, None)]
+        );
+
+        // The presenter re-renders once the load completes.
+        for _ in 0..1000 {
+            if app.world.resource::<LoadedBlob>().0.len() > 1 {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            app.update();
+        }
+        assert_eq!(
+            app.world.resource::<LoadedBlob>().0,
+            vec![(LoadState::Loading, None), (LoadState::Loaded, Some(3))]
+        );
+
+        // Once loaded, it isn't re-rendered again.
+        app.update();
+        assert_eq!(app.world.resource::<LoadedBlob>().0.len(), 2);
+    }
+
+    #[derive(Clone, Default)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Resource, Default)]
+    struct SelectedX(i32, usize);
+
+    fn select_x(cx: Cx<AtomHandle<Point>>) -> impl View {
+        let x = cx.read_atom_selector(*cx.props, |p: &Point| p.x);
+        let mut selected = cx.bc.world.resource_mut::<SelectedX>();
+        selected.0 = x;
+        selected.1 += 1;
+    }
+
+    #[test]
+    fn test_read_atom_selector() {
+        let mut world = World::new();
+        world.init_resource::<SelectedX>();
+        let point = world.create_atom::<Point>();
+        world.set_atom(point, Point::default());
+        world.spawn(ViewHandle::new(select_x, point));
+        render_views(&mut world);
+        assert_eq!(world.resource::<SelectedX>().1, 1);
+        world.clear_trackers();
+
+        // Changing an unselected field does not re-render.
+        world.set_atom(point, Point { x: 0, y: 1 });
+        render_views(&mut world);
+        assert_eq!(world.get_atom(point).y, 1);
+        assert_eq!(world.resource::<SelectedX>().1, 1);
+        world.clear_trackers();
+
+        world.set_atom(point, Point { x: 2, y: 1 });
+        render_views(&mut world);
+        assert_eq!(world.resource::<SelectedX>().0, 2);
+        assert_eq!(world.resource::<SelectedX>().1, 2);
+    }
+
+    #[derive(Resource, Default)]
+    struct Settings {
+        volume: i32,
+    }
+
+    #[derive(Resource)]
+    struct VolumeAtom(AtomHandle<i32>);
+
+    fn volume(mut cx: Cx) -> impl View {
+        let atom = cx.use_resource_field_atom::<Settings, _>(|s| s.volume, |s, v| s.volume = v);
+        cx.bc.world.insert_resource(VolumeAtom(atom));
+    }
+
+    #[test]
+    fn test_use_resource_field_atom() {
+        let mut world = World::new();
+        world.insert_resource(Settings { volume: 3 });
+        world.spawn(ViewHandle::new(volume, ()));
+        render_views(&mut world);
+        let atom = world.resource::<VolumeAtom>().0;
+        assert_eq!(world.get_atom(atom), 3);
+        world.clear_trackers();
+
+        // Writing the atom updates the resource.
+        world.set_atom(atom, 5);
+        render_views(&mut world);
+        assert_eq!(world.resource::<Settings>().volume, 5);
+        assert_eq!(world.get_atom(atom), 5);
+        world.clear_trackers();
+
+        // Writing the resource updates the atom.
+        world.resource_mut::<Settings>().volume = 7;
+        render_views(&mut world);
+        assert_eq!(world.get_atom(atom), 7);
+        assert_eq!(world.resource::<Settings>().volume, 7);
+    }
+
+    #[derive(Resource, Default)]
+    struct TaskResult(Option<TaskState<i32>>, usize);
+
+    fn task_presenter(mut cx: Cx<i32>) -> impl View {
+        let value = *cx.props;
+        let state = cx.use_task(move || async move { value * 2 }, value);
+        let mut result = cx.bc.world.resource_mut::<TaskResult>();
+        result.0 = Some(state);
+        result.1 += 1;
+    }
+
+    fn wait_for_tasks(world: &mut World) {
+        let mut q = world.query::<&PendingTask>();
+        for _ in 0..1000 {
+            world.run_system_once(poll_tasks);
+            if q.iter(world).next().is_none() {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        panic!("Task did not complete");
+    }
+
+    #[test]
+    fn test_use_task() {
+        AsyncComputeTaskPool::get_or_init(bevy::tasks::TaskPool::default);
+        let mut world = World::new();
+        world.init_resource::<TaskResult>();
+        world.spawn(ViewHandle::new(task_presenter, 21));
+        render_views(&mut world);
+        assert_eq!(world.resource::<TaskResult>().0, Some(TaskState::Pending));
+        world.clear_trackers();
+
+        // Result is surfaced on a later build.
+        wait_for_tasks(&mut world);
+        render_views(&mut world);
+        assert_eq!(world.resource::<TaskResult>().0, Some(TaskState::Ready(42)));
+        assert_eq!(world.resource::<TaskResult>().1, 2);
+    }
+
+    #[derive(Event, Clone, PartialEq, Debug)]
+    struct Ping(i32);
+
+    #[derive(Resource, Default)]
+    struct Pings(Vec<Ping>, usize);
+
+    fn ping_log(mut cx: Cx) -> impl View {
+        let events = cx.use_event::<Ping>();
+        let mut pings = cx.bc.world.resource_mut::<Pings>();
+        pings.0 = events;
+        pings.1 += 1;
+    }
+
+    #[derive(Resource, Default)]
+    struct Effects {
+        deps: i32,
+        setup: usize,
+        cleanup: usize,
+    }
+
+    fn effect_presenter(mut cx: Cx) -> impl View {
+        let deps = cx.use_resource::<Effects>().deps;
+        cx.use_effect_with_cleanup(
+            |mut entt| {
+                entt.world_scope(|world| world.resource_mut::<Effects>().setup += 1);
+                Box::new(|world: &mut World| world.resource_mut::<Effects>().cleanup += 1)
+            },
+            deps,
+        );
+    }
+
+    #[test]
+    fn test_use_effect_with_cleanup() {
+        let mut world = World::new();
+        world.init_resource::<Effects>();
+        let root = world.spawn(ViewHandle::new(effect_presenter, ())).id();
+        render_views(&mut world);
+        assert_eq!(world.resource::<Effects>().setup, 1);
+        assert_eq!(world.resource::<Effects>().cleanup, 0);
+        world.clear_trackers();
+
+        // Deps changed: cleanup runs before the effect is run again.
+        world.resource_mut::<Effects>().deps = 1;
+        render_views(&mut world);
+        assert_eq!(world.resource::<Effects>().setup, 2);
+        assert_eq!(world.resource::<Effects>().cleanup, 1);
+        world.clear_trackers();
+
+        // Re-rendering with the same deps doesn't run either.
+        world.resource_mut::<Effects>().set_changed();
+        render_views(&mut world);
+        assert_eq!(world.resource::<Effects>().setup, 2);
+        assert_eq!(world.resource::<Effects>().cleanup, 1);
+
+        // Razing the presenter runs the last cleanup.
+        let handle = world.entity_mut(root).take::<ViewHandle>().unwrap();
+        handle.inner.lock().unwrap().raze(&mut world, root);
+        assert_eq!(world.resource::<Effects>().setup, 2);
+        assert_eq!(world.resource::<Effects>().cleanup, 2);
+    }
+
+    #[test]
+    fn test_use_event() {
+        let mut world = World::new();
+        world.init_resource::<Pings>();
+        world.init_resource::<Events<Ping>>();
+        world.send_event(Ping(0));
+        world.spawn(ViewHandle::new(ping_log, ()));
+        render_views(&mut world);
+        assert_eq!(world.resource::<Pings>().0, vec![]);
+        world.clear_trackers();
+
+        // Presenter re-renders with only the new events.
+        world.send_event(Ping(1));
+        world.send_event(Ping(2));
+        render_views(&mut world);
+        assert_eq!(world.resource::<Pings>().0, vec![Ping(1), Ping(2)]);
+        assert_eq!(world.resource::<Pings>().1, 2);
+        world.clear_trackers();
+
+        // No new events, no re-render.
+        render_views(&mut world);
+        assert_eq!(world.resource::<Pings>().1, 2);
+
+        // Events are not consumed.
+        let mut reader = world.resource::<Events<Ping>>().get_reader();
+        assert_eq!(reader.read(world.resource::<Events<Ping>>()).count(), 3);
+    }
+
+    #[derive(Component)]
+    struct Marker;
+
+    #[test]
+    fn test_use_query() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let m1 = world.spawn(Marker).id();
+        let m2 = world.spawn(Marker).id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let mut tracking = TrackingContext::default();
+        let mut cx = Cx::new(&(), &mut bc, &mut tracking);
+        let mut result = cx.use_query::<Entity, With<Marker>>();
+        result.sort();
+        assert_eq!(result, vec![m1, m2]);
+        assert_eq!(tracking.resources.len(), 1);
+        assert!(!tracking.resources[0].is_changed(bc.world));
+
+        // Adding a matching entity changes the query.
+        let m3 = bc.world.spawn(Marker).id();
+        assert!(tracking.resources[0].is_changed(bc.world));
+
+        // Removing it restores the original set.
+        bc.world.despawn(m3);
+        assert!(!tracking.resources[0].is_changed(bc.world));
+    }
+
+    #[derive(Resource, Default)]
+    struct Roots(Vec<Entity>, usize);
+
+    fn root_list(mut cx: Cx) -> impl View {
+        let mut roots = cx.use_query::<Entity, (Without<Parent>, With<Marker>)>();
+        roots.sort();
+        let mut result = cx.bc.world.resource_mut::<Roots>();
+        result.0 = roots;
+        result.1 += 1;
+    }
+
+    #[test]
+    fn test_use_query_roots() {
+        let mut world = World::new();
+        world.init_resource::<Roots>();
+        let a = world.spawn(Marker).id();
+        let b = world.spawn(Marker).id();
+        world.spawn(ViewHandle::new(root_list, ()));
+        render_views(&mut world);
+        assert_eq!(world.resource::<Roots>().0, vec![a, b]);
+        world.clear_trackers();
+
+        // Parenting an entity removes it from the list of roots.
+        world.entity_mut(b).set_parent(a);
+        render_views(&mut world);
+        assert_eq!(world.resource::<Roots>().0, vec![a]);
+        assert_eq!(world.resource::<Roots>().1, 2);
+        world.clear_trackers();
+
+        // No changes, no re-render.
+        render_views(&mut world);
+        assert_eq!(world.resource::<Roots>().1, 2);
+
+        world.entity_mut(b).remove_parent();
+        render_views(&mut world);
+        assert_eq!(world.resource::<Roots>().0, vec![a, b]);
+    }
+
+    const PLAIN: ScopedValueKey<u32> = ScopedValueKey::new("plain");
+    const WITH_DEFAULT: ScopedValueKey<u32> = ScopedValueKey::with_default("with-default", 7);
+
+    #[test]
+    fn test_scoped_value_defaults() {
+        let mut world = World::new();
+        let parent = world.spawn_empty().id();
+        let child = world.spawn_empty().set_parent(parent).id();
+
+        let mut bc = BuildContext::new(&mut world, child);
+        let mut tracking = TrackingContext::default();
+        let cx = Cx::new(&(), &mut bc, &mut tracking);
+        assert_eq!(cx.get_scoped_value(WITH_DEFAULT), None);
+        assert_eq!(cx.get_scoped_value_or_default(WITH_DEFAULT), Some(7));
+        assert_eq!(cx.get_scoped_value_or_default(PLAIN), None);
+        assert_eq!(cx.consume(WITH_DEFAULT), 7);
+
+        // A value provided by an ancestor takes precedence over the default.
+        let mut bc = BuildContext::new(&mut world, parent);
+        let mut tracking = TrackingContext::default();
+        let mut cx = Cx::new(&(), &mut bc, &mut tracking);
+        cx.provide(WITH_DEFAULT, 3);
+        cx.provide(PLAIN, 4);
+
+        let mut bc = BuildContext::new(&mut world, child);
+        let mut tracking = TrackingContext::default();
+        let cx = Cx::new(&(), &mut bc, &mut tracking);
+        assert_eq!(cx.get_scoped_value_or_default(WITH_DEFAULT), Some(3));
+        assert_eq!(cx.consume(WITH_DEFAULT), 3);
+        assert_eq!(cx.consume(PLAIN), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "'plain' was not provided")]
+    fn test_scoped_value_required() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+        let mut tracking = TrackingContext::default();
+        let cx = Cx::new(&(), &mut bc, &mut tracking);
+        cx.consume(PLAIN);
+    }
+
+    #[derive(Resource, Default)]
+    struct ResA(i32);
+
+    #[derive(Resource, Default)]
+    struct ResB(i32);
+
+    fn two_resources(cx: Cx) -> impl View {
+        let a = cx.use_resource::<ResA>().0;
+        let b = cx.use_resource::<ResB>().0;
+        let a2 = cx.use_resource::<ResA>().0;
+        format!("{}", a + b + a2)
+    }
+
+    #[test]
+    fn test_tracked_resources_dedup() {
+        let mut world = World::new();
+        world.init_resource::<ResA>();
+        world.init_resource::<ResB>();
+        let root = world.spawn(ViewHandle::new(two_resources, ())).id();
+        for i in 0..5 {
+            world.resource_mut::<ResA>().0 = i;
+            render_views(&mut world);
+            world.clear_trackers();
+            assert_eq!(world.get::<TrackedResources>(root).unwrap().data.len(), 2);
+        }
+    }
+
+    #[derive(Resource)]
+    struct Flag(bool);
+
+    fn conditional_hooks(mut cx: Cx) -> impl View {
+        if cx.use_resource::<Flag>().0 {
+            cx.create_atom::<i32>();
+        }
+        cx.create_atom::<String>();
+    }
+
+    fn trailing_hooks(mut cx: Cx) -> impl View {
+        cx.create_atom::<String>();
+        if cx.use_resource::<Flag>().0 {
+            cx.create_atom::<i32>();
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_hook_order_mismatch() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut world = World::new();
+        world.insert_resource(Flag(true));
+        world.spawn(ViewHandle::new(conditional_hooks, ()));
+        render_views(&mut world);
+        world.clear_trackers();
+
+        world.resource_mut::<Flag>().0 = false;
+        let payload = catch_unwind(AssertUnwindSafe(|| render_views(&mut world))).unwrap_err();
+        let message = payload.downcast_ref::<String>().unwrap();
+        assert!(message.contains("conditional_hooks"));
+        assert!(message.contains("hook #0"));
+        assert!(message.contains(file!()));
+    }
+
+    #[test]
+    fn test_hook_count_mismatch() {
+        let mut world = World::new();
+        world.insert_resource(Flag(false));
+        let root = world.spawn(ViewHandle::new(trailing_hooks, ())).id();
+        render_views(&mut world);
+        world.clear_trackers();
+        assert_eq!(world.get::<OwnedEntities>(root).unwrap().0.len(), 1);
+
+        // Hooks called at the end of the presenter only produce a warning, and their
+        // entities are kept when the hooks are skipped.
+        for flag in [true, false, true] {
+            world.resource_mut::<Flag>().0 = flag;
+            render_views(&mut world);
+            world.clear_trackers();
+            assert_eq!(world.get::<OwnedEntities>(root).unwrap().0.len(), 2);
+        }
+    }
+
+    #[derive(Resource)]
+    struct Show(bool);
+
+    #[derive(Resource)]
+    struct LastAtom(WeakAtomHandle<i32>);
+
+    fn optional_atom(mut cx: Cx) -> impl View {
+        cx.release_unused_entities();
+        cx.create_atom::<String>();
+        if cx.use_resource::<Flag>().0 {
+            let atom = cx.create_atom_init(|| 1);
+            cx.bc.world.insert_resource(LastAtom(atom.downgrade()));
+        }
+    }
+
+    fn toggle_optional_atom(cx: Cx) -> impl View {
+        If::new(cx.use_resource::<Show>().0, optional_atom.bind(()), ())
+    }
+
+    #[test]
+    fn test_release_unused_entities() {
+        let mut world = World::new();
+        world.insert_resource(Flag(true));
+        world.insert_resource(Show(true));
+        world.spawn(ViewHandle::new(toggle_optional_atom, ()));
+        render_views(&mut world);
+        world.clear_trackers();
+        let baseline = world.entities().len();
+
+        let step = |world: &mut World, flag: bool, show: bool| {
+            world.resource_mut::<Flag>().0 = flag;
+            world.resource_mut::<Show>().0 = show;
+            render_views(world);
+            world.clear_trackers();
+            world.entities().len()
+        };
+        for _ in 0..3 {
+            // The atom in the skipped branch is despawned.
+            let atom = world.resource::<LastAtom>().0;
+            assert!(atom.upgrade(&world).is_some());
+            assert_eq!(step(&mut world, false, true), baseline - 1);
+            assert!(atom.upgrade(&world).is_none());
+
+            // And re-created when the branch is taken again.
+            assert_eq!(step(&mut world, true, true), baseline);
+            let atom = world.resource::<LastAtom>().0.upgrade(&world).unwrap();
+            assert_eq!(world.get_atom(atom), 1);
+
+            // Toggling the presenter itself doesn't leak.
+            step(&mut world, true, false);
+            assert_eq!(step(&mut world, true, true), baseline);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use bevy::ecs::{
+    change_detection::DetectChanges,
+    component::{Component, Tick},
+    entity::Entity,
+    system::SystemState,
+    world::World,
+};
+
+use super::atom::{AtomCell, AtomStore};
+
+type ComputeFn = Box<dyn Fn(&AtomStore) -> AtomCell + Send + Sync>;
+
+/// Component which holds the compute function of a derived atom, along with the list of
+/// source atoms that were read the last time it was computed.
+#[derive(Component)]
+pub(crate) struct DerivedAtom {
+    compute: Option<(ComputeFn, SystemState<AtomStore<'static, 'static>>)>,
+    equal: fn(&AtomCell, &AtomCell) -> bool,
+    sources: Vec<Entity>,
+    tick: Tick,
+}
+
+fn cells_equal<T: PartialEq + 'static>(a: &AtomCell, b: &AtomCell) -> bool {
+    a.0.downcast_ref::<T>() == b.0.downcast_ref::<T>()
+}
+
+impl DerivedAtom {
+    pub(crate) fn new<T: Clone + PartialEq + Sync + Send + 'static>(
+        world: &mut World,
+        compute: impl Fn(&AtomStore) -> T + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            compute: Some((
+                Box::new(move |store| AtomCell(Box::new(compute(store)))),
+                SystemState::new(world),
+            )),
+            equal: cells_equal::<T>,
+            sources: Vec::new(),
+            tick: world.change_tick(),
+        }
+    }
+
+    /// Run the compute function for the derived atom stored on entity `id`, and write the
+    /// result to its [`AtomCell`]. The cell is left untouched if the result is equal to the
+    /// previous value, so that readers of the atom are not notified.
+    pub(crate) fn recompute(world: &mut World, id: Entity) {
+        let Some(mut derived) = world.get_mut::<DerivedAtom>(id) else {
+            return;
+        };
+        let Some((compute, mut state)) = derived.compute.take() else {
+            panic!("Derived atom computed recursively");
+        };
+        let store = state.get_mut(world);
+        store.start_recording();
+        let cell = compute(&store);
+        let sources = store.stop_recording();
+        state.apply(world);
+
+        // Advance the tick so that writes made after this point are seen as changes.
+        let tick = world.increment_change_tick();
+        let mut entt = world.entity_mut(id);
+        let equal = entt.get::<DerivedAtom>().unwrap().equal;
+        if !entt
+            .get::<AtomCell>()
+            .is_some_and(|prev| equal(prev, &cell))
+        {
+            entt.insert(cell);
+        }
+        let mut derived = entt.get_mut::<DerivedAtom>().unwrap();
+        derived.compute = Some((compute, state));
+        derived.sources = sources;
+        derived.tick = tick;
+    }
+}
+
+/// Recompute any derived atoms whose sources have changed since they were last computed.
+pub(crate) fn update_derived_atoms(world: &mut World) {
+    let this_run = world.change_tick();
+    let mut q = world.query::<(Entity, &DerivedAtom)>();
+    let changed: Vec<Entity> = q
+        .iter(world)
+        .filter(|(_, derived)| {
+            derived.sources.iter().any(|source| {
+                world
+                    .get_entity(*source)
+                    .and_then(|ent| ent.get_ref::<AtomCell>())
+                    .map(|cell| cell.last_changed().is_newer_than(derived.tick, this_run))
+                    .unwrap_or(false)
+            })
+        })
+        .map(|(e, _)| e)
+        .collect();
+    for id in changed {
+        DerivedAtom::recompute(world, id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::{
+        plugin::render_views, AtomHandle, AtomMethods, BuildContext, Cx, TrackingContext, View,
+        ViewHandle,
+    };
+
+    #[test]
+    fn test_derived_atom() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+        let mut tracking = TrackingContext::default();
+        let mut cx = Cx::new(&(), &mut bc, &mut tracking);
+        let source = cx.create_atom_init(|| 2);
+        let derived = cx.create_derived(move |atoms| atoms.get(source) * 10);
+        assert_eq!(world.get_atom(derived), 20);
+
+        // No change to sources, no recompute.
+        world.increment_change_tick();
+        update_derived_atoms(&mut world);
+        assert_eq!(world.get_atom(derived), 20);
+
+        world.increment_change_tick();
+        world.set_atom(source, 5);
+        world.increment_change_tick();
+        update_derived_atoms(&mut world);
+        assert_eq!(world.get_atom(derived), 50);
+    }
+
+    #[derive(Resource, Default)]
+    struct Renders(usize);
+
+    fn is_even(mut cx: Cx<AtomHandle<i32>>) -> impl View {
+        let source = *cx.props;
+        let even = cx.create_derived(move |atoms| atoms.get(source) % 2 == 0);
+        cx.read_atom(even);
+        cx.bc.world.resource_mut::<Renders>().0 += 1;
+    }
+
+    #[test]
+    fn test_derived_unchanged() {
+        let mut world = World::new();
+        world.init_resource::<Renders>();
+        let source = world.create_atom::<i32>();
+        world.set_atom(source, 2);
+        world.spawn(ViewHandle::new(is_even, source));
+        render_views(&mut world);
+        assert_eq!(world.resource::<Renders>().0, 1);
+        world.clear_trackers();
+
+        // Source changed, but the derived value is the same: no re-render.
+        world.set_atom(source, 4);
+        render_views(&mut world);
+        assert_eq!(world.resource::<Renders>().0, 1);
+        world.clear_trackers();
+
+        world.set_atom(source, 5);
+        render_views(&mut world);
+        assert_eq!(world.resource::<Renders>().0, 2);
+    }
+}
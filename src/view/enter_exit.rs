@@ -0,0 +1,181 @@
+use bevy::prelude::*;
+
+use crate::{presenter_state::PresenterStateChanged, Cx, ElementClasses};
+
+/// Tracks an enter / exit transition. This is useful for widgets like dialog boxes and popup
+/// menus which have an opening and closing animation.
+#[derive(Default, Clone, PartialEq, Debug)]
+pub enum EnterExitState {
+    /// One-frame delay at start of entering.
+    EnterStart,
+
+    /// Opening animation.
+    Entering,
+
+    /// Fully open
+    Entered,
+
+    /// One frame delay at start of exiting
+    ExitStart,
+
+    /// Closing animation
+    Exiting,
+
+    /// Fully closed
+    #[default]
+    Exited,
+}
+
+impl EnterExitState {
+    /// Convert an Enter/Exit state into a class name.
+    pub fn as_class_name(&self) -> &'static str {
+        match self {
+            EnterExitState::EnterStart => "enter-start",
+            EnterExitState::Entering => "entering",
+            EnterExitState::Entered => "entered",
+            EnterExitState::ExitStart => "exit-start",
+            EnterExitState::Exiting => "exiting",
+            EnterExitState::Exited => "exited",
+        }
+    }
+}
+
+/// Component which holds the state of an enter / exit transition.
+#[derive(Component, Default)]
+pub struct EnterExit {
+    /// Whether the element should be open.
+    pub open: bool,
+    /// Duration of the opening and closing animations, in seconds.
+    pub delay: f32,
+    /// Current state of the transition.
+    pub state: EnterExitState,
+}
+
+/// Component which holds the elapsed time of the current enter / exit animation.
+#[derive(Component, Default)]
+pub struct EnterExitTimer {
+    /// Seconds since the animation started.
+    pub timer: f32,
+}
+
+/// Marks an item of a [`TransitionGroup`](crate::TransitionGroup). The item's class names
+/// follow the state of its transition, and the presenter which owns the group is re-rendered
+/// when the item has finished exiting, so that it can be razed.
+#[derive(Component)]
+pub(crate) struct TransitionItem {
+    pub(crate) owner: Entity,
+}
+
+/// Trait which adds `use_enter_exit` to [`Cx`].
+pub trait EnterExitApi {
+    /// Returns the state of an enter / exit transition which is driven by `open`, where
+    /// `delay` is the duration of the animations in seconds.
+    fn use_enter_exit(&mut self, open: bool, delay: f32) -> EnterExitState;
+}
+
+impl<'w, 'p, Props> EnterExitApi for Cx<'w, 'p, Props> {
+    fn use_enter_exit(&mut self, open: bool, delay: f32) -> EnterExitState {
+        self.use_effect(
+            |mut ve| {
+                match ve.get_mut::<EnterExit>() {
+                    Some(mut ee) => {
+                        ee.open = open;
+                    }
+                    None => {
+                        ve.insert((
+                            EnterExit {
+                                open,
+                                delay,
+                                ..default()
+                            },
+                            EnterExitTimer { ..default() },
+                        ));
+                    }
+                };
+            },
+            open,
+        );
+
+        self.use_view_component::<EnterExit>()
+            .unwrap()
+            .state
+            .clone()
+    }
+}
+
+pub(crate) fn enter_exit_state_machine(
+    mut query: Query<(
+        &mut EnterExit,
+        &mut EnterExitTimer,
+        Option<&TransitionItem>,
+        Option<&mut ElementClasses>,
+    )>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for (mut ee, mut tt, item, classes) in query.iter_mut() {
+        let prev_state = ee.state.clone();
+        match ee.state {
+            EnterExitState::EnterStart => {
+                if ee.open {
+                    ee.state = EnterExitState::Entering;
+                    tt.timer = 0.;
+                } else {
+                    ee.state = EnterExitState::ExitStart;
+                }
+            }
+            EnterExitState::Entering => {
+                if ee.open {
+                    tt.timer += time.delta_seconds();
+                    if tt.timer > ee.delay {
+                        ee.state = EnterExitState::Entered;
+                    }
+                } else {
+                    ee.state = EnterExitState::ExitStart;
+                }
+            }
+            EnterExitState::Entered => {
+                if !ee.open {
+                    ee.state = EnterExitState::ExitStart;
+                }
+            }
+            EnterExitState::ExitStart => {
+                if !ee.open {
+                    ee.state = EnterExitState::Exiting;
+                    tt.timer = 0.;
+                } else {
+                    ee.state = EnterExitState::EnterStart;
+                }
+            }
+            EnterExitState::Exiting => {
+                if ee.open {
+                    ee.state = EnterExitState::EnterStart;
+                } else {
+                    tt.timer += time.delta_seconds();
+                    if tt.timer > ee.delay {
+                        ee.state = EnterExitState::Exited;
+                    }
+                }
+            }
+            EnterExitState::Exited => {
+                if ee.open {
+                    ee.state = EnterExitState::EnterStart;
+                }
+            }
+        }
+
+        if let Some(item) = item {
+            if ee.state != prev_state {
+                if let Some(mut classes) = classes {
+                    classes.remove_class(prev_state.as_class_name());
+                    classes.add_class(ee.state.as_class_name());
+                }
+                if ee.state == EnterExitState::Exited {
+                    if let Some(mut owner) = commands.get_entity(item.owner) {
+                        owner.insert(PresenterStateChanged);
+                    }
+                }
+            }
+        }
+    }
+}
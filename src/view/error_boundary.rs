@@ -0,0 +1,250 @@
+use std::{any::Any, panic::resume_unwind};
+
+use bevy::{
+    ecs::{component::Component, entity::Entity, world::World},
+    hierarchy::Parent,
+};
+
+use crate::{AtomHandle, BuildContext, View};
+
+use crate::node_span::NodeSpan;
+
+use super::atom::DeferredAtomWrites;
+
+/// Component which marks a presenter as a boundary for panics in its child presenters,
+/// created by [`Cx::use_panic_boundary`](crate::Cx::use_panic_boundary).
+#[derive(Component)]
+pub(crate) struct PanicBoundary(pub(crate) AtomHandle<Option<String>>);
+
+/// Report a panic from the presenter `entity` to the nearest enclosing [`PanicBoundary`]. The
+/// panic message is written to the boundary's atom at the end of the current pass of presenter
+/// updates. If there is no boundary, the panic is resumed.
+pub(crate) fn report_panic(world: &mut World, entity: Entity, payload: Box<dyn Any + Send>) {
+    let mut next = world.get::<Parent>(entity).map(|parent| parent.get());
+    while let Some(ancestor) = next {
+        if let Some(boundary) = world.get::<PanicBoundary>(ancestor) {
+            let message = match payload.downcast_ref::<&str>() {
+                Some(msg) => msg.to_string(),
+                None => match payload.downcast_ref::<String>() {
+                    Some(msg) => msg.clone(),
+                    None => "Presenter panicked".to_string(),
+                },
+            };
+            let handle = boundary.0;
+            world
+                .get_resource_or_insert_with(DeferredAtomWrites::default)
+                .set(handle, Some(message));
+            return;
+        }
+        next = world.get::<Parent>(ancestor).map(|parent| parent.get());
+    }
+    resume_unwind(payload);
+}
+
+/// State for an [`ErrorBoundary`], recording which of its views is currently built.
+pub enum ErrorBoundaryState<Child, Fallback> {
+    /// No error has been reported, and the child view is built.
+    Child(Child),
+    /// An error has been reported, and the fallback view is built in place of the child.
+    Fallback(Fallback),
+}
+
+/// A view which renders its child, unless an error has been reported, in which case it renders
+/// a fallback view generated from the error instead. The child view is razed when an error
+/// occurs, and rebuilt once the error is cleared.
+///
+/// Errors are typically reported through an `AtomHandle<Option<E>>` created by the presenter
+/// that owns the boundary, and passed to nested presenters or event handlers which can write
+/// to it. The owning presenter reads the atom with [`Cx::read_atom`](crate::Cx::read_atom)
+/// and passes the result to the boundary, so that it re-renders when the error changes.
+///
+/// To also contain panics, create the atom with
+/// [`Cx::use_panic_boundary`](crate::Cx::use_panic_boundary). A panic in any child presenter
+/// is then caught, and the panic message is written to the atom before the next pass of
+/// presenter updates, so the fallback is rendered in the same update. Writing `None` to the
+/// atom retries the child. Note that only panics in child presenters are caught, not panics in
+/// the presenter which owns the boundary, or in views it builds directly.
+pub struct ErrorBoundary<E: Send, V: View, Fb: View, F: Fn(&E) -> Fb + Send> {
+    error: Option<E>,
+    child: V,
+    fallback: F,
+}
+
+impl<E: Send, V: View, Fb: View, F: Fn(&E) -> Fb + Send> ErrorBoundary<E, V, Fb, F> {
+    /// Construct a new ErrorBoundary. If `error` is `Some`, then the result of calling
+    /// `fallback` is rendered instead of `child`.
+    pub fn new(error: Option<E>, child: V, fallback: F) -> Self {
+        Self {
+            error,
+            child,
+            fallback,
+        }
+    }
+}
+
+impl<E: Send, V: View, Fb: View, F: Fn(&E) -> Fb + Send> View for ErrorBoundary<E, V, Fb, F> {
+    /// Union of child and fallback states.
+    type State = ErrorBoundaryState<V::State, (Fb, Fb::State)>;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        match state {
+            Self::State::Child(ref child_state) => self.child.nodes(bc, child_state),
+            Self::State::Fallback((ref fallback, ref fallback_state)) => {
+                fallback.nodes(bc, fallback_state)
+            }
+        }
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        match self.error {
+            Some(ref error) => {
+                let fallback = (self.fallback)(error);
+                let state = fallback.build(bc);
+                ErrorBoundaryState::Fallback((fallback, state))
+            }
+            None => ErrorBoundaryState::Child(self.child.build(bc)),
+        }
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        match (&self.error, &mut *state) {
+            (None, Self::State::Child(ref mut child_state)) => {
+                // Mutate state in place
+                self.child.update(bc, child_state)
+            }
+            (Some(error), Self::State::Fallback((ref mut fallback, ref mut fallback_state))) => {
+                // Error may have changed, update fallback in place
+                let next = (self.fallback)(error);
+                next.update(bc, fallback_state);
+                *fallback = next;
+            }
+            _ => {
+                // Despawn old state and construct new state
+                self.raze(bc.world, state);
+                bc.mark_changed_shape();
+                *state = self.build(bc);
+            }
+        }
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        match state {
+            Self::State::Child(ref mut child_state) => self.child.assemble(bc, child_state),
+            Self::State::Fallback((ref fallback, ref mut fallback_state)) => {
+                fallback.assemble(bc, fallback_state)
+            }
+        }
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        match state {
+            Self::State::Child(ref mut child_state) => self.child.raze(world, child_state),
+            Self::State::Fallback((ref fallback, ref mut fallback_state)) => {
+                fallback.raze(world, fallback_state)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::{
+        plugin::render_views, test_utils::texts, AtomHandle, AtomMethods, Cx, Element, PresenterFn,
+        TrackingContext, ViewHandle,
+    };
+
+    fn render(
+        bc: &mut BuildContext,
+        error: AtomHandle<Option<String>>,
+        state: Option<&mut ErrorBoundaryState<Entity, (String, Entity)>>,
+    ) -> Option<ErrorBoundaryState<Entity, (String, Entity)>> {
+        let mut tracking = TrackingContext::default();
+        let cx = Cx::new(&(), bc, &mut tracking);
+        let view = ErrorBoundary::new(cx.read_atom(error), Element::new(), |err: &String| {
+            format!("Error: {}", err)
+        });
+        match state {
+            Some(state) => {
+                view.update(bc, state);
+                None
+            }
+            None => Some(view.build(bc)),
+        }
+    }
+
+    #[test]
+    fn test_error_boundary() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let error = world.create_atom::<Option<String>>();
+        world.set_atom(error, None);
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let mut state = render(&mut bc, error, None).unwrap();
+        let ErrorBoundaryState::Child(child) = state else {
+            panic!("Expected child to be rendered");
+        };
+
+        // Report an error: fallback replaces the child.
+        bc.world.set_atom(error, Some("failed".to_string()));
+        render(&mut bc, error, Some(&mut state));
+        let ErrorBoundaryState::Fallback((_, fallback)) = state else {
+            panic!("Expected fallback to be rendered");
+        };
+        assert!(bc.world.get_entity(child).is_none());
+        assert_eq!(
+            bc.world.get::<Text>(fallback).unwrap().sections[0].value,
+            "Error: failed"
+        );
+
+        // Clear the error: child is rebuilt.
+        bc.world.set_atom(error, None);
+        render(&mut bc, error, Some(&mut state));
+        assert!(matches!(state, ErrorBoundaryState::Child(_)));
+        assert!(bc.world.get_entity(fallback).is_none());
+    }
+
+    #[derive(Resource, Default)]
+    struct ShouldPanic(bool);
+
+    #[derive(Resource, Default)]
+    struct Boundary(Option<AtomHandle<Option<String>>>);
+
+    fn boundary(mut cx: Cx) -> impl View {
+        let error = cx.use_panic_boundary();
+        cx.bc.world.resource_mut::<Boundary>().0 = Some(error);
+        ErrorBoundary::new(cx.read_atom(error), faulty.bind(()), |err: &String| {
+            format!("Error: {}", err)
+        })
+    }
+
+    fn faulty(cx: Cx) -> impl View {
+        if cx.use_resource::<ShouldPanic>().0 {
+            panic!("boom");
+        }
+        "ok"
+    }
+
+    #[test]
+    fn test_panic_boundary() {
+        let mut world = World::new();
+        world.insert_resource(ShouldPanic(true));
+        world.init_resource::<Boundary>();
+        world.spawn(ViewHandle::new(boundary, ()));
+
+        // The panic is caught, and the fallback is rendered in the same update.
+        render_views(&mut world);
+        assert_eq!(texts(&mut world), vec!["Error: boom"]);
+        world.clear_trackers();
+
+        // Clearing the error retries the child.
+        world.resource_mut::<ShouldPanic>().0 = false;
+        let error = world.resource::<Boundary>().0.unwrap();
+        world.set_atom(error, None);
+        render_views(&mut world);
+        assert_eq!(texts(&mut world), vec!["ok"]);
+    }
+}
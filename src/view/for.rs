@@ -1,9 +1,23 @@
-use super::{for_index::ForIndex, for_keyed::ForKeyed, View};
+use std::hash::Hash;
+
+use super::{
+    for_each::ForEach,
+    for_index::ForIndex,
+    for_keyed::ForKeyed,
+    for_separated::ForSeparated,
+    list_state::{ForObservable, ListState},
+    r#if::If,
+    View,
+};
 
 /// A namespace that contains constructor functions for various kinds of for-loops:
 /// * `For::each()`
+/// * `For::each_always()`
+/// * `For::each_separated()`
 /// * `For::keyed()`
+/// * `For::keyed_or()`
 /// * `For::index()`
+/// * `For::observable()`
 pub struct For;
 
 impl For {
@@ -24,10 +38,10 @@ impl For {
     /// Construct an keyed for loop for an array of items. There are two callbacks, one which
     /// produces a unique key for each array item, and one which produces a child view for each
     /// array item. During rebuilds, the list of child views may be re-ordered based on a
-    /// comparison of the generated keys.
+    /// comparison of the generated keys, which must be hashable.
     pub fn keyed<
         Item: Send + Clone,
-        Key: Send + PartialEq,
+        Key: Send + Eq + Hash,
         V: View,
         K: Fn(&Item) -> Key + Send + Clone,
         F: Fn(&Item) -> V + Send + Clone,
@@ -42,10 +56,38 @@ impl For {
         ForKeyed::new(items, keyof, each)
     }
 
+    /// Construct a keyed for loop, as with [`For::keyed`], which renders `fallback` instead
+    /// when there are no items.
+    pub fn keyed_or<
+        Item: Send + Clone,
+        Key: Send + Eq + Hash,
+        V: View,
+        K: Fn(&Item) -> Key + Send + Clone,
+        F: Fn(&Item) -> V + Send + Clone,
+        E: View,
+    >(
+        items: &[Item],
+        keyof: K,
+        each: F,
+        fallback: E,
+    ) -> impl View
+    where
+        V::State: Clone,
+    {
+        If::new(
+            !items.is_empty(),
+            ForKeyed::new(items, keyof, each),
+            fallback,
+        )
+    }
+
     /// Construct an unkeyed for loop for an array of items. The callback is called once for each
     /// array element; its argument is the item, which must be equals-comparable, and it's result
     /// is a View. During rebuild, the list of child views may be re-ordered based on a comparison
-    /// of the items from the previous build.
+    /// of the items from the previous build. If the items are equal to the previous items, the
+    /// child views are not updated at all, even if the callback would produce a different
+    /// result; use [`For::each_always`] if the child views depend on anything other than the
+    /// item.
     pub fn each<Item: Send + Clone + PartialEq, V: View, F: Fn(&Item) -> V + Send + Clone>(
         items: &[Item],
         each: F,
@@ -53,6 +95,55 @@ impl For {
     where
         V::State: Clone,
     {
-        ForKeyed::new(items, |item| item.clone(), each)
+        ForEach::new(items, each)
+    }
+
+    /// Construct an unkeyed for loop for an array of items, which updates every child view on
+    /// each rebuild. Unlike [`For::each`], the items don't need to be equals-comparable; child
+    /// views are matched with the previous build by array index, as with [`For::index`].
+    pub fn each_always<Item: Send + Clone, V: View, F: Fn(&Item) -> V + Send + Clone>(
+        items: &[Item],
+        each: F,
+    ) -> impl View
+    where
+        V::State: Clone,
+    {
+        ForIndex::new(items, move |item, _| each(item))
+    }
+
+    /// Construct an unkeyed for loop, as with [`For::each`], which inserts a view produced by
+    /// `separator` between each pair of adjacent items. Items are matched with the previous
+    /// build by comparison, while separators are matched by position.
+    pub fn each_separated<
+        Item: Send + Clone + PartialEq,
+        V: View,
+        F: Fn(&Item) -> V + Send + Clone,
+        S: View,
+        SF: Fn() -> S + Send,
+    >(
+        items: &[Item],
+        each: F,
+        separator: SF,
+    ) -> impl View
+    where
+        V::State: Clone,
+    {
+        ForSeparated::new(items, each, separator)
+    }
+
+    /// Construct a for loop over a [`ListState`]. The callback is called once for each item,
+    /// and its result is a View. During rebuild, the mutations recorded by the `ListState`
+    /// since the previous build are applied directly to the list of child views, so that only
+    /// inserted and removed items are built or razed. If the changelog is not available, for
+    /// example because the list was replaced, this falls back to the same comparison of items
+    /// as [`For::each`].
+    pub fn observable<Item: Send + Clone + PartialEq, V: View, F: Fn(&Item) -> V + Send + Clone>(
+        list: &ListState<Item>,
+        each: F,
+    ) -> impl View
+    where
+        V::State: Clone,
+    {
+        ForObservable::new(list, each)
     }
 }
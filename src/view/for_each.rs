@@ -0,0 +1,134 @@
+use bevy::ecs::world::World;
+
+use crate::{BuildContext, View};
+
+use crate::node_span::NodeSpan;
+
+use super::for_keyed::{ForKeyed, KeyedListItem};
+
+/// A keyed for loop which uses each item as its own key.
+type SelfKeyed<Item, V, F> = ForKeyed<Item, Item, V, fn(&Item) -> Item, F>;
+
+/// A for loop whose items are also its keys. This behaves like a [`ForKeyed`], except that
+/// when the items are equal to the items of the previous update, the child views are not
+/// updated at all. See [`For::each`](crate::For::each).
+#[doc(hidden)]
+pub struct ForEach<Item: Send + Clone + PartialEq, V: View, F: Fn(&Item) -> V + Send + Clone>
+where
+    V::State: Clone,
+{
+    inner: SelfKeyed<Item, V, F>,
+}
+
+impl<Item: Send + Clone + PartialEq, V: View, F: Fn(&Item) -> V + Send + Clone> ForEach<Item, V, F>
+where
+    V::State: Clone,
+{
+    pub fn new(items: &[Item], each: F) -> Self {
+        Self {
+            inner: ForKeyed::new_unhashed(items, Item::clone, each),
+        }
+    }
+}
+
+impl<Item: Send + Clone + PartialEq, V: View, F: Fn(&Item) -> V + Send + Clone> View
+    for ForEach<Item, V, F>
+where
+    V::State: Clone,
+{
+    type State = Vec<KeyedListItem<Item, V>>;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        self.inner.nodes(bc, state)
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        self.inner.build(bc)
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        // The keys of the previous state are a copy of the previous items, so if they are
+        // unchanged there is nothing to do.
+        if state.iter().map(|item| &item.key).eq(self.inner.items()) {
+            return;
+        }
+        self.inner.update(bc, state);
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        self.inner.assemble(bc, state)
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        self.inner.raze(world, state);
+    }
+}
+
+impl<Item: Send + Clone + PartialEq, V: View, F: Fn(&Item) -> V + Send + Clone> Clone
+    for ForEach<Item, V, F>
+where
+    V::State: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Updates(usize);
+
+    /// A view which counts how many times it has been updated.
+    #[derive(Clone)]
+    struct Counted;
+
+    impl View for Counted {
+        type State = ();
+
+        fn nodes(&self, _bc: &BuildContext, _state: &Self::State) -> NodeSpan {
+            NodeSpan::Empty
+        }
+
+        fn build(&self, _bc: &mut BuildContext) -> Self::State {}
+
+        fn update(&self, bc: &mut BuildContext, _state: &mut Self::State) {
+            bc.world.resource_mut::<Updates>().0 += 1;
+        }
+
+        fn assemble(&self, _bc: &mut BuildContext, _state: &mut Self::State) -> NodeSpan {
+            NodeSpan::Empty
+        }
+
+        fn raze(&self, _world: &mut World, _state: &mut Self::State) {}
+    }
+
+    #[test]
+    fn test_unchanged_items() {
+        let mut world = World::new();
+        world.init_resource::<Updates>();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let mut state = ForEach::new(&[1, 2, 3], |_| Counted).build(&mut bc);
+        assert_eq!(bc.world.resource::<Updates>().0, 0);
+
+        // Identical items don't update any children.
+        ForEach::new(&[1, 2, 3], |_| Counted).update(&mut bc, &mut state);
+        assert_eq!(bc.world.resource::<Updates>().0, 0);
+
+        // Changed items update the children which were kept.
+        ForEach::new(&[1, 3], |_| Counted).update(&mut bc, &mut state);
+        assert_eq!(bc.world.resource::<Updates>().0, 2);
+        assert_eq!(state.len(), 2);
+
+        ForEach::new(&[1, 3], |_| Counted).update(&mut bc, &mut state);
+        assert_eq!(bc.world.resource::<Updates>().0, 2);
+    }
+}
@@ -74,6 +74,7 @@ where
     fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
         let next_len = self.items.len();
         let mut prev_len = state.len();
+        let changed_len = next_len != prev_len;
         // let mut child_spans: Vec<NodeSpan> = Vec::with_capacity(next_len);
         // child_spans.resize(next_len, NodeSpan::Empty);
 
@@ -115,6 +116,10 @@ where
             }
             state.pop();
         }
+
+        if changed_len {
+            bc.mark_changed_shape();
+        }
     }
 
     fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
@@ -148,3 +153,80 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::presenter_state::PresenterGraphChanged;
+
+    fn entities(
+        bc: &BuildContext,
+        view: &impl View<State = Vec<IndexedListItem<String>>>,
+        state: &Vec<IndexedListItem<String>>,
+    ) -> Vec<Entity> {
+        let mut out = Vec::new();
+        view.nodes(bc, state).flatten(&mut out);
+        out
+    }
+
+    fn for_index(
+        items: &[i32],
+    ) -> ForIndex<i32, String, impl Fn(&i32, usize) -> String + Send + Clone> {
+        ForIndex::new(items, |item, index| format!("{}:{}", index, item))
+    }
+
+    #[test]
+    fn test_grow_shrink() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let view = for_index(&[1, 2]);
+        let mut state = view.build(&mut bc);
+        let initial = entities(&bc, &view, &state);
+        assert_eq!(initial.len(), 2);
+
+        // Growth: existing entities are retained, new ones appended.
+        let view = for_index(&[1, 2, 3, 4]);
+        view.update(&mut bc, &mut state);
+        let grown = entities(&bc, &view, &state);
+        assert_eq!(grown.len(), 4);
+        assert_eq!(grown[0..2], initial[..]);
+        assert!(bc.world.entity(entity).contains::<PresenterGraphChanged>());
+        bc.world
+            .entity_mut(entity)
+            .remove::<PresenterGraphChanged>();
+
+        // Shrink: tail entities are despawned.
+        let view = for_index(&[1]);
+        view.update(&mut bc, &mut state);
+        let shrunk = entities(&bc, &view, &state);
+        assert_eq!(shrunk, vec![initial[0]]);
+        for e in &grown[1..] {
+            assert!(bc.world.get_entity(*e).is_none());
+        }
+        assert!(bc.world.entity(entity).contains::<PresenterGraphChanged>());
+    }
+
+    #[test]
+    fn test_update_in_place() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let view = for_index(&[1, 2]);
+        let mut state = view.build(&mut bc);
+        let initial = entities(&bc, &view, &state);
+
+        let view = for_index(&[1, 5]);
+        view.update(&mut bc, &mut state);
+        assert_eq!(entities(&bc, &view, &state), initial);
+        assert_eq!(
+            bc.world.get::<Text>(initial[1]).unwrap().sections[0].value,
+            "1:5"
+        );
+        assert!(!bc.world.entity(entity).contains::<PresenterGraphChanged>());
+    }
+}
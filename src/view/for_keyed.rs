@@ -1,26 +1,26 @@
-use std::{marker::PhantomData, ops::Range};
+use std::{hash::Hash, marker::PhantomData, ops::Range};
 
-use bevy::ecs::world::World;
+use bevy::{ecs::world::World, utils::HashMap};
 
 use crate::{view::lcs::lcs, BuildContext, View};
 
 use crate::node_span::NodeSpan;
 
 pub struct KeyedListItem<Key: Send + PartialEq, V: View> {
-    view: Option<V>,
-    state: Option<V::State>,
-    key: Key,
+    pub(crate) view: Option<V>,
+    pub(crate) state: Option<V::State>,
+    pub(crate) key: Key,
 }
 
 impl<Key: Send + PartialEq, V: View> KeyedListItem<Key, V> {
-    fn nodes(&self, bc: &BuildContext) -> NodeSpan {
+    pub(crate) fn nodes(&self, bc: &BuildContext) -> NodeSpan {
         self.view
             .as_ref()
             .unwrap()
             .nodes(bc, self.state.as_ref().unwrap())
     }
 
-    fn assemble(&mut self, bc: &mut BuildContext) -> NodeSpan {
+    pub(crate) fn assemble(&mut self, bc: &mut BuildContext) -> NodeSpan {
         self.view
             .as_ref()
             .unwrap()
@@ -28,6 +28,51 @@ impl<Key: Send + PartialEq, V: View> KeyedListItem<Key, V> {
     }
 }
 
+/// Function which pairs up previous and next items that have the same key but were not matched
+/// by the LCS pass, returning `(prev_index, next_index)` pairs. Only previous items which still
+/// have their state, and next items which don't yet have one, are considered.
+type MatchMoved<Key, V> =
+    fn(&[KeyedListItem<Key, V>], &[KeyedListItem<Key, V>]) -> Vec<(usize, usize)>;
+
+/// Match moved items by looking up their keys in a hash map.
+fn match_moved_hashed<Key: Send + Eq + Hash, V: View>(
+    prev: &[KeyedListItem<Key, V>],
+    next: &[KeyedListItem<Key, V>],
+) -> Vec<(usize, usize)> {
+    let mut unmatched: HashMap<&Key, usize> = next
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| item.state.is_none())
+        .map(|(j, item)| (&item.key, j))
+        .collect();
+    prev.iter()
+        .enumerate()
+        .filter(|(_, item)| item.state.is_some())
+        .filter_map(|(i, item)| unmatched.remove(&item.key).map(|j| (i, j)))
+        .collect()
+}
+
+/// Match moved items by comparing every pair of keys, for keys which can't be hashed.
+fn match_moved_linear<Key: Send + PartialEq, V: View>(
+    prev: &[KeyedListItem<Key, V>],
+    next: &[KeyedListItem<Key, V>],
+) -> Vec<(usize, usize)> {
+    let mut claimed = vec![false; prev.len()];
+    let mut pairs = Vec::new();
+    for (j, item) in next.iter().enumerate() {
+        if item.state.is_some() {
+            continue;
+        }
+        if let Some(i) = (0..prev.len())
+            .find(|i| !claimed[*i] && prev[*i].state.is_some() && prev[*i].key == item.key)
+        {
+            claimed[i] = true;
+            pairs.push((i, j));
+        }
+    }
+    pairs
+}
+
 #[doc(hidden)]
 #[allow(clippy::needless_range_loop)]
 pub struct ForKeyed<
@@ -42,6 +87,7 @@ pub struct ForKeyed<
     items: Vec<Item>,
     keyof: K,
     each: F,
+    match_moved: MatchMoved<Key, V>,
     key: PhantomData<Key>,
 }
 
@@ -56,21 +102,44 @@ impl<
 where
     V::State: Clone,
 {
-    pub fn new(items: &[Item], keyof: K, each: F) -> Self {
+    pub fn new(items: &[Item], keyof: K, each: F) -> Self
+    where
+        Key: Eq + Hash,
+    {
         Self {
             items: Vec::from(items),
             each,
             keyof,
+            match_moved: match_moved_hashed,
             key: PhantomData::<Key> {},
         }
     }
 
+    /// Construct a keyed loop whose keys can only be compared for equality. Moved items are
+    /// found by a linear search, so this is slower than [`ForKeyed::new`] for large lists.
+    pub(crate) fn new_unhashed(items: &[Item], keyof: K, each: F) -> Self {
+        Self {
+            items: Vec::from(items),
+            each,
+            keyof,
+            match_moved: match_moved_linear,
+            key: PhantomData::<Key> {},
+        }
+    }
+
+    /// The items of this loop.
+    pub(crate) fn items(&self) -> &[Item] {
+        &self.items
+    }
+
     /// Uses the sequence of key values to match the previous array items with the updated
-    /// array items. Matching items are patched, other items are inserted or deleted.
+    /// array items. Matching items are patched; items which are not part of a common
+    /// subsequence are left untouched, to be handled by the caller.
     ///
     /// # Arguments
     ///
     /// * `bc` - [`BuildContext`] used to build individual elements.
+    /// * `items` - The items corresponding to `next_state`.
     /// * `prev_state` - Array of view state elements from previous update.
     /// * `prev_range` - The range of elements we are comparing in `prev_state`.
     /// * `next_state` - Array of view state elements to be built.
@@ -78,6 +147,7 @@ where
     fn build_recursive(
         &self,
         bc: &mut BuildContext,
+        items: &[Item],
         prev_state: &mut [KeyedListItem<Key, V>],
         prev_range: Range<usize>,
         next_state: &mut [KeyedListItem<Key, V>],
@@ -93,20 +163,6 @@ where
 
         // If there was nothing in common
         if lcs_length == 0 {
-            // Raze old elements
-            for i in prev_range {
-                let prev = &mut prev_state[i];
-                if let Some(ref view) = prev.view {
-                    view.raze(bc.world, prev.state.as_mut().unwrap());
-                }
-            }
-            // Build new elements
-            for i in next_range {
-                let next = &mut next_state[i];
-                let view = (self.each)(&self.items[i]);
-                next.state = Some(view.build(bc));
-                next.view = Some(view);
-            }
             return;
         }
 
@@ -115,33 +171,16 @@ where
         let next_start = next_start + next_range.start;
 
         // Stuff that precedes the LCS.
-        if prev_start > prev_range.start {
-            if next_start > next_range.start {
-                // Both prev and next have entries before lcs, so recurse
-                self.build_recursive(
-                    bc,
-                    prev_state,
-                    prev_range.start..prev_start,
-                    next_state,
-                    next_range.start..next_start,
-                )
-            } else {
-                // Deletions
-                for i in prev_range.start..prev_start {
-                    let prev = &mut prev_state[i];
-                    if let Some(ref view) = prev.view {
-                        view.raze(bc.world, prev.state.as_mut().unwrap());
-                    }
-                }
-            }
-        } else if next_start > next_range.start {
-            // Insertions
-            for i in next_range.start..next_start {
-                let next = &mut next_state[i];
-                let view = (self.each)(&self.items[i]);
-                next.state = Some(view.build(bc));
-                next.view = Some(view);
-            }
+        if prev_start > prev_range.start && next_start > next_range.start {
+            // Both prev and next have entries before lcs, so recurse
+            self.build_recursive(
+                bc,
+                items,
+                prev_state,
+                prev_range.start..prev_start,
+                next_state,
+                next_range.start..next_start,
+            )
         }
 
         // For items that match, overwrite.
@@ -150,7 +189,7 @@ where
             let next = &mut next_state[next_start + i];
             // Take the old state, update with new View for this element.
             next.state = prev.state.take();
-            let v = (self.each)(&self.items[next_start + i]);
+            let v = (self.each)(&items[next_start + i]);
             v.update(bc, next.state.as_mut().unwrap());
             next.view = Some(v);
         }
@@ -158,34 +197,91 @@ where
         // Stuff that follows the LCS.
         let prev_end = prev_start + lcs_length;
         let next_end = next_start + lcs_length;
-        if prev_end < prev_range.end {
-            if next_end < next_range.end {
-                // Both prev and next have entries after lcs, so recurse
-                self.build_recursive(
-                    bc,
-                    prev_state,
-                    prev_end..prev_range.end,
-                    next_state,
-                    next_end..next_range.end,
-                )
-            } else {
-                // Deletions
-                for i in prev_end..prev_range.end {
-                    let prev = &mut prev_state[i];
-                    if let Some(ref view) = prev.view {
-                        view.raze(bc.world, prev.state.as_mut().unwrap());
-                    }
+        if prev_end < prev_range.end && next_end < next_range.end {
+            // Both prev and next have entries after lcs, so recurse
+            self.build_recursive(
+                bc,
+                items,
+                prev_state,
+                prev_end..prev_range.end,
+                next_state,
+                next_end..next_range.end,
+            )
+        }
+    }
+
+    /// Update a single item whose key is unchanged.
+    fn update_item(&self, bc: &mut BuildContext, item: &Item, entry: &mut KeyedListItem<Key, V>) {
+        let view = (self.each)(item);
+        view.update(bc, entry.state.as_mut().unwrap());
+        entry.view = Some(view);
+    }
+
+    /// Match a range of previous items with a range of new items, returning the state for the
+    /// new items. Any previous items which are not matched are razed.
+    fn update_range(
+        &self,
+        bc: &mut BuildContext,
+        items: &[Item],
+        state: &mut [KeyedListItem<Key, V>],
+    ) -> Vec<KeyedListItem<Key, V>> {
+        let next_len = items.len();
+        let mut next_state: Vec<KeyedListItem<Key, V>> = Vec::with_capacity(next_len);
+        let prev_len = state.len();
+
+        // Initialize output state array; fill in keys.
+        for j in 0..next_len {
+            next_state.push({
+                KeyedListItem {
+                    view: None,
+                    state: None,
+                    key: (self.keyof)(&items[j]),
                 }
-            }
-        } else if next_end < next_range.end {
-            // Insertions
-            for i in next_end..next_range.end {
-                let next = &mut next_state[i];
-                let view = (self.each)(&self.items[i]);
+            });
+        }
+
+        self.build_recursive(bc, items, state, 0..prev_len, &mut next_state, 0..next_len);
+
+        // Items that were not part of a common subsequence may have been moved rather than
+        // inserted; look for an unclaimed previous item with the same key and transfer its
+        // state rather than razing and rebuilding it.
+        let mut changed_shape = false;
+        for (i, j) in (self.match_moved)(state, &next_state) {
+            let view = (self.each)(&items[j]);
+            let next = &mut next_state[j];
+            next.state = state[i].state.take();
+            view.update(bc, next.state.as_mut().unwrap());
+            next.view = Some(view);
+            changed_shape = true;
+        }
+
+        // Build the items which are new.
+        for j in 0..next_len {
+            let next = &mut next_state[j];
+            if next.state.is_none() {
+                let view = (self.each)(&items[j]);
                 next.state = Some(view.build(bc));
                 next.view = Some(view);
+                changed_shape = true;
+            }
+        }
+
+        // Raze any previous items that were not claimed.
+        for prev in state.iter_mut() {
+            if let (Some(ref view), Some(ref mut prev_state)) = (&prev.view, &mut prev.state) {
+                view.raze(bc.world, prev_state);
+                changed_shape = true;
             }
         }
+
+        if changed_shape {
+            bc.mark_changed_shape();
+        }
+
+        for j in 0..next_len {
+            assert!(next_state[j].state.is_some(), "Empty state: {}", j);
+        }
+        next_state
     }
 }
 
@@ -203,8 +299,7 @@ where
     type State = Vec<KeyedListItem<Key, V>>;
 
     fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
-        let child_spans: Vec<NodeSpan> = state.iter().map(|item| item.nodes(bc)).collect();
-        NodeSpan::Fragment(child_spans.into_boxed_slice())
+        NodeSpan::Fragment(state.iter().map(|item| item.nodes(bc)).collect())
     }
 
     fn build(&self, bc: &mut BuildContext) -> Self::State {
@@ -229,30 +324,40 @@ where
 
     fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
         let next_len = self.items.len();
-        let mut next_state: Self::State = Vec::with_capacity(next_len);
         let prev_len = state.len();
 
-        // Initialize output state array; fill in keys.
-        for j in 0..next_len {
-            next_state.push({
-                KeyedListItem {
-                    view: None,
-                    state: None,
-                    key: (self.keyof)(&self.items[j]),
-                }
-            });
+        // Items at the start and end of the list whose keys haven't changed are updated in
+        // place; only the items in between need to be compared.
+        let prefix = state
+            .iter()
+            .zip(self.items.iter())
+            .take_while(|(prev, item)| prev.key == (self.keyof)(item))
+            .count();
+        let suffix = state[prefix..]
+            .iter()
+            .rev()
+            .zip(self.items[prefix..].iter().rev())
+            .take_while(|(prev, item)| prev.key == (self.keyof)(item))
+            .count();
+
+        for j in 0..prefix {
+            self.update_item(bc, &self.items[j], &mut state[j]);
         }
 
-        self.build_recursive(bc, state, 0..prev_len, &mut next_state, 0..next_len);
-        for j in 0..next_len {
-            assert!(next_state[j].state.is_some(), "Empty state: {}", j);
+        if prefix + suffix < prev_len || prefix + suffix < next_len {
+            let mut prev_state: Vec<_> = state.drain(prefix..prev_len - suffix).collect();
+            let next_state =
+                self.update_range(bc, &self.items[prefix..next_len - suffix], &mut prev_state);
+            state.splice(prefix..prefix, next_state);
+        }
+
+        for j in next_len - suffix..next_len {
+            self.update_item(bc, &self.items[j], &mut state[j]);
         }
-        std::mem::swap(state, &mut next_state);
     }
 
     fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
-        let child_spans: Vec<NodeSpan> = state.iter_mut().map(|item| item.assemble(bc)).collect();
-        NodeSpan::Fragment(child_spans.into_boxed_slice())
+        NodeSpan::Fragment(state.iter_mut().map(|item| item.assemble(bc)).collect())
     }
 
     fn raze(&self, world: &mut World, state: &mut Self::State) {
@@ -279,6 +384,7 @@ where
             items: self.items.clone(),
             keyof: self.keyof.clone(),
             each: self.each.clone(),
+            match_moved: self.match_moved,
             key: self.key,
         }
     }
@@ -297,6 +403,7 @@ mod tests {
         let mut bc = BuildContext {
             world: &mut world,
             entity,
+            parent: None,
         };
 
         // Initial render
@@ -369,4 +476,43 @@ mod tests {
         assert_eq!(state[2].key, 3);
         assert_eq!(state[0].state, e1, "Should be same entity");
     }
+
+    #[test]
+    fn test_reorder() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext {
+            world: &mut world,
+            entity,
+            parent: None,
+        };
+
+        let view = ForKeyed::new(&[1, 2, 3, 4, 5], |item| *item, |item| format!("{}", item));
+        let mut state = view.build(&mut bc);
+        let entities: Vec<_> = state.iter().map(|item| item.state.unwrap()).collect();
+
+        // Reverse the list; every entity should be preserved.
+        let view = ForKeyed::new(&[5, 4, 3, 2, 1], |item| *item, |item| format!("{}", item));
+        view.update(&mut bc, &mut state);
+        assert_eq!(state.len(), 5);
+        for (i, item) in state.iter().enumerate() {
+            assert_eq!(item.key, 5 - i as i32);
+            assert_eq!(
+                item.state.unwrap(),
+                entities[4 - i],
+                "Should be same entity"
+            );
+        }
+
+        // Moved and new items mixed together.
+        let view = ForKeyed::new(&[2, 6, 5, 1], |item| *item, |item| format!("{}", item));
+        view.update(&mut bc, &mut state);
+        assert_eq!(state.len(), 4);
+        assert_eq!(state[0].state.unwrap(), entities[1]);
+        assert_eq!(state[1].key, 6);
+        assert_eq!(state[2].state.unwrap(), entities[4]);
+        assert_eq!(state[3].state.unwrap(), entities[0]);
+        assert!(bc.world.get_entity(entities[2]).is_none());
+        assert!(bc.world.get_entity(entities[3]).is_none());
+    }
 }
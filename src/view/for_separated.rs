@@ -0,0 +1,179 @@
+use bevy::ecs::world::World;
+
+use crate::{BuildContext, View};
+
+use crate::node_span::NodeSpan;
+
+use super::{for_each::ForEach, for_keyed::KeyedListItem};
+
+/// State for a [`ForSeparated`].
+pub struct SeparatedListState<Item: Send + PartialEq, V: View, S: View> {
+    items: Vec<KeyedListItem<Item, V>>,
+    separators: Vec<(S, S::State)>,
+}
+
+/// A for loop which inserts a separator view between each pair of items. The items are
+/// matched with the previous update in the same way as [`For::each`](crate::For::each), while
+/// the separators are matched by position. See [`For::each_separated`](crate::For::each_separated).
+#[doc(hidden)]
+pub struct ForSeparated<
+    Item: Send + Clone + PartialEq,
+    V: View,
+    F: Fn(&Item) -> V + Send + Clone,
+    S: View,
+    SF: Fn() -> S + Send,
+> where
+    V::State: Clone,
+{
+    inner: ForEach<Item, V, F>,
+    item_count: usize,
+    separator: SF,
+}
+
+impl<
+        Item: Send + Clone + PartialEq,
+        V: View,
+        F: Fn(&Item) -> V + Send + Clone,
+        S: View,
+        SF: Fn() -> S + Send,
+    > ForSeparated<Item, V, F, S, SF>
+where
+    V::State: Clone,
+{
+    pub fn new(items: &[Item], each: F, separator: SF) -> Self {
+        Self {
+            inner: ForEach::new(items, each),
+            item_count: items.len(),
+            separator,
+        }
+    }
+
+    /// The number of separators needed for the current items.
+    fn separator_count(&self) -> usize {
+        self.item_count.saturating_sub(1)
+    }
+}
+
+impl<
+        Item: Send + Clone + PartialEq,
+        V: View,
+        F: Fn(&Item) -> V + Send + Clone,
+        S: View,
+        SF: Fn() -> S + Send,
+    > View for ForSeparated<Item, V, F, S, SF>
+where
+    V::State: Clone,
+{
+    type State = SeparatedListState<Item, V, S>;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        let mut spans: Vec<NodeSpan> = Vec::with_capacity(state.items.len() * 2);
+        for (i, item) in state.items.iter().enumerate() {
+            if i > 0 {
+                let (view, separator_state) = &state.separators[i - 1];
+                spans.push(view.nodes(bc, separator_state));
+            }
+            spans.push(item.nodes(bc));
+        }
+        NodeSpan::Fragment(spans.into_boxed_slice())
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let items = self.inner.build(bc);
+        let separators = (0..self.separator_count())
+            .map(|_| {
+                let view = (self.separator)();
+                let state = view.build(bc);
+                (view, state)
+            })
+            .collect();
+        SeparatedListState { items, separators }
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        self.inner.update(bc, &mut state.items);
+
+        // Separators don't depend on the items, so they are simply updated in place, with
+        // any extra separators being added or removed at the end.
+        let count = self.separator_count();
+        let mut changed_shape = false;
+        while state.separators.len() > count {
+            let (view, mut separator_state) = state.separators.pop().unwrap();
+            view.raze(bc.world, &mut separator_state);
+            changed_shape = true;
+        }
+        for (view, separator_state) in state.separators.iter_mut() {
+            let next = (self.separator)();
+            next.update(bc, separator_state);
+            *view = next;
+        }
+        while state.separators.len() < count {
+            let view = (self.separator)();
+            let separator_state = view.build(bc);
+            state.separators.push((view, separator_state));
+            changed_shape = true;
+        }
+        if changed_shape {
+            bc.mark_changed_shape();
+        }
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        let mut spans: Vec<NodeSpan> = Vec::with_capacity(state.items.len() * 2);
+        for (i, item) in state.items.iter_mut().enumerate() {
+            if i > 0 {
+                let (view, separator_state) = &mut state.separators[i - 1];
+                spans.push(view.assemble(bc, separator_state));
+            }
+            spans.push(item.assemble(bc));
+        }
+        NodeSpan::Fragment(spans.into_boxed_slice())
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        self.inner.raze(world, &mut state.items);
+        for (view, separator_state) in state.separators.iter_mut() {
+            view.raze(world, separator_state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+
+    fn texts(world: &World, nodes: &NodeSpan) -> Vec<String> {
+        nodes
+            .iter()
+            .map(|entity| world.get::<Text>(entity).unwrap().sections[0].value.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_separators() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let list = |items: &[i32]| ForSeparated::new(items, |i| format!("{}", i), || "|");
+        let mut state = list(&[1, 2, 3]).build(&mut bc);
+        let nodes = list(&[1, 2, 3]).nodes(&bc, &state);
+        assert_eq!(texts(bc.world, &nodes), vec!["1", "|", "2", "|", "3"]);
+        let first = nodes.iter().next().unwrap();
+
+        // Separators go between items, not after them.
+        list(&[0, 1]).update(&mut bc, &mut state);
+        let nodes = list(&[0, 1]).nodes(&bc, &state);
+        assert_eq!(texts(bc.world, &nodes), vec!["0", "|", "1"]);
+        assert_eq!(nodes.iter().nth(2), Some(first), "Item should be kept");
+
+        list(&[0]).update(&mut bc, &mut state);
+        let nodes = list(&[0]).nodes(&bc, &state);
+        assert_eq!(texts(bc.world, &nodes), vec!["0"]);
+
+        list(&[]).update(&mut bc, &mut state);
+        assert_eq!(list(&[]).nodes(&bc, &state).count(), 0);
+    }
+}
@@ -4,7 +4,8 @@ use crate::{BuildContext, View, ViewTuple};
 
 use crate::node_span::NodeSpan;
 
-/// A View which renders a sequence of nodes which are inserted into the parent view.
+/// A View which renders a sequence of nodes which are inserted into the parent view. The
+/// output of nested fragments is flattened into a single level.
 pub struct Fragment<A: ViewTuple> {
     items: A,
 }
@@ -20,7 +21,7 @@ impl<A: ViewTuple> View for Fragment<A> {
     type State = A::State;
 
     fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
-        self.items.span_nodes(bc, state)
+        self.items.span_nodes(bc, state).flattened()
     }
 
     fn build(&self, bc: &mut BuildContext) -> Self::State {
@@ -32,7 +33,7 @@ impl<A: ViewTuple> View for Fragment<A> {
     }
 
     fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
-        self.items.assemble_spans(bc, state)
+        self.items.assemble_spans(bc, state).flattened()
     }
 
     fn raze(&self, world: &mut World, state: &mut Self::State) {
@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::{component::Component, system::Query, system::Res},
+    time::Time,
+};
+
+use super::atom::AtomCell;
+
+/// Component which drives an interval started by [`Cx::use_interval`](crate::Cx::use_interval).
+/// This is stored on the same entity as the atom which holds the tick count.
+#[derive(Component)]
+pub(crate) struct IntervalTimer {
+    pub(crate) period: Duration,
+    pub(crate) elapsed: Duration,
+}
+
+/// System which advances interval timers, and increments the tick count of each interval
+/// whose period has elapsed.
+pub(crate) fn update_intervals(
+    time: Res<Time>,
+    mut query: Query<(&mut IntervalTimer, &mut AtomCell)>,
+) {
+    for (mut timer, mut cell) in query.iter_mut() {
+        let period = timer.period;
+        if period.is_zero() {
+            continue;
+        }
+        timer.elapsed += time.delta();
+        let mut ticks = 0;
+        while timer.elapsed >= period {
+            timer.elapsed -= period;
+            ticks += 1;
+        }
+        if ticks > 0 {
+            let count = cell
+                .0
+                .downcast_mut::<u64>()
+                .expect("Interval atom is incorrect type");
+            *count += ticks;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::{plugin::render_views, Cx, View, ViewHandle};
+
+    #[derive(Component, Default)]
+    struct Renders(usize, u64);
+
+    fn clock(mut cx: Cx) -> impl View {
+        let ticks = cx.use_interval(Duration::from_millis(100));
+        let entity = cx.bc.entity;
+        let mut renders = cx.bc.world.get_mut::<Renders>(entity).unwrap();
+        renders.0 += 1;
+        renders.1 = ticks;
+    }
+
+    fn step(app: &mut App, millis: u64) -> (usize, u64) {
+        app.world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(millis));
+        app.update();
+        let renders = app.world.query::<&Renders>().single(&app.world);
+        (renders.0, renders.1)
+    }
+
+    #[test]
+    fn test_use_interval() {
+        let mut app = App::new();
+        app.init_resource::<Time>();
+        app.add_systems(Update, (update_intervals, render_views).chain());
+        app.world
+            .spawn((ViewHandle::new(clock, ()), Renders::default()));
+        assert_eq!(step(&mut app, 0), (1, 0));
+
+        // No re-render until the period has elapsed.
+        assert_eq!(step(&mut app, 60), (1, 0));
+        assert_eq!(step(&mut app, 60), (2, 1));
+        assert_eq!(step(&mut app, 60), (2, 1));
+        assert_eq!(step(&mut app, 60), (3, 2));
+
+        // A long frame counts every elapsed period, but only re-renders once.
+        assert_eq!(step(&mut app, 300), (4, 5));
+    }
+}
@@ -1,27 +1,48 @@
-/// Longest common substring
+/// Longest common substring. Returns the offsets of the substring in `arr1` and `arr2`, and
+/// its length. If there is more than one longest substring, the one which ends first in `arr1`
+/// (and then in `arr2`) is returned.
+///
+/// Only two rows of the dynamic programming table are kept, so the memory used is proportional
+/// to the length of the shorter array.
 pub fn lcs<T, F>(arr1: &[T], arr2: &[T], mut comparator: F) -> (usize, usize, usize)
 where
     F: FnMut(&T, &T) -> bool,
 {
-    let mut longest = 0;
-    let mut arr1_offset = 0;
-    let mut arr2_offset = 0;
-    let mut dp = vec![vec![0; arr2.len() + 1]; arr1.len() + 1];
+    // Iterate over the longer array in the outer loop, so that rows are as short as possible.
+    let transpose = arr2.len() > arr1.len();
+    let (outer_len, inner_len) = if transpose {
+        (arr2.len(), arr1.len())
+    } else {
+        (arr1.len(), arr2.len())
+    };
 
-    for i in 1..=arr1.len() {
-        for j in 1..=arr2.len() {
+    let mut longest = 0;
+    let mut end = (0, 0);
+    let mut prev_row = vec![0; inner_len + 1];
+    let mut row = vec![0; inner_len + 1];
+
+    for outer in 1..=outer_len {
+        for inner in 1..=inner_len {
+            let (i, j) = if transpose {
+                (inner, outer)
+            } else {
+                (outer, inner)
+            };
             if comparator(&arr1[i - 1], &arr2[j - 1]) {
-                dp[i][j] = dp[i - 1][j - 1] + 1;
-                if dp[i][j] > longest {
-                    longest = dp[i][j];
-                    arr1_offset = i - longest;
-                    arr2_offset = j - longest;
+                let length = prev_row[inner - 1] + 1;
+                row[inner] = length;
+                if length > longest || (length == longest && (i, j) < end) {
+                    longest = length;
+                    end = (i, j);
                 }
+            } else {
+                row[inner] = 0;
             }
         }
+        std::mem::swap(&mut prev_row, &mut row);
     }
 
-    (arr1_offset, arr2_offset, longest)
+    (end.0 - longest, end.1 - longest, longest)
 }
 
 #[cfg(test)]
@@ -115,4 +136,20 @@ mod tests {
         assert_eq!(start_b, 11);
         assert_eq!(length, 2);
     }
+
+    #[test]
+    fn test_transposed() {
+        // The shorter array is used for the rows of the table, which must not change the result.
+        let a = vec![1, 2, 9, 3, 4, 9, 1, 2];
+        let b = vec![9, 1, 2];
+
+        assert_eq!(lcs(&a, &b, |x, y| x == y), (5, 0, 3));
+        assert_eq!(lcs(&b, &a, |x, y| x == y), (0, 5, 3));
+
+        // Ties are broken the same way regardless of which array is longer.
+        let a = vec![1, 2, 5, 3, 4];
+        let b = vec![3, 4, 0, 1, 2, 0, 0];
+        assert_eq!(lcs(&a, &b, |x, y| x == y), (0, 3, 2));
+        assert_eq!(lcs(&b, &a, |x, y| x == y), (0, 3, 2));
+    }
 }
@@ -0,0 +1,361 @@
+use std::{
+    ops::Deref,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use bevy::ecs::world::World;
+
+use crate::{BuildContext, View};
+
+use crate::node_span::NodeSpan;
+
+use super::for_keyed::{ForKeyed, KeyedListItem};
+
+/// Maximum number of operations retained in the changelog of a [`ListState`]. If more
+/// operations than this are made between updates, views fall back to a full diff.
+const MAX_LOG_LEN: usize = 256;
+
+static NEXT_LIST_ID: AtomicU64 = AtomicU64::new(1);
+
+/// A recorded mutation of a [`ListState`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ListOp {
+    Insert(usize),
+    Remove(usize),
+    Move(usize, usize),
+}
+
+/// A list of items which records a changelog of the mutations made to it. When rendered with
+/// [`For::observable`](crate::For::observable), only the recorded mutations are applied to the
+/// child views, rather than diffing the entire list.
+///
+/// Clones of a `ListState` share the same identity, so only one clone should be mutated.
+#[derive(Clone, Debug)]
+pub struct ListState<Item> {
+    id: u64,
+    items: Vec<Item>,
+    /// Number of operations which have been dropped from the front of the log.
+    base: usize,
+    log: Vec<ListOp>,
+}
+
+impl<Item> ListState<Item> {
+    /// Construct a new, empty `ListState`.
+    pub fn new() -> Self {
+        Self::from(Vec::new())
+    }
+
+    /// Append an item to the end of the list.
+    pub fn push(&mut self, item: Item) {
+        self.record(ListOp::Insert(self.items.len()));
+        self.items.push(item);
+    }
+
+    /// Insert an item at position `index`.
+    pub fn insert(&mut self, index: usize, item: Item) {
+        self.items.insert(index, item);
+        self.record(ListOp::Insert(index));
+    }
+
+    /// Remove and return the item at position `index`.
+    pub fn remove(&mut self, index: usize) -> Item {
+        let item = self.items.remove(index);
+        self.record(ListOp::Remove(index));
+        item
+    }
+
+    /// Move the item at position `from` to position `to`.
+    pub fn move_item(&mut self, from: usize, to: usize) {
+        let item = self.items.remove(from);
+        self.items.insert(to, item);
+        self.record(ListOp::Move(from, to));
+    }
+
+    /// Remove all items from the list.
+    pub fn clear(&mut self) {
+        while !self.items.is_empty() {
+            self.remove(self.items.len() - 1);
+        }
+    }
+
+    /// The total number of operations which have been made on this list.
+    fn version(&self) -> usize {
+        self.base + self.log.len()
+    }
+
+    fn record(&mut self, op: ListOp) {
+        self.log.push(op);
+        if self.log.len() > MAX_LOG_LEN {
+            let excess = self.log.len() - MAX_LOG_LEN / 2;
+            self.log.drain(..excess);
+            self.base += excess;
+        }
+    }
+}
+
+impl<Item> Default for ListState<Item> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Item> From<Vec<Item>> for ListState<Item> {
+    fn from(items: Vec<Item>) -> Self {
+        Self {
+            id: NEXT_LIST_ID.fetch_add(1, Ordering::Relaxed),
+            items,
+            base: 0,
+            log: Vec::new(),
+        }
+    }
+}
+
+impl<Item> Deref for ListState<Item> {
+    type Target = [Item];
+
+    fn deref(&self) -> &Self::Target {
+        &self.items
+    }
+}
+
+/// State for a [`ForObservable`] view.
+pub struct ForObservableState<Item: Send + PartialEq, V: View> {
+    list_id: u64,
+    version: usize,
+    items: Vec<KeyedListItem<Item, V>>,
+}
+
+/// A for loop over a [`ListState`], created by [`For::observable`](crate::For::observable).
+#[doc(hidden)]
+pub struct ForObservable<Item: Send + Clone + PartialEq, V: View, F: Fn(&Item) -> V + Send + Clone>
+where
+    V::State: Clone,
+{
+    list_id: u64,
+    version: usize,
+    ops: Vec<ListOp>,
+    items: Vec<Item>,
+    each: F,
+}
+
+impl<Item: Send + Clone + PartialEq, V: View, F: Fn(&Item) -> V + Send + Clone>
+    ForObservable<Item, V, F>
+where
+    V::State: Clone,
+{
+    pub(crate) fn new(list: &ListState<Item>, each: F) -> Self {
+        Self {
+            list_id: list.id,
+            version: list.version(),
+            ops: list.log.clone(),
+            items: list.items.clone(),
+            each,
+        }
+    }
+
+    /// Apply the operations made since `state.version` to the child states. Returns false
+    /// if the operations are not available, in which case the state is unchanged.
+    fn apply_ops(&self, bc: &mut BuildContext, state: &mut ForObservableState<Item, V>) -> bool {
+        let log = &self.ops;
+        let base = self.version - log.len();
+        if state.list_id != self.list_id || state.version < base || state.version > self.version {
+            return false;
+        }
+        let ops = &log[state.version - base..];
+
+        // Check that the ops are consistent with the current state before making any changes.
+        let mut len = state.items.len();
+        for op in ops {
+            match *op {
+                ListOp::Insert(index) if index <= len => len += 1,
+                ListOp::Remove(index) if index < len => len -= 1,
+                ListOp::Move(from, to) if from < len && to < len => {}
+                _ => return false,
+            }
+        }
+        if len != self.items.len() {
+            return false;
+        }
+
+        // Apply the ops; inserted items are placeholders until all ops have been applied.
+        let mut items: Vec<Option<KeyedListItem<Item, V>>> =
+            state.items.drain(..).map(Some).collect();
+        for op in ops {
+            match *op {
+                ListOp::Insert(index) => items.insert(index, None),
+                ListOp::Remove(index) => {
+                    if let Some(mut item) = items.remove(index) {
+                        if let (Some(view), Some(child)) = (&item.view, &mut item.state) {
+                            view.raze(bc.world, child);
+                        }
+                    }
+                }
+                ListOp::Move(from, to) => {
+                    let item = items.remove(from);
+                    items.insert(to, item);
+                }
+            }
+        }
+
+        for (item, value) in items.into_iter().zip(self.items.iter()) {
+            let view = (self.each)(value);
+            let child = match item {
+                Some(KeyedListItem {
+                    state: Some(mut child),
+                    ..
+                }) => {
+                    view.update(bc, &mut child);
+                    child
+                }
+                _ => view.build(bc),
+            };
+            state.items.push(KeyedListItem {
+                view: Some(view),
+                state: Some(child),
+                key: value.clone(),
+            });
+        }
+        if !ops.is_empty() {
+            bc.mark_changed_shape();
+        }
+        true
+    }
+}
+
+impl<Item: Send + Clone + PartialEq, V: View, F: Fn(&Item) -> V + Send + Clone> View
+    for ForObservable<Item, V, F>
+where
+    V::State: Clone,
+{
+    type State = ForObservableState<Item, V>;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        let child_spans: Vec<NodeSpan> = state.items.iter().map(|item| item.nodes(bc)).collect();
+        NodeSpan::Fragment(child_spans.into_boxed_slice())
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let items = self
+            .items
+            .iter()
+            .map(|value| {
+                let view = (self.each)(value);
+                let state = view.build(bc);
+                KeyedListItem {
+                    view: Some(view),
+                    state: Some(state),
+                    key: value.clone(),
+                }
+            })
+            .collect();
+        ForObservableState {
+            list_id: self.list_id,
+            version: self.version,
+            items,
+        }
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        if !self.apply_ops(bc, state) {
+            // Changelog not available, fall back to diffing the list.
+            ForKeyed::new_unhashed(&self.items, Item::clone, self.each.clone())
+                .update(bc, &mut state.items);
+        }
+        state.list_id = self.list_id;
+        state.version = self.version;
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        let child_spans: Vec<NodeSpan> = state
+            .items
+            .iter_mut()
+            .map(|item| item.assemble(bc))
+            .collect();
+        NodeSpan::Fragment(child_spans.into_boxed_slice())
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        for item in state.items.iter_mut() {
+            if let (Some(view), Some(child)) = (&item.view, &mut item.state) {
+                view.raze(world, child);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::test_utils::texts;
+
+    fn entities<Item: Send + PartialEq, V: View<State = Entity>>(
+        state: &ForObservableState<Item, V>,
+    ) -> Vec<Entity> {
+        state.items.iter().map(|item| item.state.unwrap()).collect()
+    }
+
+    #[test]
+    fn test_list_state_ops() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+        let each = |item: &i32| format!("{}", item);
+
+        let mut list = ListState::from(vec![1, 2, 3]);
+        let view = ForObservable::new(&list, each);
+        let mut state = view.build(&mut bc);
+        let e = entities(&state);
+        let spawned = bc.world.entities().len();
+
+        // Push: one entity spawned, the rest preserved.
+        list.push(4);
+        ForObservable::new(&list, each).update(&mut bc, &mut state);
+        let e4 = entities(&state);
+        assert_eq!(&e4[..3], &e[..]);
+        assert_eq!(bc.world.entities().len(), spawned + 1);
+        assert_eq!(texts(bc.world), vec!["1", "2", "3", "4"]);
+
+        // Remove: one entity despawned, the rest preserved.
+        list.remove(1);
+        ForObservable::new(&list, each).update(&mut bc, &mut state);
+        assert_eq!(entities(&state), vec![e[0], e[2], e4[3]]);
+        assert!(bc.world.get_entity(e[1]).is_none());
+        assert_eq!(bc.world.entities().len(), spawned);
+
+        // Reorder: no entities spawned or despawned.
+        list.move_item(0, 2);
+        list.move_item(1, 0);
+        ForObservable::new(&list, each).update(&mut bc, &mut state);
+        assert_eq!(&*list, &[4, 3, 1]);
+        assert_eq!(entities(&state), vec![e4[3], e[2], e[0]]);
+        assert_eq!(bc.world.entities().len(), spawned);
+
+        // Several ops in one update.
+        list.insert(1, 5);
+        list.remove(0);
+        list.push(6);
+        ForObservable::new(&list, each).update(&mut bc, &mut state);
+        assert_eq!(&*list, &[5, 3, 1, 6]);
+        assert_eq!(entities(&state)[1..3], [e[2], e[0]]);
+        assert_eq!(texts(bc.world), vec!["1", "3", "5", "6"]);
+    }
+
+    #[test]
+    fn test_list_state_fallback() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+        let each = |item: &i32| format!("{}", item);
+
+        let list = ListState::from(vec![1, 2, 3]);
+        let mut state = ForObservable::new(&list, each).build(&mut bc);
+        let e = entities(&state);
+
+        // A different list has no changelog relative to the old one: items are diffed.
+        let list = ListState::from(vec![3, 2, 1]);
+        ForObservable::new(&list, each).update(&mut bc, &mut state);
+        assert_eq!(entities(&state), vec![e[2], e[1], e[0]]);
+    }
+}
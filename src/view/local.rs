@@ -0,0 +1,53 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::ecs::component::Component;
+
+use super::atom::{AtomHandle, DeferredAtomWrites};
+
+/// Component which holds the most recently set value of a local state variable. This is
+/// shared with all [`StateSetter`]s for that variable.
+#[derive(Component)]
+pub(crate) struct LocalState<T: Send + 'static>(pub(crate) Arc<Mutex<T>>);
+
+/// A handle, returned from [`Cx::use_local`](crate::Cx::use_local), which can be used to
+/// update a local state variable. It is cheap to clone, and can be moved into event handler
+/// closures without needing access to an [`AtomStore`](crate::AtomStore).
+pub struct StateSetter<T: Clone + PartialEq + Send + Sync + 'static> {
+    pub(crate) handle: AtomHandle<T>,
+    pub(crate) current: Arc<Mutex<T>>,
+    pub(crate) writes: DeferredAtomWrites,
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> StateSetter<T> {
+    /// Return the most recent value of the state variable, including any changes which have
+    /// been set but not yet rendered.
+    pub fn get(&self) -> T {
+        self.current.lock().unwrap().clone()
+    }
+
+    /// Set the value of the state variable. If the value differs from the previous value, the
+    /// presenter which owns the state will re-render.
+    pub fn set(&self, value: T) {
+        let mut current = self.current.lock().unwrap();
+        if *current != value {
+            *current = value.clone();
+            self.writes.set(self.handle, value);
+        }
+    }
+
+    /// Update the state variable by applying a function to the current value.
+    pub fn update(&self, f: impl FnOnce(&T) -> T) {
+        let value = f(&self.current.lock().unwrap());
+        self.set(value);
+    }
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> Clone for StateSetter<T> {
+    fn clone(&self) -> Self {
+        Self {
+            handle: self.handle,
+            current: self.current.clone(),
+            writes: self.writes.clone(),
+        }
+    }
+}
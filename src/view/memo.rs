@@ -0,0 +1,95 @@
+use bevy::ecs::world::World;
+
+use crate::{BuildContext, View};
+
+use crate::node_span::NodeSpan;
+
+/// A View which only re-generates its inner view when the value of `deps` changes. When the
+/// deps are unchanged, the previously generated view and its display nodes are retained as-is,
+/// skipping the update of the entire subtree.
+pub struct Memo<D: Clone + PartialEq + Send, V: View, F: Fn() -> V + Send> {
+    deps: D,
+    factory: F,
+}
+
+impl<D: Clone + PartialEq + Send, V: View, F: Fn() -> V + Send> Memo<D, V, F> {
+    /// Construct a new [`Memo`]. The `factory` function is called to produce the inner view
+    /// when the memo is first built, and again whenever `deps` is not equal to the previous value.
+    pub fn new(deps: D, factory: F) -> Self {
+        Self { deps, factory }
+    }
+}
+
+impl<D: Clone + PartialEq + Send, V: View, F: Fn() -> V + Send> View for Memo<D, V, F> {
+    /// State holds the previous deps, along with the view that was produced for them. The view
+    /// is retained so that `nodes`, `assemble` and `raze` can be delegated to it even when
+    /// the factory has not been called.
+    type State = (D, V, V::State);
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        state.1.nodes(bc, &state.2)
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let view = (self.factory)();
+        let state = view.build(bc);
+        (self.deps.clone(), view, state)
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        if state.0 != self.deps {
+            let view = (self.factory)();
+            view.update(bc, &mut state.2);
+            state.0 = self.deps.clone();
+            state.1 = view;
+        }
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        state.1.assemble(bc, &mut state.2)
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        state.1.raze(world, &mut state.2);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use bevy::{prelude::*, text::Text};
+
+    use super::*;
+
+    #[test]
+    fn test_memo() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let memo = |deps: i32, text: &'static str| {
+            Memo::new(deps, move || {
+                CALLS.fetch_add(1, Ordering::Relaxed);
+                text.to_string()
+            })
+        };
+
+        let mut state = memo(1, "a").build(&mut bc);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+        let node = state.2;
+
+        // Same deps: factory not called, text unchanged.
+        memo(1, "b").update(&mut bc, &mut state);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+        assert_eq!(bc.world.get::<Text>(node).unwrap().sections[0].value, "a");
+
+        // Different deps: inner view updated in place.
+        memo(2, "c").update(&mut bc, &mut state);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 2);
+        assert_eq!(state.2, node);
+        assert_eq!(bc.world.get::<Text>(node).unwrap().sections[0].value, "c");
+        assert_eq!(memo(2, "d").nodes(&bc, &state), NodeSpan::Node(node));
+    }
+}
@@ -1,47 +1,93 @@
-mod atom;
+mod any_view;
+pub(crate) mod atom;
 mod bind;
+mod callback;
 mod cx;
+pub(crate) mod derived;
 mod element;
+pub(crate) mod enter_exit;
+mod error_boundary;
 mod r#for;
+mod for_each;
 mod for_index;
 mod for_keyed;
+mod for_separated;
 mod fragment;
 mod r#if;
+pub(crate) mod interval;
 mod lcs;
-mod portal;
+mod list_state;
+mod local;
+mod memo;
+pub(crate) mod portal;
 pub(crate) mod presenter_state;
 mod ref_element;
+pub(crate) mod resource_field;
+pub(crate) mod root_camera;
 mod scoped_values;
+pub(crate) mod show;
+mod spatial_element;
+mod suspense;
+mod switch;
+pub(crate) mod task;
 pub(crate) mod tracked_resources;
 pub(crate) mod tracking;
+mod transition_group;
 #[allow(clippy::module_inception)]
 pub(crate) mod view;
 mod view_children;
 mod view_classes;
+pub(crate) mod view_classes_atom;
+pub(crate) mod view_disabled;
 mod view_insert_bundle;
+mod view_lifecycle;
 mod view_named;
+mod view_on_key;
+mod view_on_scroll;
 mod view_param;
+mod view_ref_out;
 mod view_styled;
 mod view_tuple;
 mod view_with;
 mod view_with_memo;
+mod virtual_list;
 
+pub use any_view::AnyView;
 pub use atom::*;
 pub use bind::Bind;
-pub use cx::Cx;
+pub use callback::{Callback, CallbackHandle, RunCallback};
+pub use cx::{CleanupFn, Cx};
 pub use element::Element;
+pub use enter_exit::{EnterExit, EnterExitApi, EnterExitState, EnterExitTimer};
+pub use error_boundary::{ErrorBoundary, ErrorBoundaryState};
+pub use for_each::ForEach;
 pub use for_index::ForIndex;
 pub use for_keyed::ForKeyed;
+pub use for_separated::{ForSeparated, SeparatedListState};
 pub use fragment::Fragment;
+pub use list_state::ListState;
+pub use local::StateSetter;
+pub use memo::Memo;
 pub use portal::Portal;
 pub use presenter_state::ViewHandle;
 pub use r#for::For;
 pub use r#if::If;
 pub use ref_element::RefElement;
 pub use scoped_values::ScopedValueKey;
+pub use show::Show;
+pub use spatial_element::SpatialElement;
+pub use suspense::{Suspense, SuspenseState};
+pub use switch::Switch;
+pub use task::TaskState;
 pub(crate) use tracking::TrackingContext;
+pub use transition_group::{TransitionEntry, TransitionGroup};
 pub use view::PresenterFn;
 pub use view::View;
 pub use view::*;
+pub use view_classes_atom::ViewClassesAtom;
+pub use view_disabled::{Disabled, ViewDisabled, CLS_DISABLED};
+pub use view_lifecycle::{ViewOnMount, ViewOnUnmount};
 pub use view_param::ViewParam;
+pub use view_ref_out::{RefHandle, ViewRefOut};
 pub use view_tuple::ViewTuple;
+pub use virtual_list::{VirtualList, VirtualListState};
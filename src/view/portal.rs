@@ -1,23 +1,102 @@
 use bevy::prelude::*;
 
-use crate::{BuildContext, View};
+use crate::{hover::ignore_picking, BuildContext, View, ViewTuple};
 
 use crate::node_span::NodeSpan;
 
+/// Component on a portal's container which records the presenter that the portal belongs to.
+#[derive(Component)]
+pub(crate) struct PortalOwner(pub(crate) Entity);
+
 /// Portal behaves just like Element, except that the generated UI nodes are unparented,
-/// making them roots.
+/// making them roots. Alternatively, the portal can be parented to a specific target entity
+/// by constructing it with [`Portal::to`].
+///
+/// The children of the portal are placed in a container node which covers the whole of its
+/// parent (or the window, if there is no target), and which doesn't intercept pointer events.
+/// The stacking order of the container can be set with [`Portal::with_z`].
 #[derive(Default)]
-pub struct Portal {}
+pub struct Portal<A: ViewTuple = ()> {
+    target: Option<Entity>,
+    z_index: Option<i32>,
+    items: A,
+}
 
-impl Portal {
+impl Portal<()> {
     /// Construct a new, empty `Element`.
     pub fn new() -> Self {
-        Self {}
+        Self {
+            target: None,
+            z_index: None,
+            items: (),
+        }
+    }
+
+    /// Construct a new Portal whose content is parented to `target`, rather than to the
+    /// parent of the Portal. If the target entity doesn't exist, a warning is logged and the
+    /// content becomes a root instead.
+    pub fn to(target: Entity) -> Self {
+        Self {
+            target: Some(target),
+            z_index: None,
+            items: (),
+        }
+    }
+}
+
+impl<A: ViewTuple> Portal<A> {
+    /// Set the child views of the portal.
+    pub fn children<A2: ViewTuple>(self, items: A2) -> Portal<A2> {
+        Portal {
+            target: self.target,
+            z_index: self.z_index,
+            items,
+        }
+    }
+
+    /// Set the global z-index of the portal's container, which controls how it stacks relative
+    /// to other portals. If not set, the container has no [`ZIndex`].
+    pub fn with_z(mut self, z_index: i32) -> Self {
+        self.z_index = Some(z_index);
+        self
+    }
+
+    /// Parent the portal entity to the target, if there is one, and update its z-index.
+    fn attach(&self, world: &mut World, portal: Entity) {
+        let mut entt = world.entity_mut(portal);
+        match self.z_index {
+            Some(z) => {
+                entt.insert(ZIndex::Global(z));
+            }
+            None => {
+                entt.remove::<ZIndex>();
+            }
+        }
+
+        let parent = world.get::<Parent>(portal).map(|p| p.get());
+        match self.target {
+            Some(target) if world.get_entity(target).is_none() => {
+                warn!("Portal target {:?} does not exist", target);
+                if parent.is_some() {
+                    world.entity_mut(portal).remove_parent();
+                }
+            }
+            Some(target) => {
+                if parent != Some(target) {
+                    world.entity_mut(portal).set_parent(target);
+                }
+            }
+            None => {
+                if parent.is_some() {
+                    world.entity_mut(portal).remove_parent();
+                }
+            }
+        }
     }
 }
 
-impl View for Portal {
-    type State = Entity;
+impl<A: ViewTuple> View for Portal<A> {
+    type State = (Entity, A::State);
 
     fn nodes(&self, _vc: &BuildContext, _state: &Self::State) -> NodeSpan {
         NodeSpan::Empty
@@ -28,36 +107,154 @@ impl View for Portal {
             .world
             .spawn((
                 NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(0.),
+                        top: Val::Px(0.),
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        ..default()
+                    },
                     visibility: Visibility::Visible,
                     ..default()
                 },
+                ignore_picking(),
                 Name::new("Portal"),
+                PortalOwner(bc.entity),
             ))
             .id();
-        new_entity
+        self.attach(bc.world, new_entity);
+        let prev_parent = bc.parent.replace(new_entity);
+        let items = self.items.build_spans(bc);
+        bc.parent = prev_parent;
+        (new_entity, items)
     }
 
-    fn update(&self, _vc: &mut BuildContext, _state: &mut Self::State) {}
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        self.attach(bc.world, state.0);
+        let prev_parent = bc.parent.replace(state.0);
+        self.items.update_spans(bc, &mut state.1);
+        bc.parent = prev_parent;
+    }
 
-    fn assemble(&self, _vc: &mut BuildContext, _state: &mut Self::State) -> NodeSpan {
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        let children = self.items.assemble_spans(bc, &mut state.1);
+        let flat: Vec<Entity> = children.iter().collect();
+        let mut em = bc.entity_mut(state.0);
+        if em
+            .get::<Children>()
+            .is_none_or(|children| !children.eq(&flat))
+        {
+            em.replace_children(&flat);
+        }
         NodeSpan::Empty
     }
 
     fn raze(&self, world: &mut World, state: &mut Self::State) {
-        let mut entt = world.entity_mut(*state);
+        self.items.raze_spans(world, &mut state.1);
+        let mut entt = world.entity_mut(state.0);
         entt.remove_parent();
         entt.despawn();
     }
 }
 
-impl Clone for Portal {
+impl<A: ViewTuple + Clone> Clone for Portal<A> {
     fn clone(&self) -> Self {
-        Self {}
+        Self {
+            target: self.target,
+            z_index: self.z_index,
+            items: self.items.clone(),
+        }
     }
 }
 
-impl PartialEq for Portal {
-    fn eq(&self, _other: &Self) -> bool {
-        true
+impl<A: ViewTuple + PartialEq> PartialEq for Portal<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.target == other.target && self.z_index == other.z_index && self.items == other.items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_portal_to() {
+        let mut world = World::new();
+        let container = world.spawn(Name::new("container")).id();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let (portal, _) = Portal::to(container).build(&mut bc);
+        assert_eq!(bc.world.get::<Parent>(portal).unwrap().get(), container);
+        assert_eq!(
+            bc.world.get::<Children>(container).unwrap().to_vec(),
+            vec![portal]
+        );
+
+        // Missing target falls back to a root.
+        let missing = bc.world.spawn_empty().id();
+        bc.world.despawn(missing);
+        let (portal, _) = Portal::to(missing).build(&mut bc);
+        assert!(bc.world.get::<Parent>(portal).is_none());
+    }
+
+    #[test]
+    fn test_portal_retarget() {
+        let mut world = World::new();
+        let layer1 = world.spawn(Name::new("layer1")).id();
+        let layer2 = world.spawn(Name::new("layer2")).id();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let view = |target: Option<Entity>, z: Option<i32>| {
+            let portal = match target {
+                Some(target) => Portal::to(target),
+                None => Portal::new(),
+            };
+            let portal = match z {
+                Some(z) => portal.with_z(z),
+                None => portal,
+            };
+            portal.children("content")
+        };
+
+        let mut state = view(Some(layer1), Some(10)).build(&mut bc);
+        assert_eq!(
+            view(Some(layer1), Some(10)).assemble(&mut bc, &mut state),
+            NodeSpan::Empty
+        );
+        let (portal, content) = state;
+        assert_eq!(bc.world.get::<Parent>(portal).unwrap().get(), layer1);
+        assert_eq!(
+            bc.world.get::<Children>(portal).unwrap().to_vec(),
+            vec![content]
+        );
+        assert!(matches!(
+            bc.world.get::<ZIndex>(portal),
+            Some(ZIndex::Global(10))
+        ));
+
+        // Changing the target moves the container, along with its content.
+        view(Some(layer2), None).update(&mut bc, &mut state);
+        view(Some(layer2), None).assemble(&mut bc, &mut state);
+        assert_eq!(bc.world.get::<Parent>(portal).unwrap().get(), layer2);
+        assert!(bc
+            .world
+            .get::<Children>(layer1)
+            .is_none_or(|c| c.is_empty()));
+        assert_eq!(
+            bc.world.get::<Children>(portal).unwrap().to_vec(),
+            vec![content]
+        );
+        assert!(bc.world.get::<ZIndex>(portal).is_none());
+
+        // Without a target, the container becomes a root.
+        view(None, None).update(&mut bc, &mut state);
+        assert!(bc.world.get::<Parent>(portal).is_none());
+
+        view(None, None).raze(bc.world, &mut state);
+        assert!(bc.world.get_entity(portal).is_none());
+        assert!(bc.world.get_entity(content).is_none());
     }
 }
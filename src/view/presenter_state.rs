@@ -1,19 +1,28 @@
 use std::{
     any::Any,
+    panic::{catch_unwind, AssertUnwindSafe, Location},
     sync::{Arc, Mutex},
 };
 
-use bevy::{prelude::*, utils::HashSet};
+use bevy::{
+    ecs::system::SystemState,
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
 
 use crate::{
     tracked_resources::TrackedResources,
-    tracking::{OwnedEntities, TrackedComponents},
+    tracking::{EffectCleanup, OwnedEntities, TrackedComponents},
     BuildContext, NodeSpan, PresenterFn, TrackingContext,
 };
 
-use super::{cx::Cx, View};
+use super::{cx::Cx, error_boundary::report_panic, View};
 
 /// A ViewHandle holds a type-erased reference to a presenter function and its props and state.
+///
+/// To display a view hierarchy with a camera other than the default UI camera, such as one
+/// which renders to a texture, add a [`TargetCamera`](bevy::ui::TargetCamera) to the root
+/// `ViewHandle` entity. It is copied to the top-level nodes of the view, and to its portals.
 #[derive(Component)]
 pub struct ViewHandle {
     /// A reference to the type-erased presenter state.
@@ -65,6 +74,14 @@ pub struct PresenterState<Marker: 'static, F: PresenterFn<Marker>> {
 
     /// The UiNodes generated by this view state
     nodes: NodeSpan,
+
+    /// Call sites of the hooks which allocated owned entities, used to diagnose hooks which
+    /// are called out of order.
+    hook_locations: Vec<&'static Location<'static>>,
+
+    /// Copy of the entities owned by hooks, so that they can be released even if the presenter
+    /// entity (and its [`OwnedEntities`] component) has already been despawned.
+    owned_entities: Vec<Entity>,
 }
 
 impl<Marker, F: PresenterFn<Marker>> PresenterState<Marker, F> {
@@ -75,6 +92,8 @@ impl<Marker, F: PresenterFn<Marker>> PresenterState<Marker, F> {
             props,
             view: None,
             state: Option::None,
+            hook_locations: Vec::new(),
+            owned_entities: Vec::new(),
         }
     }
 }
@@ -99,6 +118,9 @@ pub trait AnyPresenterState: Send {
 
     /// Update the copy of props in this view state.
     fn get_props(&mut self) -> &mut dyn Any;
+
+    /// Return the type name of the presenter function, for debugging.
+    fn presenter_name(&self) -> &'static str;
 }
 
 impl<Marker, F: PresenterFn<Marker>> AnyPresenterState for PresenterState<Marker, F> {
@@ -107,31 +129,71 @@ impl<Marker, F: PresenterFn<Marker>> AnyPresenterState for PresenterState<Marker
             Some(owned) => owned.0.clone(),
             None => Vec::new(),
         };
+        // If the output has already been attached to a display node, views which are rebuilt
+        // can inherit from it. Otherwise, use the node it will be attached to, if known.
+        let parent = match self.nodes {
+            NodeSpan::Node(node) => bc.world.get::<Parent>(node).map(|parent| parent.get()),
+            _ => None,
+        }
+        .or_else(|| {
+            bc.world
+                .get::<PresenterAttachment>(entity)
+                .map(|attachment| attachment.0)
+        });
         let mut child_context = bc.for_entity(entity);
+        child_context.parent = parent;
         let mut tracking = TrackingContext {
             resources: Vec::new(),
+            resource_types: HashSet::new(),
             components: HashSet::new(),
             next_entity_index: 0,
             owned_entities: atom_handles,
+            hook_locations: std::mem::take(&mut self.hook_locations),
+            presenter_name: self.presenter_name(),
+            release_unused: false,
         };
-        let cx = Cx::new(&self.props, &mut child_context, &mut tracking);
-        self.view = Some(self.presenter.call(cx));
-        match self.state {
-            Some(ref mut state) => {
-                self.view
-                    .as_ref()
-                    .unwrap()
-                    .update(&mut child_context, state);
-                self.attach(bc, entity);
+        let rebuild = self.state.is_some();
+        let hook_count = tracking.hook_locations.len();
+        // Catch panics from the presenter and its views, so that they can be reported to an
+        // enclosing panic boundary instead of taking down the app. Presenter state is not
+        // unwind safe: if a panic occurs while updating the view, the state may be left
+        // partially updated. This is acceptable because the boundary razes the presenter.
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let cx = Cx::new(&self.props, &mut child_context, &mut tracking);
+            let view = self.view.insert(self.presenter.call(cx));
+            match self.state {
+                Some(ref mut state) => {
+                    view.update(&mut child_context, state);
+                    false
+                }
+                None => {
+                    self.state = Some(view.build(&mut child_context));
+                    true
+                }
             }
-            None => {
-                let state = self.view.as_ref().unwrap().build(&mut child_context);
-                self.state = Some(state);
+        }));
+        if tracking.release_unused && result.is_ok() {
+            tracking.release_unused_entities(bc.world);
+        } else if rebuild && result.is_ok() && tracking.next_entity_index != hook_count {
+            warn!(
+                "Presenter `{}` called {} hooks, but {} on a previous render. Hooks must be \
+                called unconditionally and in the same order each time the presenter runs.",
+                self.presenter_name(),
+                tracking.next_entity_index,
+                hook_count
+            );
+        }
+        self.hook_locations = std::mem::take(&mut tracking.hook_locations);
+
+        match result {
+            Ok(false) => self.attach(bc, entity),
+            Ok(true) => {
                 bc.mark_changed_shape();
                 if let Some(parent) = bc.entity(bc.entity).get::<Parent>() {
                     bc.entity_mut(parent.get()).insert(PresenterGraphChanged);
                 }
             }
+            Err(payload) => report_panic(bc.world, entity, payload),
         };
 
         let tick = bc.world.change_tick();
@@ -153,6 +215,7 @@ impl<Marker, F: PresenterFn<Marker>> AnyPresenterState for PresenterState<Marker
             });
         }
 
+        self.owned_entities.clone_from(&tracking.owned_entities);
         if tracking.owned_entities.is_empty() {
             entt.remove::<OwnedEntities>();
         } else {
@@ -169,24 +232,28 @@ impl<Marker, F: PresenterFn<Marker>> AnyPresenterState for PresenterState<Marker
             self.view = None;
             self.state = None;
         }
+        self.hook_locations.clear();
 
-        // Release all owned entities.
-        if let Some(mut handles) = world.entity_mut(entity).get_mut::<OwnedEntities>() {
-            let mut handles_copy: Vec<Entity> = Vec::new();
-            std::mem::swap(&mut handles.0, &mut handles_copy);
-            for handle in handles_copy.iter() {
-                world.despawn(*handle);
+        // Release all owned entities. The presenter entity may already have been despawned, in
+        // which case the copy of the owned entities is used.
+        if let Some(mut entt) = world.get_entity_mut(entity) {
+            entt.remove::<OwnedEntities>();
+        }
+        for handle in std::mem::take(&mut self.owned_entities) {
+            if world.get_entity(handle).is_some() {
+                EffectCleanup::run(world, handle);
+                world.despawn(handle);
             }
         }
     }
 
     fn attach(&mut self, bc: &mut BuildContext, entity: Entity) {
+        // The presenter may not have been built if it panicked.
+        let (Some(view), Some(state)) = (self.view.as_ref(), self.state.as_mut()) else {
+            return;
+        };
         let mut child_context = bc.for_entity(entity);
-        let nodes = self
-            .view
-            .as_ref()
-            .unwrap()
-            .assemble(&mut child_context, self.state.as_mut().unwrap());
+        let nodes = view.assemble(&mut child_context, state);
         if self.nodes != nodes {
             self.nodes = nodes;
             // Parent needs to rebuild children
@@ -217,6 +284,10 @@ impl<Marker, F: PresenterFn<Marker>> AnyPresenterState for PresenterState<Marker
     fn get_props(&mut self) -> &mut dyn Any {
         &mut self.props
     }
+
+    fn presenter_name(&self) -> &'static str {
+        std::any::type_name::<F>()
+    }
 }
 
 /// Marker component that lets us know when the internal state of a presenter needs to be
@@ -228,3 +299,90 @@ pub struct PresenterStateChanged;
 /// rebuilt.
 #[derive(Component)]
 pub struct PresenterGraphChanged;
+
+/// The display node which the output of a bound presenter will be attached to, recorded when
+/// the presenter entity is spawned, so that views can inherit from it before it is attached.
+#[derive(Component)]
+pub(crate) struct PresenterAttachment(pub(crate) Entity);
+
+/// Keeps a reference to the presenter state of every [`ViewHandle`] which has been built, so
+/// that the presenter can still be razed after its entity has been despawned.
+#[derive(Resource, Default)]
+pub(crate) struct ViewHandleRegistry(pub(crate) HashMap<Entity, Arc<Mutex<dyn AnyPresenterState>>>);
+
+/// System which razes presenters whose [`ViewHandle`] was removed or despawned by something
+/// other than Quill, such as game code tearing down a scene. Without this, the display nodes
+/// generated by the presenter would be leaked. Presenters which were already razed normally
+/// are unaffected, since razing is idempotent.
+pub(crate) fn raze_removed_views(
+    world: &mut World,
+    removed: &mut SystemState<RemovedComponents<ViewHandle>>,
+) {
+    let mut removed: Vec<Entity> = removed.get_mut(world).read().collect();
+    // The entity may have been given a new ViewHandle since the old one was removed.
+    removed.retain(|entity| world.get::<ViewHandle>(*entity).is_none());
+    if removed.is_empty() {
+        return;
+    }
+
+    let Some(mut registry) = world.get_resource_mut::<ViewHandleRegistry>() else {
+        return;
+    };
+    let razed: Vec<_> = removed
+        .iter()
+        .filter_map(|entity| registry.0.remove(entity).map(|inner| (*entity, inner)))
+        .collect();
+    for (entity, inner) in razed {
+        inner.lock().unwrap().raze(world, entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{plugin::render_views, Element, View};
+
+    fn root(_cx: Cx) -> impl View {
+        Element::new().children(("root", child.bind(())))
+    }
+
+    fn child(_cx: Cx) -> impl View {
+        Element::new().children("child")
+    }
+
+    fn node_count(world: &mut World) -> usize {
+        world.query::<&Node>().iter(world).count()
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_systems(Update, (raze_removed_views, render_views).chain());
+        app
+    }
+
+    #[test]
+    fn test_raze_despawned_root() {
+        let mut app = app();
+        let root = app.world.spawn(ViewHandle::new(root, ())).id();
+        app.update();
+        assert_eq!(node_count(&mut app.world), 4);
+
+        app.world.entity_mut(root).despawn_recursive();
+        app.update();
+        assert_eq!(node_count(&mut app.world), 0);
+    }
+
+    #[test]
+    fn test_raze_despawned_root_non_recursive() {
+        let mut app = app();
+        let root = app.world.spawn(ViewHandle::new(root, ())).id();
+        app.update();
+        assert_eq!(node_count(&mut app.world), 4);
+
+        app.world.despawn(root);
+        app.update();
+        assert_eq!(node_count(&mut app.world), 0);
+        // The child presenter was razed along with the root.
+        assert_eq!(app.world.query::<&ViewHandle>().iter(&app.world).count(), 0);
+    }
+}
@@ -0,0 +1,63 @@
+use bevy::ecs::{
+    change_detection::{DetectChanges, Ref},
+    component::{Component, Tick},
+    entity::Entity,
+    system::Resource,
+    world::World,
+};
+
+use super::atom::{AtomCell, AtomHandle, AtomMethods};
+
+type SyncFn = Box<dyn Fn(&mut World) + Send + Sync>;
+
+/// Component which binds an atom to a field of a resource, created by
+/// [`Cx::use_resource_field_atom`](crate::Cx::use_resource_field_atom). When the atom is
+/// written, the new value is copied to the resource.
+#[derive(Component)]
+pub(crate) struct ResourceFieldBinding {
+    sync: SyncFn,
+    tick: Tick,
+}
+
+impl ResourceFieldBinding {
+    pub(crate) fn new<T: Resource, V: Clone + PartialEq + Send + Sync + 'static>(
+        world: &mut World,
+        handle: AtomHandle<V>,
+        get: fn(&T) -> V,
+        set: fn(&mut T, V),
+    ) -> Self {
+        Self {
+            sync: Box::new(move |world| {
+                let value = world.get_atom(handle);
+                let mut res = world.resource_mut::<T>();
+                // Only mutate (and trigger change detection) if the value is different.
+                if get(&res) != value {
+                    set(&mut res, value);
+                }
+            }),
+            tick: world.increment_change_tick(),
+        }
+    }
+}
+
+/// Copy the values of any atoms bound to resource fields which have been written since the
+/// last sync.
+pub(crate) fn sync_resource_fields(world: &mut World) {
+    let this_run = world.change_tick();
+    let mut q = world.query::<(Entity, &ResourceFieldBinding, Ref<AtomCell>)>();
+    let changed: Vec<Entity> = q
+        .iter(world)
+        .filter(|(_, binding, cell)| cell.last_changed().is_newer_than(binding.tick, this_run))
+        .map(|(e, _, _)| e)
+        .collect();
+    for id in changed {
+        let binding = world.entity_mut(id).take::<ResourceFieldBinding>().unwrap();
+        (binding.sync)(world);
+        // Advance the tick so that writes made after this point are seen as changes.
+        let tick = world.increment_change_tick();
+        world.entity_mut(id).insert(ResourceFieldBinding {
+            sync: binding.sync,
+            tick,
+        });
+    }
+}
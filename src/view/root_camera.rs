@@ -0,0 +1,80 @@
+use bevy::{prelude::*, ui::TargetCamera};
+
+use crate::{portal::PortalOwner, ViewHandle};
+
+/// Copy the [`TargetCamera`] of each root presenter entity to the display nodes which it outputs
+/// at the top level, so that bevy_ui renders them with that camera. This lets a view hierarchy
+/// be displayed by a camera other than the default UI camera, such as one which renders to a
+/// texture or to a secondary window. Portals which are not parented to a target are also roots
+/// of the UI hierarchy, and are given the camera of the root presenter which they belong to.
+/// Roots without a `TargetCamera` leave their nodes alone.
+pub(crate) fn update_root_cameras(
+    mut commands: Commands,
+    roots: Query<(&ViewHandle, &TargetCamera), Without<Parent>>,
+    portals: Query<(Entity, &PortalOwner), Without<Parent>>,
+    parents: Query<&Parent>,
+    nodes: Query<Option<&TargetCamera>, (With<Node>, Without<Parent>)>,
+) {
+    let mut set_camera = |node: Entity, camera: &TargetCamera| {
+        if let Ok(current) = nodes.get(node) {
+            if current != Some(camera) {
+                commands.entity(node).insert(camera.clone());
+            }
+        }
+    };
+    for (handle, camera) in roots.iter() {
+        for node in handle.nodes().iter() {
+            set_camera(node, camera);
+        }
+    }
+    for (portal, owner) in portals.iter() {
+        let root = parents.iter_ancestors(owner.0).last().unwrap_or(owner.0);
+        if let Ok((_, camera)) = roots.get(root) {
+            set_camera(portal, camera);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::{plugin::render_views, Cx, Element, Portal, View};
+
+    fn root_presenter(_cx: Cx) -> impl View {
+        Element::new().children((
+            "Hello",
+            Portal::new().children(Element::new().children("Popup")),
+        ))
+    }
+
+    #[test]
+    fn test_root_camera() {
+        let mut world = World::new();
+        let camera = world.spawn(Camera::default()).id();
+        let root = world
+            .spawn((ViewHandle::new(root_presenter, ()), TargetCamera(camera)))
+            .id();
+        render_views(&mut world);
+        world.run_system_once(update_root_cameras);
+
+        // The element output by the root, and the portal container, are both roots of the UI
+        // hierarchy, and are both displayed by the camera.
+        let mut q = world
+            .query_filtered::<(Entity, Option<&TargetCamera>), (With<Node>, Without<Parent>)>();
+        let ui_roots: Vec<_> = q.iter(&world).collect();
+        assert_eq!(ui_roots.len(), 2);
+        for (_, target) in ui_roots {
+            assert_eq!(target, Some(&TargetCamera(camera)));
+        }
+
+        // Changing the camera of the root moves the nodes to the new camera.
+        let camera2 = world.spawn(Camera::default()).id();
+        world.entity_mut(root).insert(TargetCamera(camera2));
+        world.run_system_once(update_root_cameras);
+        for (_, target) in q.iter(&world) {
+            assert_eq!(target, Some(&TargetCamera(camera2)));
+        }
+    }
+}
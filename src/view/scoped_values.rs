@@ -5,10 +5,12 @@ use std::fmt::Debug;
 use std::marker::PhantomData;
 
 /// A unique key for accessing a variable whose scope is the current presenter invocation
-/// and any nested presenters.
+/// and any nested presenters. A key can optionally carry a default value, which is used when
+/// no ancestor presenter has defined the variable.
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct ScopedValueKey<T: Clone> {
     name: &'static str,
+    default: Option<T>,
     marker: PhantomData<T>,
 }
 
@@ -23,6 +25,16 @@ impl<T: Clone> ScopedValueKey<T> {
     pub const fn new(name: &'static str) -> Self {
         Self {
             name,
+            default: None,
+            marker: PhantomData,
+        }
+    }
+
+    /// Construct a new variable token given a name and a default value.
+    pub const fn with_default(name: &'static str, default: T) -> Self {
+        Self {
+            name,
+            default: Some(default),
             marker: PhantomData,
         }
     }
@@ -31,6 +43,11 @@ impl<T: Clone> ScopedValueKey<T> {
     pub fn id(&self) -> &'static str {
         self.name
     }
+
+    /// Return the default value for this token, if any.
+    pub fn default_value(&self) -> Option<&T> {
+        self.default.as_ref()
+    }
 }
 
 /// Component used to store context variables.
@@ -0,0 +1,250 @@
+use bevy::prelude::*;
+
+use crate::{BuildContext, View};
+
+use crate::node_span::NodeSpan;
+
+/// State for a [`Show`] view.
+pub struct ShowState<S> {
+    /// State of the child view, or `None` if it has not been built yet.
+    child: Option<S>,
+    /// Whether the child's output is currently hidden.
+    hidden: bool,
+    /// The visibility of the output entities before they were hidden.
+    saved: Vec<(Entity, Option<Visibility>)>,
+}
+
+/// Component which keeps an entity hidden by a [`Show`] view at `Display::None` when it is
+/// restyled. It records the styled display, which is restored when the entity is shown again.
+#[derive(Component)]
+pub(crate) struct ShowHidden(pub(crate) Display);
+
+/// A view which shows or hides its child without razing it. Unlike [`If`](crate::If), the
+/// child is built once, and its state (and the state of its display entities, such as scroll
+/// position) is preserved while it is hidden. Hidden display entities have their
+/// [`Visibility`] set to `Hidden` and their [`Display`] set to `None`.
+///
+/// The child view is not updated while it is hidden.
+pub struct Show<V: View> {
+    when: bool,
+    lazy: bool,
+    child: V,
+}
+
+impl<V: View> Show<V> {
+    /// Construct a new `Show` view, which displays `child` when `when` is true.
+    pub fn new(when: bool, child: V) -> Self {
+        Self {
+            when,
+            lazy: false,
+            child,
+        }
+    }
+
+    /// Delay building the child until the first time that it is shown. By default the child
+    /// is built immediately, even if it is hidden.
+    pub fn lazy(mut self) -> Self {
+        self.lazy = true;
+        self
+    }
+
+    fn hide(
+        bc: &mut BuildContext,
+        nodes: &NodeSpan,
+        saved: &mut Vec<(Entity, Option<Visibility>)>,
+    ) {
+        for entity in nodes.iter() {
+            let mut entt = bc.entity_mut(entity);
+            let visibility = entt.get::<Visibility>().copied();
+            saved.push((entity, visibility));
+            if let Some(mut visibility) = entt.get_mut::<Visibility>() {
+                *visibility = Visibility::Hidden;
+            }
+            if let Some(mut style) = entt.get_mut::<Style>() {
+                let display = std::mem::replace(&mut style.display, Display::None);
+                entt.insert(ShowHidden(display));
+            }
+        }
+    }
+
+    fn unhide(bc: &mut BuildContext, saved: &mut Vec<(Entity, Option<Visibility>)>) {
+        for (entity, visibility) in saved.drain(..) {
+            let Some(mut entt) = bc.world.get_entity_mut(entity) else {
+                continue;
+            };
+            if let (Some(prev), Some(mut visibility)) = (visibility, entt.get_mut::<Visibility>()) {
+                *visibility = prev;
+            }
+            if let Some(ShowHidden(display)) = entt.take::<ShowHidden>() {
+                if let Some(mut style) = entt.get_mut::<Style>() {
+                    style.display = display;
+                }
+            }
+        }
+    }
+}
+
+impl<V: View> View for Show<V> {
+    type State = ShowState<V::State>;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        match state.child {
+            Some(ref child) => self.child.nodes(bc, child),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let mut state = ShowState {
+            child: None,
+            hidden: false,
+            saved: Vec::new(),
+        };
+        if self.when || !self.lazy {
+            let child = self.child.build(bc);
+            if !self.when {
+                Self::hide(bc, &self.child.nodes(bc, &child), &mut state.saved);
+                state.hidden = true;
+            }
+            state.child = Some(child);
+        }
+        state
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        match state.child {
+            Some(ref mut child) => {
+                if self.when {
+                    self.child.update(bc, child);
+                    if state.hidden {
+                        Self::unhide(bc, &mut state.saved);
+                        state.hidden = false;
+                    }
+                } else if !state.hidden {
+                    Self::hide(bc, &self.child.nodes(bc, child), &mut state.saved);
+                    state.hidden = true;
+                }
+            }
+            None => {
+                if self.when {
+                    state.child = Some(self.child.build(bc));
+                    bc.mark_changed_shape();
+                }
+            }
+        }
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        match state.child {
+            Some(ref mut child) => self.child.assemble(bc, child),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        if let Some(ref mut child) = state.child {
+            self.child.raze(world, child);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Element;
+
+    #[test]
+    fn test_show() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let mut state = Show::new(true, Element::new()).build(&mut bc);
+        let child = state.child.unwrap();
+        assert_eq!(
+            *bc.world.get::<Visibility>(child).unwrap(),
+            Visibility::Visible
+        );
+
+        // Hiding keeps the same entity.
+        Show::new(false, Element::new()).update(&mut bc, &mut state);
+        assert_eq!(state.child, Some(child));
+        assert_eq!(
+            *bc.world.get::<Visibility>(child).unwrap(),
+            Visibility::Hidden
+        );
+        assert_eq!(bc.world.get::<Style>(child).unwrap().display, Display::None);
+
+        // Showing restores the previous settings.
+        Show::new(true, Element::new()).update(&mut bc, &mut state);
+        assert_eq!(state.child, Some(child));
+        assert_eq!(
+            *bc.world.get::<Visibility>(child).unwrap(),
+            Visibility::Visible
+        );
+        assert_eq!(bc.world.get::<Style>(child).unwrap().display, Display::Flex);
+
+        // Toggle again.
+        Show::new(false, Element::new()).update(&mut bc, &mut state);
+        Show::new(true, Element::new()).update(&mut bc, &mut state);
+        assert_eq!(state.child, Some(child));
+        assert_eq!(
+            *bc.world.get::<Visibility>(child).unwrap(),
+            Visibility::Visible
+        );
+    }
+
+    #[test]
+    fn test_show_restyled() {
+        use crate::{
+            hover::{HoverState, PreviousHoverState},
+            update::{update_styles, PreviousFocus},
+            StyleHandle,
+        };
+        use bevy::{a11y::Focus, ecs::system::RunSystemOnce};
+
+        fn child() -> impl View<State = Entity> {
+            Element::new().styled(StyleHandle::build(|ss| ss.display(Display::Grid)))
+        }
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_resource::<HoverState>()
+            .init_resource::<PreviousHoverState>()
+            .init_resource::<Focus>()
+            .init_resource::<PreviousFocus>();
+        let world = &mut app.world;
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(world, entity);
+
+        // The child is styled after it has been hidden, but stays hidden.
+        let mut state = Show::new(false, child()).build(&mut bc);
+        let child_entity = state.child.unwrap();
+        bc.world.run_system_once(update_styles);
+        assert_eq!(
+            bc.world.get::<Style>(child_entity).unwrap().display,
+            Display::None
+        );
+
+        // Showing it restores the styled display.
+        Show::new(true, child()).update(&mut bc, &mut state);
+        assert_eq!(
+            bc.world.get::<Style>(child_entity).unwrap().display,
+            Display::Grid
+        );
+    }
+
+    #[test]
+    fn test_show_lazy() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let mut state = Show::new(false, Element::new()).lazy().build(&mut bc);
+        assert!(state.child.is_none());
+        Show::new(true, Element::new())
+            .lazy()
+            .update(&mut bc, &mut state);
+        assert!(state.child.is_some());
+    }
+}
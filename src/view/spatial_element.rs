@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+
+use crate::{BuildContext, View};
+
+use crate::node_span::NodeSpan;
+
+/// A View which renders a [`SpatialBundle`] that can have multiple children. This is the
+/// world-space counterpart of [`Element`](crate::Element), for managing scene entities such as
+/// meshes or editor gizmos rather than UI nodes.
+#[derive(Copy, Default)]
+pub struct SpatialElement {}
+
+impl SpatialElement {
+    /// Construct a new, empty `SpatialElement`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl View for SpatialElement {
+    type State = Entity;
+
+    fn nodes(&self, _vc: &BuildContext, state: &Self::State) -> NodeSpan {
+        NodeSpan::Node(*state)
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        bc.world
+            .spawn((SpatialBundle::default(), Name::new("spatial_element")))
+            .id()
+    }
+
+    fn update(&self, _vc: &mut BuildContext, _state: &mut Self::State) {}
+
+    fn assemble(&self, _vc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        NodeSpan::Node(*state)
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        let mut entt = world.entity_mut(*state);
+        entt.remove_parent();
+        entt.despawn();
+    }
+}
+
+impl Clone for SpatialElement {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl PartialEq for SpatialElement {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spatial_element_children() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let view = SpatialElement::new()
+            .insert(Transform::from_xyz(1., 2., 3.))
+            .children((SpatialElement::new(), SpatialElement::new()));
+        let mut state = view.build(&mut bc);
+        let NodeSpan::Node(parent) = view.assemble(&mut bc, &mut state) else {
+            panic!("Expected a single node");
+        };
+        assert!(bc.world.get::<Node>(parent).is_none());
+        assert_eq!(
+            bc.world.get::<Transform>(parent).unwrap().translation,
+            Vec3::new(1., 2., 3.)
+        );
+        assert_eq!(bc.world.get::<Children>(parent).unwrap().len(), 2);
+    }
+}
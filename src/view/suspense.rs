@@ -0,0 +1,282 @@
+use std::cell::Cell;
+
+use bevy::ecs::world::World;
+
+use crate::{BuildContext, TaskState, View};
+
+use crate::node_span::NodeSpan;
+
+/// State for a [`Suspense`] view.
+pub enum SuspenseState<V: View, E: View> {
+    /// The task is still running, and the fallback is displayed.
+    Pending(E::State),
+    /// The task is complete. The content view is kept so that it can be razed later.
+    Ready(V, V::State),
+}
+
+/// A view which displays `fallback` while an asynchronous task started by
+/// [`Cx::use_task`](crate::Cx::use_task) is running, and then displays the content produced from
+/// the task's result.
+///
+/// ```ignore
+/// let config = cx.use_task(|| async { load_config() }, ());
+/// Suspense::new(config, "Loading...", |config| config.title)
+/// ```
+pub struct Suspense<V: View, E: View> {
+    ready: bool,
+    content: Cell<Option<V>>,
+    fallback: E,
+}
+
+impl<V: View, E: View> Suspense<V, E> {
+    /// Construct a new `Suspense`. If `state` is ready, `content` is called with the result to
+    /// produce the view to display; otherwise `fallback` is displayed.
+    pub fn new<T>(state: TaskState<T>, fallback: E, content: impl FnOnce(T) -> V) -> Self {
+        let content = match state {
+            TaskState::Pending => None,
+            TaskState::Ready(value) => Some(content(value)),
+        };
+        Self {
+            ready: content.is_some(),
+            content: Cell::new(content),
+            fallback,
+        }
+    }
+}
+
+impl<V: View, E: View> View for Suspense<V, E> {
+    type State = SuspenseState<V, E>;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        match state {
+            SuspenseState::Pending(ref fallback_state) => self.fallback.nodes(bc, fallback_state),
+            SuspenseState::Ready(ref view, ref content_state) => view.nodes(bc, content_state),
+        }
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        match self.content.take() {
+            Some(view) => {
+                let content_state = view.build(bc);
+                SuspenseState::Ready(view, content_state)
+            }
+            None => SuspenseState::Pending(self.fallback.build(bc)),
+        }
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        // The content view is moved into the state, so it can only be consumed once.
+        let Some(view) = self.content.take() else {
+            if !self.ready {
+                match state {
+                    SuspenseState::Pending(ref mut fallback_state) => {
+                        self.fallback.update(bc, fallback_state)
+                    }
+                    SuspenseState::Ready(..) => {
+                        // The task was restarted, so go back to the fallback.
+                        self.raze(bc.world, state);
+                        bc.mark_changed_shape();
+                        *state = SuspenseState::Pending(self.fallback.build(bc));
+                    }
+                }
+            }
+            return;
+        };
+
+        match state {
+            SuspenseState::Ready(ref mut prev_view, ref mut content_state) => {
+                view.update(bc, content_state);
+                *prev_view = view;
+            }
+            SuspenseState::Pending(_) => {
+                self.raze(bc.world, state);
+                bc.mark_changed_shape();
+                let content_state = view.build(bc);
+                *state = SuspenseState::Ready(view, content_state);
+            }
+        }
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        match state {
+            SuspenseState::Pending(ref mut fallback_state) => {
+                self.fallback.assemble(bc, fallback_state)
+            }
+            SuspenseState::Ready(ref view, ref mut content_state) => {
+                view.assemble(bc, content_state)
+            }
+        }
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        match state {
+            SuspenseState::Pending(ref mut fallback_state) => {
+                self.fallback.raze(world, fallback_state)
+            }
+            SuspenseState::Ready(ref view, ref mut content_state) => {
+                view.raze(world, content_state)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+        task::{Context, Poll, Waker},
+    };
+
+    use bevy::{
+        ecs::system::RunSystemOnce,
+        prelude::*,
+        tasks::{AsyncComputeTaskPool, TaskPool},
+    };
+
+    use super::*;
+    use crate::{
+        plugin::render_views,
+        presenter_state::raze_removed_views,
+        task::{poll_tasks, PendingTask},
+        test_utils::texts,
+        Cx, ViewHandle,
+    };
+
+    /// A future which completes when it is opened by the test.
+    #[derive(Clone, Default)]
+    struct Gate(Arc<(AtomicBool, Mutex<Option<Waker>>)>);
+
+    impl Gate {
+        fn open(&self) {
+            self.0 .0.store(true, Ordering::SeqCst);
+            if let Some(waker) = self.0 .1.lock().unwrap().take() {
+                waker.wake();
+            }
+        }
+    }
+
+    impl Future for Gate {
+        type Output = &'static str;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            *self.0 .1.lock().unwrap() = Some(cx.waker().clone());
+            if self.0 .0.load(Ordering::SeqCst) {
+                Poll::Ready("opened")
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    /// Sets a flag when dropped, to detect when a task's future has been dropped.
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[derive(Resource, Clone)]
+    struct Source(Gate, Arc<AtomicBool>);
+
+    fn wait_for_tasks(world: &mut World) {
+        let mut q = world.query::<&PendingTask>();
+        for _ in 0..1000 {
+            world.run_system_once(poll_tasks);
+            if q.iter(world).next().is_none() {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        panic!("Task did not complete");
+    }
+
+    fn ready_presenter(mut cx: Cx) -> impl View {
+        let state = cx.use_task(|| async { 42 }, ());
+        Suspense::new(state, "Loading", |value| format!("Value: {}", value))
+    }
+
+    fn gated_presenter(mut cx: Cx) -> impl View {
+        let Source(gate, dropped) = cx.use_resource::<Source>().clone();
+        let flag = DropFlag(dropped);
+        let state = cx.use_task(
+            move || async move {
+                let _flag = flag;
+                gate.await
+            },
+            (),
+        );
+        Suspense::new(state, "Loading", |value| value)
+    }
+
+    #[test]
+    fn test_ready_future() {
+        AsyncComputeTaskPool::get_or_init(TaskPool::default);
+        let mut world = World::new();
+        world.spawn(ViewHandle::new(ready_presenter, ()));
+        render_views(&mut world);
+        world.clear_trackers();
+        wait_for_tasks(&mut world);
+        render_views(&mut world);
+        assert_eq!(texts(&mut world), vec!["Value: 42"]);
+    }
+
+    #[test]
+    fn test_pending_future() {
+        AsyncComputeTaskPool::get_or_init(TaskPool::default);
+        let mut world = World::new();
+        let gate = Gate::default();
+        let dropped = Arc::new(AtomicBool::new(false));
+        world.insert_resource(Source(gate.clone(), dropped.clone()));
+        world.spawn(ViewHandle::new(gated_presenter, ()));
+        render_views(&mut world);
+        world.clear_trackers();
+        assert_eq!(texts(&mut world), vec!["Loading"]);
+
+        // The fallback remains until the future completes.
+        world.run_system_once(poll_tasks);
+        render_views(&mut world);
+        world.clear_trackers();
+        assert_eq!(texts(&mut world), vec!["Loading"]);
+
+        gate.open();
+        wait_for_tasks(&mut world);
+        render_views(&mut world);
+        assert_eq!(texts(&mut world), vec!["opened"]);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_cancel_on_raze() {
+        AsyncComputeTaskPool::get_or_init(TaskPool::default);
+        let mut app = App::new();
+        app.add_systems(Update, (raze_removed_views, render_views).chain());
+        let dropped = Arc::new(AtomicBool::new(false));
+        app.insert_resource(Source(Gate::default(), dropped.clone()));
+        let root = app.world.spawn(ViewHandle::new(gated_presenter, ())).id();
+        app.update();
+        assert_eq!(texts(&mut app.world), vec!["Loading"]);
+        assert!(!dropped.load(Ordering::SeqCst));
+
+        // Razing the presenter drops the task, and with it the future.
+        app.world.entity_mut(root).despawn_recursive();
+        app.update();
+        assert_eq!(
+            app.world.query::<&PendingTask>().iter(&app.world).count(),
+            0
+        );
+        for _ in 0..1000 {
+            if dropped.load(Ordering::SeqCst) {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert!(dropped.load(Ordering::SeqCst), "Task was not cancelled");
+    }
+}
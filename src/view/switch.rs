@@ -0,0 +1,162 @@
+use bevy::ecs::world::World;
+
+use crate::{AnyView, BuildContext, View};
+
+use crate::node_span::NodeSpan;
+
+type ViewFactory = Box<dyn Fn() -> AnyView + Send>;
+
+/// A conditional view which renders one of several children depending on the value of a key.
+/// Only the matching case is built; when the key changes so that a different case matches,
+/// the previous case is razed and the new one is built from scratch.
+///
+/// Each case is stored as a type-erased [`AnyView`], so the cases need not be the same type.
+pub struct Switch<K: PartialEq + Send> {
+    key: K,
+    cases: Vec<(K, ViewFactory)>,
+    fallback: Option<ViewFactory>,
+}
+
+impl<K: PartialEq + Send> Switch<K> {
+    /// Construct a new Switch View, which selects among its cases using `key`.
+    pub fn new(key: K) -> Self {
+        Self {
+            key,
+            cases: Vec::new(),
+            fallback: None,
+        }
+    }
+
+    /// Add a case which is rendered when the key is equal to `value`. If more than one case
+    /// matches, the first one wins.
+    pub fn case<V: View + 'static, F: Fn() -> V + Send + 'static>(
+        mut self,
+        value: K,
+        factory: F,
+    ) -> Self
+    where
+        V::State: 'static,
+    {
+        self.cases
+            .push((value, Box::new(move || AnyView::new(factory()))));
+        self
+    }
+
+    /// Set the view which is rendered when no case matches. If there is no fallback, then
+    /// nothing is rendered.
+    pub fn fallback<V: View + 'static, F: Fn() -> V + Send + 'static>(mut self, factory: F) -> Self
+    where
+        V::State: 'static,
+    {
+        self.fallback = Some(Box::new(move || AnyView::new(factory())));
+        self
+    }
+
+    /// Returns the index of the matching case, or `cases.len()` for the fallback.
+    fn selected(&self) -> usize {
+        self.cases
+            .iter()
+            .position(|(value, _)| *value == self.key)
+            .unwrap_or(self.cases.len())
+    }
+
+    fn view(&self, index: usize) -> AnyView {
+        match self.cases.get(index) {
+            Some((_, factory)) => factory(),
+            None => match self.fallback {
+                Some(ref factory) => factory(),
+                None => AnyView::new(()),
+            },
+        }
+    }
+}
+
+impl<K: PartialEq + Send> View for Switch<K> {
+    /// Index of the selected case, and the state of the view for that case.
+    type State = (usize, <AnyView as View>::State);
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        state.1.nodes(bc)
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let index = self.selected();
+        (index, self.view(index).build(bc))
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        let index = self.selected();
+        let view = self.view(index);
+        if index == state.0 {
+            // Mutate state in place
+            view.update(bc, &mut state.1);
+        } else {
+            // Despawn old state and construct new state
+            view.raze(bc.world, &mut state.1);
+            bc.mark_changed_shape();
+            *state = (index, view.build(bc));
+        }
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        state.1.assemble(bc)
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        state.1.raze(world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+
+    fn switch(key: i32) -> Switch<i32> {
+        Switch::new(key)
+            .case(0, || "zero".to_string())
+            .case(1, || "one".to_string())
+            .case(2, || "two".to_string())
+    }
+
+    fn text(bc: &BuildContext, nodes: &NodeSpan) -> Vec<String> {
+        let mut entities = Vec::new();
+        nodes.flatten(&mut entities);
+        entities
+            .iter()
+            .map(|e| bc.world.get::<Text>(*e).unwrap().sections[0].value.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_switch() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let mut state = switch(0).build(&mut bc);
+        assert_eq!(text(&bc, &switch(0).nodes(&bc, &state)), vec!["zero"]);
+        let NodeSpan::Node(zero) = switch(0).nodes(&bc, &state) else {
+            panic!("Expected a single node");
+        };
+
+        // Switching to another case despawns the old one, even if the view type is the same.
+        switch(1).update(&mut bc, &mut state);
+        assert_eq!(text(&bc, &switch(1).nodes(&bc, &state)), vec!["one"]);
+        assert!(bc.world.get_entity(zero).is_none());
+
+        switch(2).update(&mut bc, &mut state);
+        assert_eq!(text(&bc, &switch(2).nodes(&bc, &state)), vec!["two"]);
+
+        // No matching case and no fallback renders nothing.
+        switch(3).update(&mut bc, &mut state);
+        assert_eq!(switch(3).nodes(&bc, &state).count(), 0);
+        let mut q = bc.world.query::<&Text>();
+        assert_eq!(q.iter(bc.world).count(), 0);
+
+        let fallback = switch(3).fallback(|| "other".to_string());
+        fallback.update(&mut bc, &mut state);
+        assert_eq!(text(&bc, &fallback.nodes(&bc, &state)), vec!["other"]);
+    }
+}
@@ -0,0 +1,47 @@
+use std::any::Any;
+
+use bevy::{
+    ecs::{component::Component, entity::Entity, system::Commands, system::Query},
+    tasks::{block_on, poll_once, Task},
+};
+
+use super::atom::AtomCell;
+
+/// The state of an asynchronous task started by [`Cx::use_task`](crate::Cx::use_task).
+#[derive(Clone, Debug, PartialEq)]
+pub enum TaskState<T> {
+    /// The task is still running.
+    Pending,
+    /// The task has completed with the given result.
+    Ready(T),
+}
+
+type PollFn = Box<dyn FnMut() -> Option<Box<dyn Any + Send + Sync>> + Send + Sync>;
+
+/// Component which holds a running task. This is stored on the same entity as the atom which
+/// will receive the result; replacing or despawning it cancels the task.
+#[derive(Component)]
+pub(crate) struct PendingTask(PollFn);
+
+impl PendingTask {
+    pub(crate) fn new<T: Clone + Send + Sync + 'static>(mut task: Task<T>) -> Self {
+        Self(Box::new(move || {
+            block_on(poll_once(&mut task))
+                .map(|result| Box::new(TaskState::Ready(result)) as Box<dyn Any + Send + Sync>)
+        }))
+    }
+}
+
+/// System which polls running tasks, and writes the result of any completed tasks to the
+/// associated atom.
+pub(crate) fn poll_tasks(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut PendingTask, &mut AtomCell)>,
+) {
+    for (entity, mut task, mut cell) in query.iter_mut() {
+        if let Some(result) = (task.0)() {
+            cell.0 = result;
+            commands.entity(entity).remove::<PendingTask>();
+        }
+    }
+}
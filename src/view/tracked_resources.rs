@@ -1,9 +1,31 @@
-use std::marker::PhantomData;
+use std::{
+    any::{Any, TypeId},
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
 
-use bevy::ecs::{component::Component, system::Resource, world::World};
+use bevy::{
+    asset::{AssetServer, LoadState, UntypedAssetId},
+    ecs::{
+        component::{Component, ComponentId, Tick},
+        entity::Entity,
+        event::{Event, Events, ManualEventReader},
+        query::{QueryFilter, QueryState, ReadOnlyQueryData},
+        system::Resource,
+        world::World,
+    },
+    utils::HashMap,
+};
+
+use super::atom::AtomCell;
 
 pub trait AnyResource: Send + Sync {
     fn is_changed(&self, world: &World) -> bool;
+
+    /// Return the type name of the tracker, for debugging.
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
 }
 
 #[derive(PartialEq, Eq)]
@@ -26,6 +48,173 @@ where
     }
 }
 
+/// A query state which is shared by every use of the same query type.
+pub(crate) type SharedQueryState<D, F> = Arc<Mutex<QueryState<(Entity, D), F>>>;
+
+/// Query states used by [`Cx::use_query`](crate::Cx::use_query), keyed by the type of the query,
+/// so that each one is only created once rather than every time a presenter runs.
+#[derive(Resource, Default)]
+pub(crate) struct QueryStateCache(HashMap<TypeId, Box<dyn Any + Send + Sync>>);
+
+impl QueryStateCache {
+    /// Return the cached state for a query, creating it if needed.
+    pub(crate) fn get<D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static>(
+        world: &mut World,
+    ) -> SharedQueryState<D, F> {
+        let key = TypeId::of::<QueryState<(Entity, D), F>>();
+        if let Some(state) = world
+            .get_resource::<QueryStateCache>()
+            .and_then(|cache| cache.0.get(&key))
+            .and_then(|state| state.downcast_ref::<SharedQueryState<D, F>>())
+        {
+            return state.clone();
+        }
+        let state = Arc::new(Mutex::new(QueryState::new(world)));
+        world
+            .get_resource_or_insert_with(QueryStateCache::default)
+            .0
+            .insert(key, Box::new(state.clone()));
+        state
+    }
+}
+
+/// Tracks the results of a query. This is considered changed if the set of matching entities
+/// changes, or if any of the components read by the query have changed.
+pub struct TrackedQuery<D: ReadOnlyQueryData, F: QueryFilter> {
+    state: SharedQueryState<D, F>,
+    reads: Vec<ComponentId>,
+    entities: Vec<Entity>,
+    tick: Tick,
+}
+
+impl<D: ReadOnlyQueryData, F: QueryFilter> TrackedQuery<D, F> {
+    pub(crate) fn new(state: SharedQueryState<D, F>, entities: Vec<Entity>, tick: Tick) -> Self {
+        let reads = state
+            .lock()
+            .unwrap()
+            .component_access()
+            .access()
+            .reads()
+            .collect();
+        Self {
+            state,
+            reads,
+            entities,
+            tick,
+        }
+    }
+}
+
+impl<D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static> AnyResource for TrackedQuery<D, F> {
+    fn is_changed(&self, world: &World) -> bool {
+        let this_run = world.read_change_tick();
+        let mut state = self.state.lock().unwrap();
+        state.update_archetypes(world);
+        let mut count: usize = 0;
+        for (entity, _) in state.iter_manual(world) {
+            if self.entities.get(count) != Some(&entity) {
+                return true;
+            }
+            count += 1;
+            let entt = world.entity(entity);
+            if self.reads.iter().any(|cid| {
+                entt.get_change_ticks_by_id(*cid)
+                    .map(|ct| ct.is_changed(self.tick, this_run))
+                    .unwrap_or(false)
+            }) {
+                return true;
+            }
+        }
+        count != self.entities.len()
+    }
+}
+
+/// Tracks a projection of the value of an atom. This is considered changed only if the
+/// selected value differs from the value at the time it was read.
+pub struct TrackedAtomSelector<T, S, F: Fn(&T) -> S> {
+    atom: Entity,
+    select: F,
+    value: S,
+    marker: PhantomData<fn(&T)>,
+}
+
+impl<T, S, F: Fn(&T) -> S> TrackedAtomSelector<T, S, F> {
+    pub(crate) fn new(atom: Entity, select: F, value: S) -> Self {
+        Self {
+            atom,
+            select,
+            value,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, S, F> AnyResource for TrackedAtomSelector<T, S, F>
+where
+    T: Send + Sync + 'static,
+    S: PartialEq + Send + Sync,
+    F: Fn(&T) -> S + Send + Sync,
+{
+    fn is_changed(&self, world: &World) -> bool {
+        world
+            .get::<AtomCell>(self.atom)
+            .and_then(|cell| cell.0.downcast_ref::<T>())
+            .map(|value| (self.select)(value) != self.value)
+            .unwrap_or(false)
+    }
+}
+
+/// Tracks the load state of an asset. This is considered changed when the state reported by
+/// the [`AssetServer`] differs from the state at the time it was read.
+pub struct TrackedLoadState {
+    id: UntypedAssetId,
+    state: LoadState,
+}
+
+impl TrackedLoadState {
+    pub(crate) fn new(id: UntypedAssetId, state: LoadState) -> Self {
+        Self { id, state }
+    }
+}
+
+impl AnyResource for TrackedLoadState {
+    fn is_changed(&self, world: &World) -> bool {
+        world
+            .get_resource::<AssetServer>()
+            .and_then(|server| server.get_load_state(self.id))
+            .unwrap_or(LoadState::NotLoaded)
+            != self.state
+    }
+}
+
+/// Component which holds the position of a presenter's reader within an event queue, created
+/// by [`Cx::use_event`](crate::Cx::use_event).
+#[derive(Component)]
+pub(crate) struct EventCursor<E: Event>(pub(crate) ManualEventReader<E>);
+
+/// Tracks an event queue. This is considered changed if any events have been sent since the
+/// tracker was created.
+pub struct TrackedEvents<E: Event> {
+    reader: ManualEventReader<E>,
+}
+
+impl<E: Event> TrackedEvents<E> {
+    /// Create a tracker which is positioned after all current events.
+    pub(crate) fn new(events: &Events<E>) -> Self {
+        let mut reader = ManualEventReader::default();
+        reader.clear(events);
+        Self { reader }
+    }
+}
+
+impl<E: Event> AnyResource for TrackedEvents<E> {
+    fn is_changed(&self, world: &World) -> bool {
+        world
+            .get_resource::<Events<E>>()
+            .is_some_and(|events| !self.reader.is_empty(events))
+    }
+}
+
 /// List of resources used by a presenter.
 pub(crate) type TrackedResourceList = Vec<Box<dyn AnyResource>>;
 
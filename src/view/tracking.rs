@@ -1,15 +1,69 @@
-use crate::tracked_resources::TrackedResourceList;
+use crate::{tracked_resources::TrackedResourceList, CleanupFn};
 use bevy::{
     ecs::component::{ComponentId, Tick},
     prelude::*,
     utils::HashSet,
 };
+use std::{any::TypeId, panic::Location};
 
+#[derive(Default)]
 pub(crate) struct TrackingContext {
     pub(crate) resources: TrackedResourceList,
+    /// Types of the resources in `resources` which are tracked with a
+    /// [`TrackedResource`](crate::tracked_resources::TrackedResource), used to avoid adding
+    /// the same resource more than once.
+    pub(crate) resource_types: HashSet<TypeId>,
     pub(crate) components: HashSet<(Entity, ComponentId)>,
     pub(crate) next_entity_index: usize,
     pub(crate) owned_entities: Vec<Entity>,
+    /// Call sites of the hooks which allocated each of the `owned_entities`, as of the first
+    /// render in which they were called. Used to detect hooks which are called conditionally
+    /// or out of order.
+    pub(crate) hook_locations: Vec<&'static Location<'static>>,
+    /// Type name of the presenter, for diagnostics.
+    pub(crate) presenter_name: &'static str,
+    /// Whether owned entities of hooks which were not called during this render should be
+    /// despawned at the end of it. See
+    /// [`Cx::release_unused_entities`](crate::Cx::release_unused_entities).
+    pub(crate) release_unused: bool,
+}
+
+impl TrackingContext {
+    /// Check that the hook which allocated owned entity `index` was called from the same
+    /// location as on previous renders. A mismatch means that hooks have been called
+    /// conditionally, so the entity may hold state of the wrong type. This panics in debug
+    /// builds, and logs an error otherwise.
+    pub(crate) fn check_hook_location(&mut self, index: usize, location: &'static Location) {
+        match self.hook_locations.get(index) {
+            Some(prev) if *prev != location => {
+                let message = format!(
+                    "Hook order changed in presenter `{}`: hook #{} was called from {}, but \
+                    was previously called from {}. Hooks must be called unconditionally and in \
+                    the same order each time the presenter runs.",
+                    self.presenter_name, index, location, prev
+                );
+                if cfg!(debug_assertions) {
+                    panic!("{}", message);
+                }
+                error!("{}", message);
+            }
+            Some(_) => {}
+            None => self.hook_locations.push(location),
+        }
+    }
+
+    /// Despawn the owned entities of hooks which were not called during this render. Since
+    /// entities are assigned to hooks in call order, these are always at the end of the list.
+    pub(crate) fn release_unused_entities(&mut self, world: &mut World) {
+        let used = self.next_entity_index;
+        if used < self.owned_entities.len() {
+            for entity in self.owned_entities.drain(used..) {
+                EffectCleanup::run(world, entity);
+                world.despawn(entity);
+            }
+        }
+        self.hook_locations.truncate(used);
+    }
 }
 
 /// Tracks components used by each View tree entity
@@ -22,3 +76,18 @@ pub(crate) struct TrackedComponents {
 /// Tracks entities which were explicitly allocated by a presenter.
 #[derive(Component, Default)]
 pub(crate) struct OwnedEntities(pub(crate) Vec<Entity>);
+
+/// Cleanup function for an effect created by
+/// [`Cx::use_effect_with_cleanup`](crate::Cx::use_effect_with_cleanup), stored on the entity
+/// which holds the effect's deps.
+#[derive(Component)]
+pub(crate) struct EffectCleanup(pub(crate) CleanupFn);
+
+impl EffectCleanup {
+    /// Run the cleanup function stored on `entity`, if any.
+    pub(crate) fn run(world: &mut World, entity: Entity) {
+        if let Some(cleanup) = world.entity_mut(entity).take::<EffectCleanup>() {
+            (cleanup.0)(world);
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::{
+    enter_exit::TransitionItem, BuildContext, ElementClasses, ElementStyles, EnterExit,
+    EnterExitState, EnterExitTimer, StyleHandle, StyleTuple, View,
+};
+
+use crate::node_span::NodeSpan;
+
+/// A keyed list view which animates items as they are added and removed. Each item is wrapped
+/// in an element whose class names follow the state of an enter / exit transition (see
+/// [`EnterExitState::as_class_name`]), so styles with transitions can be used to animate the
+/// item's opacity or transform. When an item is removed from the list, it is kept until its
+/// exit transition is complete, and then razed.
+pub struct TransitionGroup<
+    Item: Send + Clone,
+    Key: Send + PartialEq,
+    V: View,
+    K: Fn(&Item) -> Key + Send,
+    F: Fn(&Item) -> V + Send,
+> {
+    items: Vec<Item>,
+    keyof: K,
+    each: F,
+    delay: f32,
+    item_styles: Vec<StyleHandle>,
+    key: PhantomData<Key>,
+}
+
+impl<
+        Item: Send + Clone,
+        Key: Send + PartialEq,
+        V: View,
+        K: Fn(&Item) -> Key + Send,
+        F: Fn(&Item) -> V + Send,
+    > TransitionGroup<Item, Key, V, K, F>
+{
+    /// Construct a new `TransitionGroup`. The `keyof` function is used to match items with the
+    /// items of the previous update, and `each` generates the view for each item.
+    pub fn new(items: &[Item], keyof: K, each: F) -> Self {
+        Self {
+            items: Vec::from(items),
+            keyof,
+            each,
+            delay: 0.3,
+            item_styles: Vec::new(),
+            key: PhantomData,
+        }
+    }
+
+    /// Set the duration of the enter and exit transitions, in seconds. The default is 0.3.
+    pub fn delay(mut self, delay: f32) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Set the styles of the element which wraps each item.
+    pub fn item_styled<S: StyleTuple>(mut self, styles: S) -> Self {
+        self.item_styles = styles.to_vec();
+        self
+    }
+
+    fn build_entry(&self, bc: &mut BuildContext, item: &Item) -> TransitionEntry<Key, V> {
+        let mut classes = ElementClasses::default();
+        classes.add_class(EnterExitState::Exited.as_class_name());
+        let mut wrapper = bc.world.spawn((
+            NodeBundle {
+                visibility: Visibility::Visible,
+                ..default()
+            },
+            Name::new("transition-item"),
+            EnterExit {
+                open: true,
+                delay: self.delay,
+                ..default()
+            },
+            EnterExitTimer::default(),
+            TransitionItem { owner: bc.entity },
+            classes,
+        ));
+        if !self.item_styles.is_empty() {
+            wrapper.insert(ElementStyles::new(&self.item_styles));
+        }
+        let wrapper = wrapper.id();
+
+        let prev_parent = bc.parent.replace(wrapper);
+        let view = (self.each)(item);
+        let state = view.build(bc);
+        bc.parent = prev_parent;
+        TransitionEntry {
+            key: (self.keyof)(item),
+            wrapper,
+            view,
+            state,
+            exiting: false,
+        }
+    }
+
+    fn update_entry(
+        &self,
+        bc: &mut BuildContext,
+        item: &Item,
+        entry: &mut TransitionEntry<Key, V>,
+    ) {
+        let prev_parent = bc.parent.replace(entry.wrapper);
+        let view = (self.each)(item);
+        view.update(bc, &mut entry.state);
+        entry.view = view;
+        bc.parent = prev_parent;
+    }
+
+    /// Start the exit transition for an item which has been removed. Returns the entry if it
+    /// should be kept, or `None` if it has finished exiting and has been razed.
+    fn exit_entry(
+        world: &mut World,
+        mut entry: TransitionEntry<Key, V>,
+    ) -> Option<TransitionEntry<Key, V>> {
+        if !entry.exiting {
+            entry.exiting = true;
+            if let Some(mut ee) = world.get_mut::<EnterExit>(entry.wrapper) {
+                ee.open = false;
+            }
+            return Some(entry);
+        }
+        let exited = world
+            .get::<EnterExit>(entry.wrapper)
+            .is_none_or(|ee| ee.state == EnterExitState::Exited);
+        if exited {
+            entry.raze(world);
+            None
+        } else {
+            Some(entry)
+        }
+    }
+}
+
+/// An item in a [`TransitionGroup`], which may be in the process of exiting.
+pub struct TransitionEntry<Key, V: View> {
+    key: Key,
+    wrapper: Entity,
+    view: V,
+    state: V::State,
+    exiting: bool,
+}
+
+impl<Key, V: View> TransitionEntry<Key, V> {
+    fn raze(&mut self, world: &mut World) {
+        self.view.raze(world, &mut self.state);
+        let mut entt = world.entity_mut(self.wrapper);
+        entt.remove_parent();
+        entt.despawn();
+    }
+}
+
+impl<
+        Item: Send + Clone,
+        Key: Send + PartialEq,
+        V: View,
+        K: Fn(&Item) -> Key + Send,
+        F: Fn(&Item) -> V + Send,
+    > View for TransitionGroup<Item, Key, V, K, F>
+{
+    type State = Vec<TransitionEntry<Key, V>>;
+
+    fn nodes(&self, _bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        NodeSpan::Fragment(
+            state
+                .iter()
+                .map(|entry| NodeSpan::Node(entry.wrapper))
+                .collect(),
+        )
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        self.items
+            .iter()
+            .map(|item| self.build_entry(bc, item))
+            .collect()
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        let prev_wrappers: Vec<Entity> = state.iter().map(|entry| entry.wrapper).collect();
+        let mut prev: Vec<Option<TransitionEntry<Key, V>>> = state.drain(..).map(Some).collect();
+
+        // Match each item with the first unclaimed entry that has the same key and isn't
+        // already exiting.
+        let mut used = vec![false; prev.len()];
+        let matches: Vec<Option<usize>> = self
+            .items
+            .iter()
+            .map(|item| {
+                let key = (self.keyof)(item);
+                let index = (0..prev.len()).find(|j| {
+                    !used[*j]
+                        && prev[*j]
+                            .as_ref()
+                            .is_some_and(|entry| !entry.exiting && entry.key == key)
+                });
+                if let Some(j) = index {
+                    used[j] = true;
+                }
+                index
+            })
+            .collect();
+
+        // Unmatched entries are kept in their previous position relative to the matched ones.
+        let mut cursor = 0;
+        for (item, matched) in self.items.iter().zip(matches.iter()) {
+            match matched {
+                Some(i) => {
+                    if *i >= cursor {
+                        for j in cursor..*i {
+                            if !used[j] {
+                                let entry = prev[j].take().unwrap();
+                                state.extend(Self::exit_entry(bc.world, entry));
+                            }
+                        }
+                        cursor = i + 1;
+                    }
+                    let mut entry = prev[*i].take().unwrap();
+                    self.update_entry(bc, item, &mut entry);
+                    state.push(entry);
+                }
+                None => {
+                    let entry = self.build_entry(bc, item);
+                    state.push(entry);
+                }
+            }
+        }
+        for entry in prev.iter_mut().skip(cursor) {
+            if let Some(entry) = entry.take() {
+                state.extend(Self::exit_entry(bc.world, entry));
+            }
+        }
+
+        if !state.iter().map(|entry| entry.wrapper).eq(prev_wrappers) {
+            bc.mark_changed_shape();
+        }
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        for entry in state.iter_mut() {
+            let children = entry.view.assemble(bc, &mut entry.state);
+            let flat: Vec<Entity> = children.iter().collect();
+
+            let mut em = bc.entity_mut(entry.wrapper);
+            if em
+                .get::<Children>()
+                .is_none_or(|children| !children.eq(&flat))
+            {
+                em.replace_children(&flat);
+            }
+        }
+        self.nodes(bc, state)
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        for entry in state.iter_mut() {
+            entry.raze(world);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        enter_exit::enter_exit_state_machine, plugin::render_views, test_utils::texts, Cx,
+        ViewHandle,
+    };
+
+    #[derive(Resource, Default)]
+    struct Items(Vec<i32>);
+
+    fn list(cx: Cx) -> impl View {
+        let items = &cx.use_resource::<Items>().0;
+        TransitionGroup::new(items, |i| *i, |i| format!("{}", i)).delay(0.3)
+    }
+
+    fn step(app: &mut App, seconds: f32) {
+        app.world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(seconds));
+        app.update();
+    }
+
+    #[test]
+    fn test_transition_group() {
+        let mut app = App::new();
+        app.init_resource::<Time>();
+        app.insert_resource(Items(vec![1, 2, 3]));
+        app.add_systems(Update, (enter_exit_state_machine, render_views).chain());
+        app.world.spawn(ViewHandle::new(list, ()));
+        app.update();
+        assert_eq!(texts(&mut app.world), vec!["1", "2", "3"]);
+
+        // New items start out with the "exited" class, and then transition in.
+        let mut q = app
+            .world
+            .query_filtered::<(&Children, &ElementClasses), With<TransitionItem>>();
+        assert!(q
+            .iter(&app.world)
+            .all(|(_, classes)| classes.0.contains("exited")));
+        step(&mut app, 0.1);
+        assert!(q
+            .iter(&app.world)
+            .all(|(_, classes)| classes.0.contains("enter-start")));
+
+        // A removed item survives until its exit transition completes.
+        app.world.resource_mut::<Items>().0 = vec![1, 3];
+        let mut elapsed = 0.;
+        while texts(&mut app.world).len() == 3 {
+            assert!(elapsed < 1., "Item was not removed");
+            step(&mut app, 0.1);
+            elapsed += 0.1;
+        }
+        assert!(elapsed > 0.3);
+        assert_eq!(texts(&mut app.world), vec!["1", "3"]);
+        assert_eq!(q.iter(&app.world).count(), 2);
+    }
+}
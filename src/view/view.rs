@@ -5,14 +5,29 @@ use bevy::{
     text::{Text, TextStyle},
 };
 
-use crate::{presenter_state::*, ClassNames, Cx, StyleTuple, ViewHandle, ViewTuple};
+use crate::{
+    presenter_state::*, style::TextStyles, AtomHandle, ClassNames, Cx, ElementClasses, StyleTuple,
+    ViewHandle, ViewTuple,
+};
 
 use crate::node_span::NodeSpan;
 
 use super::{
-    bind::Bind, view_children::ViewChildren, view_classes::ViewClasses,
-    view_insert_bundle::ViewInsertBundle, view_named::ViewNamed, view_styled::ViewStyled,
-    view_with::ViewWith, view_with_memo::ViewWithMemo,
+    any_view::AnyView,
+    bind::Bind,
+    view_children::ViewChildren,
+    view_classes::ViewClasses,
+    view_classes_atom::ViewClassesAtom,
+    view_disabled::ViewDisabled,
+    view_insert_bundle::ViewInsertBundle,
+    view_lifecycle::{ViewOnMount, ViewOnUnmount},
+    view_named::ViewNamed,
+    view_on_key::ViewOnKey,
+    view_on_scroll::ViewOnScroll,
+    view_ref_out::{RefHandle, ViewRefOut},
+    view_styled::ViewStyled,
+    view_with::ViewWith,
+    view_with_memo::ViewWithMemo,
 };
 
 /// Passed to `build`, `update` and `raze` methods to give access to the world and the view entity.
@@ -21,11 +36,18 @@ pub struct BuildContext<'w> {
 
     /// The entity which contains the PresenterState.
     pub(crate) entity: Entity,
+
+    /// The display node that the output of the views being built will be attached to, if known.
+    pub(crate) parent: Option<Entity>,
 }
 
 impl<'w> BuildContext<'w> {
     pub(crate) fn new(world: &'w mut World, entity: Entity) -> Self {
-        Self { world, entity }
+        Self {
+            world,
+            entity,
+            parent: None,
+        }
     }
 
     /// Indicate that the shape of the display graph has changed.
@@ -43,7 +65,28 @@ impl<'w> BuildContext<'w> {
         BuildContext {
             world: &mut *self.world,
             entity,
+            parent: None,
+        }
+    }
+
+    /// Return the text style inherited from the nearest ancestor of the parent display node
+    /// which has text styles, or the default style if there is none.
+    pub(crate) fn inherited_text_style(&self) -> TextStyle {
+        let mut next = self.parent;
+        while let Some(entity) = next {
+            let entt = self.world.entity(entity);
+            if let Some(styles) = entt.get::<TextStyles>() {
+                let default_style = TextStyle::default();
+                return TextStyle {
+                    font: styles.font.clone().unwrap_or(default_style.font),
+                    font_size: styles.font_size.unwrap_or(default_style.font_size),
+                    // White is the default.
+                    color: styles.color.unwrap_or(Color::WHITE),
+                };
+            }
+            next = entt.get::<Parent>().map(|parent| parent.get());
         }
+        TextStyle::default()
     }
 
     pub(crate) fn entity(&self, entity: Entity) -> EntityRef {
@@ -99,6 +142,41 @@ where
         ViewClasses::new(self, class_names)
     }
 
+    /// Bind the class names for this View to an atom. Writing to the atom updates the classes
+    /// of the display entities directly, without re-rendering the presenter.
+    fn class_names_atom(self, class_names: AtomHandle<ElementClasses>) -> ViewClassesAtom<Self> {
+        ViewClassesAtom {
+            inner: self,
+            atom: class_names,
+        }
+    }
+
+    /// Enable or disable the display entities of this View. Disabled entities are given the
+    /// [`Disabled`](crate::Disabled) marker and the `disabled` class, and are made
+    /// non-interactive: pointer event listeners on them, or on their descendants, will not
+    /// fire. Keyboard handlers are not affected.
+    ///
+    /// This should be applied after [`class_names`](View::class_names), which would otherwise
+    /// replace the `disabled` class.
+    fn disabled(self, disabled: bool) -> ViewDisabled<Self> {
+        ViewDisabled {
+            inner: self,
+            disabled,
+        }
+    }
+
+    /// Record the display entity produced by this `View` into a [`RefHandle`], so that the
+    /// presenter which created the handle can access it.
+    ///
+    /// This method will panic if you call this on a view which produces more than one output
+    /// entity.
+    fn ref_out(self, handle: RefHandle) -> ViewRefOut<Self> {
+        ViewRefOut {
+            inner: self,
+            handle,
+        }
+    }
+
     /// Inserts a default instance of the specified component or bundle to the display entity.
     /// This insertion occurs only once per output entity. The entity takes ownership of the
     /// bundle.
@@ -122,6 +200,25 @@ where
         }
     }
 
+    /// Sets up a callback which is called once for each output entity when this `View` is first
+    /// built. Unlike [`with`](View::with), the callback is not called again when the view is
+    /// updated.
+    fn on_mount<F: Fn(EntityWorldMut) + Send>(self, callback: F) -> ViewOnMount<Self, F> {
+        ViewOnMount {
+            inner: self,
+            callback,
+        }
+    }
+
+    /// Sets up a callback which is called once for each output entity when this `View` is
+    /// razed. The callback is called before the output entities are despawned.
+    fn on_unmount<F: Fn(&mut World, Entity) + Send>(self, callback: F) -> ViewOnUnmount<Self, F> {
+        ViewOnUnmount {
+            inner: self,
+            callback,
+        }
+    }
+
     /// Sets up a callback which is called for each output UiNode generated by this `View`.
     /// Typically used to manipulate components on the entity. This callback is called when
     /// the view is first created, and then called again if either (a) the output entity
@@ -139,6 +236,46 @@ where
         }
     }
 
+    /// Run `handler` when `key` is pressed while the output entity, or one of its descendants,
+    /// has keyboard focus (as determined by the [`Focus`](bevy::a11y::Focus) resource). The
+    /// handler is passed the output entity, and is replaced each time the view is rebuilt, so it
+    /// can safely capture props.
+    fn on_key<F: Fn(Entity, &mut World) + Send + Sync + 'static>(
+        self,
+        key: KeyCode,
+        handler: F,
+    ) -> ViewOnKey<Self, F> {
+        ViewOnKey {
+            inner: self,
+            key,
+            handler: Arc::new(handler),
+        }
+    }
+
+    /// Run `handler` when the scroll position of the output entity, which should be a
+    /// [`ScrollArea`](crate::ScrollArea), changes. The handler is passed the output entity and
+    /// the new scroll position, and runs at most once per frame. Like
+    /// [`on_key`](View::on_key), it is replaced each time the view is rebuilt.
+    fn on_scroll<F: Fn(Entity, Vec2, &mut World) + Send + Sync + 'static>(
+        self,
+        handler: F,
+    ) -> ViewOnScroll<Self, F> {
+        ViewOnScroll {
+            inner: self,
+            handler: Arc::new(handler),
+        }
+    }
+
+    /// Convert this view into a type-erased [`AnyView`], so that it can be combined with
+    /// views of other types, for example in a `Vec<AnyView>`.
+    fn boxed(self) -> AnyView
+    where
+        Self: 'static,
+        Self::State: 'static,
+    {
+        AnyView::new(self)
+    }
+
     /// Sets up a callback which is called for each output UiNode, but only when the node is first
     /// created.
     fn children<A: ViewTuple>(self, items: A) -> ViewChildren<Self, A> {
@@ -161,6 +298,25 @@ impl View for () {
     fn raze(&self, _vc: &mut World, _state: &mut Self::State) {}
 }
 
+/// Replace the contents of a text node with a single section. The text component is only
+/// mutated if the text differs, and the style of the existing first section is retained, since
+/// it may have been set by inherited text styles.
+fn update_text(mut text: Mut<Text>, value: &str) {
+    if text.sections.len() == 1 && text.sections[0].value == value {
+        return;
+    }
+    let style = text
+        .sections
+        .first()
+        .map(|section| section.style.clone())
+        .unwrap_or_default();
+    text.sections.clear();
+    text.sections.push(TextSection {
+        value: value.to_string(),
+        style,
+    });
+}
+
 /// View which renders a String
 impl View for String {
     type State = Entity;
@@ -173,7 +329,7 @@ impl View for String {
         let id = bc
             .world
             .spawn((TextBundle {
-                text: Text::from_section(self.clone(), TextStyle { ..default() }),
+                text: Text::from_section(self.clone(), bc.inherited_text_style()),
                 // TextStyle {
                 //     font_size: 40.0,
                 //     color: Color::rgb(0.9, 0.9, 0.9),
@@ -192,13 +348,8 @@ impl View for String {
         // If it's a single node and has a text component
         let nodes = self.nodes(bc, state);
         if let NodeSpan::Node(text_node) = nodes {
-            if let Some(mut old_text) = bc.entity_mut(text_node).get_mut::<Text>() {
-                // TODO: compare text for equality.
-                old_text.sections.clear();
-                old_text.sections.push(TextSection {
-                    value: self.to_owned(),
-                    style: TextStyle { ..default() },
-                });
+            if let Some(old_text) = bc.entity_mut(text_node).get_mut::<Text>() {
+                update_text(old_text, self);
                 return;
             }
         }
@@ -228,7 +379,7 @@ impl View for &str {
         let id = bc
             .world
             .spawn((TextBundle {
-                text: Text::from_section(self.to_string(), TextStyle { ..default() }),
+                text: Text::from_section(self.to_string(), bc.inherited_text_style()),
                 // TextStyle {
                 //     font_size: 40.0,
                 //     color: Color::rgb(0.9, 0.9, 0.9),
@@ -247,13 +398,8 @@ impl View for &str {
         // If it's a single node and has a text component
         let nodes = self.nodes(bc, state);
         if let NodeSpan::Node(text_node) = nodes {
-            if let Some(mut old_text) = bc.entity_mut(text_node).get_mut::<Text>() {
-                // TODO: compare text for equality.
-                old_text.sections.clear();
-                old_text.sections.push(TextSection {
-                    value: self.to_string(),
-                    style: TextStyle { ..default() },
-                });
+            if let Some(old_text) = bc.entity_mut(text_node).get_mut::<Text>() {
+                update_text(old_text, self);
                 return;
             }
         }
@@ -381,3 +527,90 @@ impl<Inner: View + Sync> View for Arc<Inner> {
         self.as_ref().raze(bc, state)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{plugin::render_views, Element, If};
+
+    #[test]
+    fn test_text_inherited_style() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+        let view = |show: bool| Element::new().children((If::new(show, "Hello", ()),));
+        let mut state = view(false).build(&mut bc);
+        view(false).assemble(&mut bc, &mut state);
+        let parent = state.0;
+        bc.world.entity_mut(parent).insert(TextStyles {
+            font_size: Some(24.),
+            color: Some(Color::RED),
+            ..default()
+        });
+
+        // Text nodes which are added to a styled parent start out with the inherited style.
+        view(true).update(&mut bc, &mut state);
+        view(true).assemble(&mut bc, &mut state);
+        let child = bc.world.get::<Children>(parent).unwrap()[0];
+        let text = bc.world.get::<Text>(child).unwrap();
+        assert_eq!(text.sections[0].style.font_size, 24.);
+        assert_eq!(text.sections[0].style.color, Color::RED);
+    }
+
+    fn label(_cx: Cx) -> impl View {
+        "Hello"
+    }
+
+    fn panel(_cx: Cx) -> impl View {
+        Element::new()
+            .insert(TextStyles {
+                font_size: Some(24.),
+                ..default()
+            })
+            .children(label.bind(()))
+    }
+
+    #[test]
+    fn test_text_inherited_style_bound() {
+        let mut world = World::new();
+        let root = world.spawn(ViewHandle::new(panel, ())).id();
+        render_views(&mut world);
+
+        // The bound presenter's first build inherits from the element it will be attached to.
+        let NodeSpan::Node(parent) = world.get::<ViewHandle>(root).unwrap().nodes() else {
+            panic!("Expected a single node");
+        };
+        let child = world.get::<Children>(parent).unwrap()[0];
+        let text = world.get::<Text>(child).unwrap();
+        assert_eq!(text.sections[0].style.font_size, 24.);
+    }
+
+    #[test]
+    fn test_text_update_unchanged() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+        let mut state = "Hello".build(&mut bc);
+        bc.world.get_mut::<Text>(state).unwrap().sections[0]
+            .style
+            .font_size = 20.;
+        bc.world.clear_trackers();
+
+        // Same text: component is not mutated.
+        "Hello".update(&mut bc, &mut state);
+        String::from("Hello").update(&mut bc, &mut state);
+        assert!(!bc
+            .world
+            .entity(state)
+            .get_ref::<Text>()
+            .unwrap()
+            .is_changed());
+
+        // Different text: value is replaced, style is retained.
+        String::from("World").update(&mut bc, &mut state);
+        let text = bc.world.entity(state).get_ref::<Text>().unwrap();
+        assert!(text.is_changed());
+        assert_eq!(text.sections[0].value, "World");
+        assert_eq!(text.sections[0].style.font_size, 20.);
+    }
+}
@@ -13,6 +13,18 @@ pub struct ViewChildren<V: View, A: ViewTuple> {
     pub(crate) items: A,
 }
 
+impl<V: View, A: ViewTuple> ViewChildren<V, A> {
+    /// Set the parent node of the build context to the output of the inner view, so that
+    /// child views can inherit from it. Returns the previous parent.
+    fn enter_parent(&self, bc: &mut BuildContext, state: &V::State) -> Option<Entity> {
+        let parent = match self.inner.nodes(bc, state) {
+            NodeSpan::Node(entity) => Some(entity),
+            _ => None,
+        };
+        std::mem::replace(&mut bc.parent, parent)
+    }
+}
+
 impl<V: View, A: ViewTuple> View for ViewChildren<V, A> {
     type State = (V::State, A::State);
 
@@ -24,13 +36,17 @@ impl<V: View, A: ViewTuple> View for ViewChildren<V, A> {
         // Build state for inner view
         let st = self.inner.build(bc);
         // Build Views for each child element
+        let prev_parent = self.enter_parent(bc, &st);
         let ch = self.items.build_spans(bc);
+        bc.parent = prev_parent;
         (st, ch)
     }
 
     fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
         self.inner.update(bc, &mut state.0);
+        let prev_parent = self.enter_parent(bc, &state.0);
         self.items.update_spans(bc, &mut state.1);
+        bc.parent = prev_parent;
     }
 
     fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
@@ -38,8 +54,7 @@ impl<V: View, A: ViewTuple> View for ViewChildren<V, A> {
         let children = self.items.assemble_spans(bc, &mut state.1);
         if let NodeSpan::Node(parent) = nodes {
             // Attach child view outputs to parent.
-            let mut flat: Vec<Entity> = Vec::with_capacity(children.count());
-            children.flatten(&mut flat);
+            let flat: Vec<Entity> = children.iter().collect();
 
             let mut em = bc.entity_mut(parent);
             if let Some(children) = em.get::<Children>() {
@@ -0,0 +1,208 @@
+use bevy::ecs::{
+    change_detection::DetectChanges,
+    component::{Component, Tick},
+    entity::Entity,
+    world::World,
+};
+
+use crate::node_span::NodeSpan;
+use crate::{AtomHandle, AtomMethods, BuildContext, ElementClasses, View};
+
+use super::atom::AtomCell;
+
+/// Component which binds the [`ElementClasses`] of an entity to an atom. When the atom is
+/// written, the new classes are copied to the entity.
+#[derive(Component)]
+pub(crate) struct ClassNamesBinding {
+    atom: AtomHandle<ElementClasses>,
+    tick: Tick,
+}
+
+/// Copy the contents of an atom to the [`ElementClasses`] of an entity, only mutating the
+/// component if the classes differ.
+fn apply_classes(world: &mut World, entity: Entity, atom: AtomHandle<ElementClasses>) {
+    let classes = world.get_atom(atom);
+    let mut entt = world.entity_mut(entity);
+    match entt.get_mut::<ElementClasses>() {
+        Some(mut ec) => {
+            if *ec != classes {
+                *ec = classes;
+            }
+        }
+        None => {
+            entt.insert(classes);
+        }
+    }
+}
+
+/// Update the classes of any entities whose class atoms have been written since the last sync.
+pub(crate) fn sync_class_names(world: &mut World) {
+    let this_run = world.change_tick();
+    let mut q = world.query::<(Entity, &ClassNamesBinding)>();
+    let changed: Vec<(Entity, AtomHandle<ElementClasses>)> = q
+        .iter(world)
+        .filter(|(_, binding)| {
+            world
+                .get_entity(binding.atom.id)
+                .and_then(|atom| atom.get_ref::<AtomCell>())
+                .is_some_and(|cell| cell.last_changed().is_newer_than(binding.tick, this_run))
+        })
+        .map(|(e, binding)| (e, binding.atom))
+        .collect();
+    if changed.is_empty() {
+        return;
+    }
+    for (entity, atom) in changed.iter() {
+        apply_classes(world, *entity, *atom);
+    }
+    // Advance the tick so that writes made after this point are seen as changes.
+    let tick = world.increment_change_tick();
+    for (entity, _) in changed {
+        world.get_mut::<ClassNamesBinding>(entity).unwrap().tick = tick;
+    }
+}
+
+/// A wrapper view which binds the class names of the output of an inner view to an atom.
+/// Writing to the atom updates the classes directly, without rebuilding the view.
+pub struct ViewClassesAtom<V: View> {
+    pub(crate) inner: V,
+    pub(crate) atom: AtomHandle<ElementClasses>,
+}
+
+impl<V: View> ViewClassesAtom<V> {
+    fn bind_class_names(&self, nodes: &NodeSpan, bc: &mut BuildContext) {
+        match nodes {
+            NodeSpan::Empty => (),
+            NodeSpan::Node(entity) => {
+                let bound = bc
+                    .entity(*entity)
+                    .get::<ClassNamesBinding>()
+                    .is_some_and(|binding| binding.atom == self.atom);
+                if !bound {
+                    apply_classes(bc.world, *entity, self.atom);
+                    let tick = bc.world.increment_change_tick();
+                    bc.entity_mut(*entity).insert(ClassNamesBinding {
+                        atom: self.atom,
+                        tick,
+                    });
+                }
+            }
+            NodeSpan::Fragment(ref nodes) => {
+                for node in nodes.iter() {
+                    // Recurse
+                    self.bind_class_names(node, bc);
+                }
+            }
+        }
+    }
+}
+
+impl<V: View> View for ViewClassesAtom<V> {
+    type State = V::State;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        self.inner.nodes(bc, state)
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let state = self.inner.build(bc);
+        self.bind_class_names(&self.nodes(bc, &state), bc);
+        state
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        self.inner.update(bc, state);
+        self.bind_class_names(&self.nodes(bc, state), bc);
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        self.inner.assemble(bc, state)
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        self.inner.raze(world, state);
+    }
+}
+
+impl<V: View + Clone> Clone for ViewClassesAtom<V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            atom: self.atom,
+        }
+    }
+}
+
+impl<V: View + PartialEq> PartialEq for ViewClassesAtom<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner && self.atom == other.atom
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        hover::{HoverState, PreviousHoverState},
+        plugin::render_views,
+        update::{update_styles, PreviousFocus},
+        Cx, Element, StyleHandle, ViewHandle,
+    };
+    use bevy::{a11y::Focus, ecs::system::RunSystemOnce, prelude::*, utils::HashSet};
+
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Renders(usize);
+
+    fn presenter(cx: Cx<AtomHandle<ElementClasses>>) -> impl View {
+        cx.bc.world.resource_mut::<Renders>().0 += 1;
+        Element::new()
+            .styled(StyleHandle::build(|ss| {
+                ss.width(10.).selector(".wide", |ss| ss.width(20.))
+            }))
+            .class_names_atom(*cx.props)
+    }
+
+    #[test]
+    fn test_class_names_atom() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_resource::<HoverState>()
+            .init_resource::<PreviousHoverState>()
+            .init_resource::<Focus>()
+            .init_resource::<PreviousFocus>()
+            .init_resource::<Renders>();
+        let world = &mut app.world;
+        let classes = world.create_atom::<ElementClasses>();
+        world.set_atom(classes, ElementClasses::default());
+        let root = world.spawn(ViewHandle::new(presenter, classes)).id();
+        render_views(world);
+        world.run_system_once(update_styles);
+        let NodeSpan::Node(element) = world.get::<ViewHandle>(root).unwrap().nodes() else {
+            panic!("Expected a single node");
+        };
+        assert_eq!(world.get::<Style>(element).unwrap().width, Val::Px(10.));
+        world.clear_trackers();
+
+        // Writing the atom updates the classes and restyles without re-rendering.
+        world.set_atom(classes, ElementClasses(HashSet::from(["wide".to_string()])));
+        render_views(world);
+        world.run_system_once(update_styles);
+        assert_eq!(world.resource::<Renders>().0, 1);
+        assert!(world
+            .get::<ElementClasses>(element)
+            .unwrap()
+            .0
+            .contains("wide"));
+        assert_eq!(world.get::<Style>(element).unwrap().width, Val::Px(20.));
+        world.clear_trackers();
+
+        // No further writes: the classes are not touched.
+        render_views(world);
+        assert!(!world
+            .entity(element)
+            .get_ref::<ElementClasses>()
+            .unwrap()
+            .is_changed());
+    }
+}
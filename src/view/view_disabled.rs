@@ -0,0 +1,268 @@
+use bevy::prelude::*;
+#[cfg(feature = "picking")]
+use bevy_mod_picking::focus::HoverMap;
+
+use crate::{BuildContext, ElementClasses, View};
+
+use crate::node_span::NodeSpan;
+
+/// Class name which is added to disabled elements, so that themes can style them.
+pub const CLS_DISABLED: &str = "disabled";
+
+/// Marker component for elements which have been disabled with [`View::disabled`]. Disabled
+/// elements, and their descendants, are removed from the hover map, so pointer event
+/// listeners on them will not fire. The element's `Pickable` is left alone, so that it can
+/// still be controlled by styles.
+#[derive(Component, Default, Clone, Copy, Debug)]
+pub struct Disabled;
+
+/// An implementation of [`View`] which enables or disables the output entities of the inner
+/// view. See [`View::disabled`].
+pub struct ViewDisabled<V: View> {
+    pub(crate) inner: V,
+    pub(crate) disabled: bool,
+}
+
+impl<V: View> ViewDisabled<V> {
+    fn set_disabled(&self, nodes: &NodeSpan, bc: &mut BuildContext) {
+        for entity in nodes.iter() {
+            let mut em = bc.entity_mut(entity);
+            if self.disabled {
+                if !em.contains::<Disabled>() {
+                    em.insert(Disabled);
+                }
+                // Re-add the class on each update, in case it was replaced by `.class_names()`.
+                let has_class = em
+                    .get::<ElementClasses>()
+                    .is_some_and(|classes| classes.0.contains(CLS_DISABLED));
+                if !has_class {
+                    match em.get_mut::<ElementClasses>() {
+                        Some(mut classes) => classes.add_class(CLS_DISABLED),
+                        None => {
+                            let mut classes = ElementClasses::default();
+                            classes.add_class(CLS_DISABLED);
+                            em.insert(classes);
+                        }
+                    }
+                }
+            } else if em.contains::<Disabled>() {
+                em.remove::<Disabled>();
+                if let Some(mut classes) = em.get_mut::<ElementClasses>() {
+                    classes.remove_class(CLS_DISABLED);
+                }
+            }
+        }
+    }
+}
+
+impl<V: View> View for ViewDisabled<V> {
+    type State = V::State;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        self.inner.nodes(bc, state)
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let state = self.inner.build(bc);
+        self.set_disabled(&self.nodes(bc, &state), bc);
+        state
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        self.inner.update(bc, state);
+        self.set_disabled(&self.nodes(bc, state), bc);
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        self.inner.assemble(bc, state)
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        self.inner.raze(world, state);
+    }
+}
+
+/// System which removes disabled elements and their descendants from the hover map, after it
+/// has been computed by the picking plugin, and before pointer events are generated. This
+/// prevents events on the children of a disabled element from bubbling up to its listeners.
+#[cfg(feature = "picking")]
+pub(crate) fn block_disabled_hovers(
+    hover_map: Option<ResMut<HoverMap>>,
+    disabled: Query<(), With<Disabled>>,
+    parents: Query<&Parent>,
+) {
+    let Some(mut hover_map) = hover_map else {
+        return;
+    };
+    if disabled.is_empty() {
+        return;
+    }
+    for hovers in hover_map.values_mut() {
+        hovers.retain(|entity, _| {
+            !disabled.contains(*entity)
+                && !parents
+                    .iter_ancestors(*entity)
+                    .any(|ancestor| disabled.contains(ancestor))
+        });
+    }
+}
+
+#[cfg(all(test, feature = "picking"))]
+mod tests {
+    use bevy::render::camera::NormalizedRenderTarget;
+    use bevy_mod_picking::{
+        backend::{HitData, PointerHits},
+        events::{pointer_events, Click, Pointer},
+        focus::update_focus,
+        picking_core::{CorePlugin, InteractionPlugin},
+        pointer::{InputPress, Location, PointerButton, PointerId, PointerLocation},
+        prelude::{ListenerMut, On},
+        PointerBundle,
+    };
+
+    use super::*;
+    use crate::Element;
+
+    #[derive(Resource, Default)]
+    struct Clicks(usize);
+
+    fn button(disabled: bool) -> impl View {
+        Element::new()
+            .insert(On::<Pointer<Click>>::run(
+                |_: ListenerMut<Pointer<Click>>, mut clicks: ResMut<Clicks>| {
+                    clicks.0 += 1;
+                },
+            ))
+            .disabled(disabled)
+            .children(Element::new())
+    }
+
+    /// Simulate a click on `target`, as if it were the topmost entity under the pointer.
+    fn click(app: &mut App, target: Entity) -> usize {
+        let camera = app.world.spawn_empty().id();
+        let hit = || {
+            PointerHits::new(
+                PointerId::Mouse,
+                vec![(target, HitData::new(camera, 0., None, None))],
+                0.,
+            )
+        };
+        app.world.send_event(hit());
+        app.world.send_event(InputPress::new_down(
+            PointerId::Mouse,
+            PointerButton::Primary,
+        ));
+        app.update();
+        app.world.send_event(hit());
+        app.world
+            .send_event(InputPress::new_up(PointerId::Mouse, PointerButton::Primary));
+        app.update();
+        app.world.resource::<Clicks>().0
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            CorePlugin,
+            InteractionPlugin,
+        ))
+        .init_resource::<Clicks>()
+        .add_systems(
+            PreUpdate,
+            block_disabled_hovers
+                .after(update_focus)
+                .before(pointer_events),
+        );
+        app.world
+            .spawn(PointerBundle::new(PointerId::Mouse))
+            .insert(PointerLocation {
+                location: Some(Location {
+                    target: NormalizedRenderTarget::Image(Handle::default()),
+                    position: Vec2::ZERO,
+                }),
+            });
+        app
+    }
+
+    #[test]
+    fn test_disabled_click() {
+        let mut app = app();
+        let root = app.world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut app.world, root);
+        let mut state = button(false).build(&mut bc);
+        let nodes = button(false).assemble(&mut bc, &mut state);
+        let NodeSpan::Node(entity) = nodes else {
+            panic!("Expected a single node");
+        };
+        let child = app.world.get::<Children>(entity).unwrap()[0];
+        assert_eq!(click(&mut app, entity), 1);
+        assert_eq!(click(&mut app, child), 2);
+
+        // Disabled elements don't receive clicks, either directly or bubbled from a child.
+        let mut bc = BuildContext::new(&mut app.world, root);
+        button(true).update(&mut bc, &mut state);
+        assert!(app.world.get::<Disabled>(entity).is_some());
+        assert!(app
+            .world
+            .get::<ElementClasses>(entity)
+            .unwrap()
+            .0
+            .contains(CLS_DISABLED));
+        assert_eq!(click(&mut app, entity), 2);
+        assert_eq!(click(&mut app, child), 2);
+
+        // Re-enabling restores interaction.
+        let mut bc = BuildContext::new(&mut app.world, root);
+        button(false).update(&mut bc, &mut state);
+        assert!(app.world.get::<Disabled>(entity).is_none());
+        assert_eq!(click(&mut app, child), 3);
+    }
+
+    #[test]
+    fn test_disabled_restyled() {
+        use crate::{
+            hover::{HoverState, PreviousHoverState},
+            update::{update_styles, PreviousFocus},
+            PointerEvents, StyleHandle,
+        };
+        use bevy::{a11y::Focus, ecs::system::RunSystemOnce};
+        use bevy_mod_picking::prelude::Pickable;
+
+        fn styled_button(disabled: bool) -> impl View {
+            Element::new()
+                .styled(StyleHandle::build(|ss| {
+                    ss.pointer_events(PointerEvents::All)
+                }))
+                .insert(On::<Pointer<Click>>::run(
+                    |_: ListenerMut<Pointer<Click>>, mut clicks: ResMut<Clicks>| {
+                        clicks.0 += 1;
+                    },
+                ))
+                .disabled(disabled)
+        }
+
+        let mut app = app();
+        app.init_resource::<HoverState>()
+            .init_resource::<PreviousHoverState>()
+            .init_resource::<Focus>()
+            .init_resource::<PreviousFocus>();
+        let root = app.world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut app.world, root);
+        let mut state = styled_button(true).build(&mut bc);
+        let NodeSpan::Node(entity) = styled_button(true).assemble(&mut bc, &mut state) else {
+            panic!("Expected a single node");
+        };
+
+        // Styles control the element's `Pickable`, but don't re-enable it.
+        app.world.run_system_once(update_styles);
+        assert!(app.world.get::<Pickable>(entity).unwrap().is_hoverable);
+        assert_eq!(click(&mut app, entity), 0);
+
+        let mut bc = BuildContext::new(&mut app.world, root);
+        styled_button(false).update(&mut bc, &mut state);
+        app.world.run_system_once(update_styles);
+        assert_eq!(click(&mut app, entity), 1);
+    }
+}
@@ -0,0 +1,140 @@
+use bevy::prelude::*;
+
+use crate::{BuildContext, View};
+
+use crate::node_span::NodeSpan;
+
+/// An implementation of View that calls a callback once for each output entity, after the
+/// inner view is first built.
+pub struct ViewOnMount<V: View, F: Fn(EntityWorldMut) + Send> {
+    /// Inner view
+    pub(crate) inner: V,
+
+    /// Callback function called for each output entity when the view is built
+    pub(crate) callback: F,
+}
+
+impl<V: View, F: Fn(EntityWorldMut) + Send> View for ViewOnMount<V, F> {
+    type State = V::State;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        self.inner.nodes(bc, state)
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let state = self.inner.build(bc);
+        for entity in self.nodes(bc, &state).iter() {
+            (self.callback)(bc.world.entity_mut(entity));
+        }
+        state
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        self.inner.update(bc, state);
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        self.inner.assemble(bc, state)
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        self.inner.raze(world, state);
+    }
+}
+
+/// An implementation of View that calls a callback once for each output entity, just before
+/// the inner view is razed. The output entities are still alive when the callback is called.
+pub struct ViewOnUnmount<V: View, F: Fn(&mut World, Entity) + Send> {
+    /// Inner view
+    pub(crate) inner: V,
+
+    /// Callback function called for each output entity when the view is razed
+    pub(crate) callback: F,
+}
+
+impl<V: View, F: Fn(&mut World, Entity) + Send> View for ViewOnUnmount<V, F> {
+    /// Inner state, plus the output entities as of the last build or update, since `raze`
+    /// has no [`BuildContext`] with which to query them.
+    type State = (V::State, Vec<Entity>);
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        self.inner.nodes(bc, &state.0)
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let state = self.inner.build(bc);
+        let entities = self.inner.nodes(bc, &state).iter().collect();
+        (state, entities)
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        self.inner.update(bc, &mut state.0);
+        state.1.clear();
+        state.1.extend(self.inner.nodes(bc, &state.0).iter());
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        self.inner.assemble(bc, &mut state.0)
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        for entity in state.1.drain(..) {
+            (self.callback)(world, entity);
+        }
+        self.inner.raze(world, &mut state.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{Element, If};
+
+    static MOUNTED: AtomicUsize = AtomicUsize::new(0);
+    static UNMOUNTED: AtomicUsize = AtomicUsize::new(0);
+
+    fn view(show: bool) -> impl View {
+        If::new(
+            show,
+            Element::new()
+                .on_mount(|_| {
+                    MOUNTED.fetch_add(1, Ordering::SeqCst);
+                })
+                .on_unmount(|world, entity| {
+                    // Output entity is still alive.
+                    assert!(world.get::<Node>(entity).is_some());
+                    UNMOUNTED.fetch_add(1, Ordering::SeqCst);
+                }),
+            (),
+        )
+    }
+
+    #[test]
+    fn test_mount_unmount() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let mut state = view(true).build(&mut bc);
+        assert_eq!(MOUNTED.load(Ordering::SeqCst), 1);
+        assert_eq!(UNMOUNTED.load(Ordering::SeqCst), 0);
+
+        // Updating in place doesn't re-mount.
+        view(true).update(&mut bc, &mut state);
+        assert_eq!(MOUNTED.load(Ordering::SeqCst), 1);
+
+        // Conditional removal unmounts exactly once.
+        view(false).update(&mut bc, &mut state);
+        view(false).update(&mut bc, &mut state);
+        assert_eq!(MOUNTED.load(Ordering::SeqCst), 1);
+        assert_eq!(UNMOUNTED.load(Ordering::SeqCst), 1);
+
+        // Showing again mounts a new entity.
+        view(true).update(&mut bc, &mut state);
+        assert_eq!(MOUNTED.load(Ordering::SeqCst), 2);
+        view(true).raze(bc.world, &mut state);
+        assert_eq!(UNMOUNTED.load(Ordering::SeqCst), 2);
+    }
+}
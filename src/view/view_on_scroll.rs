@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+use crate::{BuildContext, ScrollHandler, View};
+
+use crate::node_span::NodeSpan;
+
+/// A wrapper view which binds a scroll handler to the output of an inner view.
+pub struct ViewOnScroll<V: View, F: Fn(Entity, Vec2, &mut World) + Send + Sync + 'static> {
+    pub(crate) inner: V,
+    pub(crate) handler: Arc<F>,
+}
+
+impl<V: View, F: Fn(Entity, Vec2, &mut World) + Send + Sync + 'static> ViewOnScroll<V, F> {
+    fn bind(&self, nodes: &NodeSpan, world: &mut World) {
+        match nodes {
+            NodeSpan::Empty => (),
+            NodeSpan::Node(entity) => {
+                world
+                    .entity_mut(*entity)
+                    .insert(ScrollHandler(self.handler.clone()));
+            }
+            NodeSpan::Fragment(ref nodes) => {
+                for node in nodes.iter() {
+                    // Recurse
+                    self.bind(node, world);
+                }
+            }
+        }
+    }
+}
+
+impl<V: View, F: Fn(Entity, Vec2, &mut World) + Send + Sync + 'static> View for ViewOnScroll<V, F> {
+    type State = V::State;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        self.inner.nodes(bc, state)
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let state = self.inner.build(bc);
+        self.bind(&self.nodes(bc, &state), bc.world);
+        state
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        self.inner.update(bc, state);
+        // Re-bind so that the handler sees the latest captured values.
+        self.bind(&self.nodes(bc, state), bc.world);
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        self.inner.assemble(bc, state)
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        self.inner.raze(world, state);
+    }
+}
@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+
+use crate::node_span::NodeSpan;
+use crate::{AtomHandle, AtomMethods, BuildContext, View};
+
+use super::atom::DeferredAtomWrites;
+
+/// Handle to an atom which records the output entity of a view, created by
+/// [`Cx::use_ref`](crate::Cx::use_ref) and filled in by [`View::ref_out`]. The value is `None`
+/// until the view has been built, and after it has been razed.
+pub type RefHandle = AtomHandle<Option<Entity>>;
+
+/// A wrapper view which records the output entity of an inner view into a [`RefHandle`]. The
+/// handle is updated once the current pass of presenter updates is complete, so presenters
+/// which read it are re-rendered in a later pass of the same update.
+pub struct ViewRefOut<V: View> {
+    pub(crate) inner: V,
+    pub(crate) handle: RefHandle,
+}
+
+impl<V: View> ViewRefOut<V> {
+    fn set_ref(&self, world: &mut World, value: Option<Entity>) {
+        // Only write the atom if the entity changed, to avoid re-rendering readers. The write
+        // is deferred until the end of the current pass, since the presenters which read the
+        // handle may already have been rendered in this one.
+        if world.get_atom(self.handle) != value {
+            world
+                .get_resource_or_insert_with(DeferredAtomWrites::default)
+                .set(self.handle, value);
+        }
+    }
+}
+
+impl<V: View> View for ViewRefOut<V> {
+    type State = V::State;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        self.inner.nodes(bc, state)
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let state = self.inner.build(bc);
+        self.update_ref(bc, &state);
+        state
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        self.inner.update(bc, state);
+        self.update_ref(bc, state);
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        self.inner.assemble(bc, state)
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        self.inner.raze(world, state);
+        self.set_ref(world, None);
+    }
+}
+
+impl<V: View> ViewRefOut<V> {
+    fn update_ref(&self, bc: &mut BuildContext, state: &V::State) {
+        match self.inner.nodes(bc, state) {
+            NodeSpan::Empty => self.set_ref(bc.world, None),
+            NodeSpan::Node(entity) => self.set_ref(bc.world, Some(entity)),
+            NodeSpan::Fragment(_) => {
+                panic!("ref_out can only be used on views which produce a single node")
+            }
+        }
+    }
+}
+
+impl<V: View + Clone> Clone for ViewRefOut<V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            handle: self.handle,
+        }
+    }
+}
+
+impl<V: View + PartialEq> PartialEq for ViewRefOut<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner && self.handle == other.handle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{plugin::render_views, Cx, Element, PresenterFn, ViewHandle};
+
+    #[derive(Resource, Default)]
+    struct ChildRef(Option<Entity>);
+
+    fn parent(mut cx: Cx) -> impl View {
+        let child_ref = cx.use_ref();
+        let entity = cx.read_atom(child_ref);
+        cx.bc.world.resource_mut::<ChildRef>().0 = entity;
+        child.bind(child_ref)
+    }
+
+    fn child(cx: Cx<RefHandle>) -> impl View {
+        Element::new().ref_out(*cx.props)
+    }
+
+    #[test]
+    fn test_ref_out() {
+        let mut world = World::new();
+        world.init_resource::<ChildRef>();
+        world.spawn(ViewHandle::new(parent, ()));
+        // Parent re-renders with the child's entity in the same update.
+        render_views(&mut world);
+        let entity = world.resource::<ChildRef>().0.expect("Child ref not set");
+        assert!(world.get::<Node>(entity).is_some());
+    }
+}
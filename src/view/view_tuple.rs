@@ -97,3 +97,26 @@ impl ViewTuple for Tuple {
         for_tuples!(#( self.Tuple.raze(world, &mut state.Tuple); )*)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Fragment;
+
+    #[test]
+    fn test_large_tuple() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        let items = ("1", "2", "3", "4", "5", "6", "7", "8");
+        assert_eq!(items.len(), 8);
+        let view = Fragment::new(items);
+        let mut state = view.build(&mut bc);
+        assert_eq!(view.nodes(&bc, &state).count(), 8);
+        view.update(&mut bc, &mut state);
+        assert_eq!(view.nodes(&bc, &state).count(), 8);
+        view.raze(bc.world, &mut state);
+        assert_eq!(bc.world.entities().len(), 1);
+    }
+}
@@ -0,0 +1,363 @@
+use std::{ops::Range, sync::Arc};
+
+use bevy::prelude::*;
+
+use crate::{BuildContext, ScrollArea, View};
+
+use crate::node_span::NodeSpan;
+
+/// A list view which only builds the items that are visible within a scrolling viewport,
+/// plus a small number of extra items (the "overscan") on either side. The space occupied by
+/// the items which are not built is filled by spacer elements before and after the visible
+/// items, so that the scroll content has the same size as if every item were present.
+///
+/// By default all items have the same height; lists whose items vary in height can supply an
+/// estimated height for each item with [`VirtualList::estimated_heights`]. When the visible
+/// range changes, items which remain visible keep their view state and output entities; only
+/// the items which scroll into or out of view are built or razed.
+pub struct VirtualList<V: View, F: Fn(usize) -> V + Send> {
+    count: usize,
+    item_height: f32,
+    /// Estimated height of each item, when items vary in height.
+    heights: Option<Arc<[f32]>>,
+    viewport_height: f32,
+    scroll_top: f32,
+    overscan: usize,
+    each: F,
+}
+
+impl<V: View, F: Fn(usize) -> V + Send> VirtualList<V, F> {
+    /// Construct a new `VirtualList` with `count` items, each `item_height` pixels tall. The
+    /// `each` callback is called with the index of each visible item, and returns its view.
+    pub fn new(count: usize, item_height: f32, each: F) -> Self {
+        Self {
+            count,
+            item_height,
+            heights: None,
+            viewport_height: 0.,
+            scroll_top: 0.,
+            overscan: 2,
+            each,
+        }
+    }
+
+    /// Set the height of the visible area and the current scroll position.
+    pub fn viewport(mut self, height: f32, scroll_top: f32) -> Self {
+        self.viewport_height = height;
+        self.scroll_top = scroll_top;
+        self
+    }
+
+    /// Set the viewport from the current state of a [`ScrollArea`].
+    pub fn scroll_area(self, scroll_area: &ScrollArea) -> Self {
+        self.viewport(scroll_area.visible_size.y, scroll_area.scroll_top)
+    }
+
+    /// Set the number of items to build beyond each edge of the visible area. The default is 2.
+    pub fn overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+
+    /// Use a separate height for each item, rather than the fixed item height. `heights` must
+    /// have an entry for each item, and need only be an estimate: the space reserved for the
+    /// items which are not built is based on these heights, while the items which are built are
+    /// laid out normally.
+    ///
+    /// The offsets of the items are only recomputed when the heights change, so a list which
+    /// memoizes `heights`, for example with [`Cx::use_memo`](crate::Cx::use_memo), doesn't pay
+    /// for them on each update.
+    pub fn estimated_heights(mut self, heights: impl Into<Arc<[f32]>>) -> Self {
+        let heights = heights.into();
+        assert_eq!(heights.len(), self.count, "Expected one height per item");
+        self.heights = Some(heights);
+        self
+    }
+
+    /// Returns the offset of the top of each item, plus the total height.
+    fn offsets(heights: &[f32]) -> Vec<f32> {
+        let mut offsets = Vec::with_capacity(heights.len() + 1);
+        let mut top = 0.;
+        offsets.push(top);
+        for height in heights {
+            top += height.max(0.);
+            offsets.push(top);
+        }
+        offsets
+    }
+
+    /// Returns the item offsets for the current heights, reusing `cached` if the heights are
+    /// unchanged.
+    fn cached_offsets(&self, cached: Option<CachedOffsets>) -> Option<CachedOffsets> {
+        let heights = self.heights.as_ref()?;
+        match cached {
+            Some(cached) if Arc::ptr_eq(&cached.0, heights) || cached.0 == *heights => Some(cached),
+            _ => Some((heights.clone(), Self::offsets(heights))),
+        }
+    }
+
+    /// Returns the distance from the top of the list to the top of the item at `index`.
+    /// An index equal to the item count returns the height of the whole list.
+    fn offset(&self, offsets: Option<&CachedOffsets>, index: usize) -> f32 {
+        match offsets {
+            Some((_, offsets)) => offsets[index],
+            None => index as f32 * self.item_height,
+        }
+    }
+
+    /// Returns the range of item indices which should be built.
+    fn window(&self, offsets: Option<&CachedOffsets>) -> Range<usize> {
+        let bottom = self.scroll_top + self.viewport_height;
+        let (first, last) = match offsets {
+            Some((_, offsets)) => (
+                offsets
+                    .partition_point(|&top| top <= self.scroll_top)
+                    .saturating_sub(1),
+                offsets.partition_point(|&top| top < bottom),
+            ),
+            None if self.item_height <= 0. => return 0..self.count,
+            None => (
+                (self.scroll_top / self.item_height).floor().max(0.) as usize,
+                (bottom / self.item_height).ceil().max(0.) as usize,
+            ),
+        };
+        first.saturating_sub(self.overscan).min(self.count)..(last + self.overscan).min(self.count)
+    }
+
+    fn spawn_spacer(world: &mut World, height: f32) -> Entity {
+        world
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        height: Val::Px(height),
+                        flex_shrink: 0.,
+                        ..default()
+                    },
+                    ..default()
+                },
+                Name::new("virtual_list_spacer"),
+            ))
+            .id()
+    }
+
+    fn set_spacer_height(world: &mut World, spacer: Entity, height: f32) {
+        let mut style = world.get_mut::<Style>(spacer).unwrap();
+        if style.height != Val::Px(height) {
+            style.height = Val::Px(height);
+        }
+    }
+
+    fn despawn_spacer(world: &mut World, spacer: Entity) {
+        let mut entt = world.entity_mut(spacer);
+        entt.remove_parent();
+        entt.despawn();
+    }
+}
+
+/// Item heights, and the item offsets computed from them.
+type CachedOffsets = (Arc<[f32]>, Vec<f32>);
+
+/// State for a [`VirtualList`].
+pub struct VirtualListState<V: View> {
+    spacer_before: Entity,
+    spacer_after: Entity,
+    /// Index of the first built item.
+    start: usize,
+    /// Views and states of the built items.
+    items: Vec<(V, V::State)>,
+    /// Item offsets, when items vary in height.
+    offsets: Option<CachedOffsets>,
+}
+
+impl<V: View, F: Fn(usize) -> V + Send> View for VirtualList<V, F> {
+    type State = VirtualListState<V>;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        let mut spans = Vec::with_capacity(state.items.len() + 2);
+        spans.push(NodeSpan::Node(state.spacer_before));
+        spans.extend(state.items.iter().map(|(view, st)| view.nodes(bc, st)));
+        spans.push(NodeSpan::Node(state.spacer_after));
+        NodeSpan::Fragment(spans.into_boxed_slice())
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let offsets = self.cached_offsets(None);
+        let window = self.window(offsets.as_ref());
+        let items = window
+            .clone()
+            .map(|i| {
+                let view = (self.each)(i);
+                let st = view.build(bc);
+                (view, st)
+            })
+            .collect();
+        VirtualListState {
+            spacer_before: Self::spawn_spacer(
+                bc.world,
+                self.offset(offsets.as_ref(), window.start),
+            ),
+            spacer_after: Self::spawn_spacer(
+                bc.world,
+                self.offset(offsets.as_ref(), self.count)
+                    - self.offset(offsets.as_ref(), window.end),
+            ),
+            start: window.start,
+            items,
+            offsets,
+        }
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        state.offsets = self.cached_offsets(state.offsets.take());
+        let offsets = state.offsets.as_ref();
+        let window = self.window(offsets);
+        let prev_window = state.start..state.start + state.items.len();
+        let mut prev_items: Vec<Option<(V, V::State)>> = state.items.drain(..).map(Some).collect();
+
+        // Update items which are still visible, and build the ones which are newly visible.
+        for i in window.clone() {
+            let view = (self.each)(i);
+            let item = if prev_window.contains(&i) {
+                let (_, mut st) = prev_items[i - prev_window.start].take().unwrap();
+                view.update(bc, &mut st);
+                (view, st)
+            } else {
+                let st = view.build(bc);
+                (view, st)
+            };
+            state.items.push(item);
+        }
+
+        // Raze items which are no longer visible.
+        for (view, mut st) in prev_items.into_iter().flatten() {
+            view.raze(bc.world, &mut st);
+        }
+
+        if window != prev_window {
+            state.start = window.start;
+            bc.mark_changed_shape();
+        }
+
+        let offsets = state.offsets.as_ref();
+        Self::set_spacer_height(
+            bc.world,
+            state.spacer_before,
+            self.offset(offsets, window.start),
+        );
+        Self::set_spacer_height(
+            bc.world,
+            state.spacer_after,
+            self.offset(offsets, self.count) - self.offset(offsets, window.end),
+        );
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        let mut spans = Vec::with_capacity(state.items.len() + 2);
+        spans.push(NodeSpan::Node(state.spacer_before));
+        spans.extend(
+            state
+                .items
+                .iter_mut()
+                .map(|(view, st)| view.assemble(bc, st)),
+        );
+        spans.push(NodeSpan::Node(state.spacer_after));
+        NodeSpan::Fragment(spans.into_boxed_slice())
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        for (view, st) in state.items.iter_mut() {
+            view.raze(world, st);
+        }
+        Self::despawn_spacer(world, state.spacer_before);
+        Self::despawn_spacer(world, state.spacer_after);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::texts;
+
+    fn list(scroll_top: f32) -> VirtualList<String, impl Fn(usize) -> String + Send> {
+        VirtualList::new(1000, 20., |i| format!("{}", i)).viewport(100., scroll_top)
+    }
+
+    #[test]
+    fn test_virtual_list() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        // 5 visible items plus 2 overscan after.
+        let mut state = list(0.).build(&mut bc);
+        assert_eq!(texts(bc.world), vec!["0", "1", "2", "3", "4", "5", "6"]);
+        assert_eq!(
+            bc.world.get::<Style>(state.spacer_after).unwrap().height,
+            Val::Px(993. * 20.)
+        );
+        let NodeSpan::Node(item_3) = state.items[3].0.nodes(&bc, &state.items[3].1) else {
+            panic!("Expected a single node");
+        };
+
+        // Scroll by 2 items: items which remain visible are reused.
+        list(40.).update(&mut bc, &mut state);
+        assert_eq!(
+            texts(bc.world),
+            vec!["0", "1", "2", "3", "4", "5", "6", "7", "8"]
+        );
+        assert!(bc.world.get::<Text>(item_3).is_some());
+
+        // Scroll to the middle.
+        list(10000.).update(&mut bc, &mut state);
+        assert_eq!(
+            texts(bc.world),
+            vec!["498", "499", "500", "501", "502", "503", "504", "505", "506"]
+        );
+        assert!(bc.world.get_entity(item_3).is_none());
+        assert_eq!(
+            bc.world.get::<Style>(state.spacer_before).unwrap().height,
+            Val::Px(498. * 20.)
+        );
+    }
+
+    #[test]
+    fn test_estimated_heights() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, entity);
+
+        // Even items are 10 pixels tall, odd items 30, so each pair of items is 40 pixels.
+        let heights: Arc<[f32]> = (0..100)
+            .map(|i| if i % 2 == 0 { 10. } else { 30. })
+            .collect();
+        let list = |scroll_top: f32| {
+            VirtualList::new(100, 0., |i| format!("{}", i))
+                .estimated_heights(heights.clone())
+                .viewport(50., scroll_top)
+                .overscan(0)
+        };
+
+        // Items 0-3 span 0..80, so only items 0-2 overlap the first 50 pixels.
+        let mut state = list(0.).build(&mut bc);
+        assert_eq!(texts(bc.world), vec!["0", "1", "2"]);
+        assert_eq!(
+            bc.world.get::<Style>(state.spacer_after).unwrap().height,
+            Val::Px(2000. - 50.)
+        );
+
+        // Item 21 spans 410..440, and item 23 spans 450..480. The heights are unchanged, so
+        // the offsets aren't recomputed.
+        let offsets = state.offsets.as_ref().unwrap().1.as_ptr();
+        list(415.).update(&mut bc, &mut state);
+        assert_eq!(state.offsets.as_ref().unwrap().1.as_ptr(), offsets);
+        assert_eq!(texts(bc.world), vec!["21", "22", "23"]);
+        assert_eq!(
+            bc.world.get::<Style>(state.spacer_before).unwrap().height,
+            Val::Px(410.)
+        );
+        assert_eq!(
+            bc.world.get::<Style>(state.spacer_after).unwrap().height,
+            Val::Px(2000. - 480.)
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! Window metrics which can be tracked by presenters.
+
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::*,
+    render::camera::RenderTarget,
+    ui::TargetCamera,
+    window::{PrimaryWindow, Window, WindowRef},
+};
+
+/// Summary of the properties of a window which are relevant to layout. This is kept as a
+/// component on each window entity, and is only modified when one of these properties
+/// changes, so that presenters which read it are not re-run on unrelated window changes
+/// such as cursor movement.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+pub struct WindowInfo {
+    /// Size of the window, in logical pixels.
+    pub logical_size: Vec2,
+
+    /// Ratio of physical pixels to logical pixels.
+    pub scale_factor: f32,
+
+    /// Whether the window has input focus.
+    pub focused: bool,
+}
+
+/// System which updates the [`WindowInfo`] component of each window.
+pub(crate) fn update_window_info(
+    mut commands: Commands,
+    mut query: Query<(Entity, &Window, Option<&mut WindowInfo>), Changed<Window>>,
+) {
+    for (entity, window, info) in query.iter_mut() {
+        let next = WindowInfo {
+            logical_size: Vec2::new(window.width(), window.height()),
+            scale_factor: window.scale_factor(),
+            focused: window.focused,
+        };
+        match info {
+            Some(mut info) => {
+                info.set_if_neq(next);
+            }
+            None => {
+                commands.entity(entity).insert(next);
+            }
+        }
+    }
+}
+
+/// Returns the window entity which a render target draws to, or `None` if it is not a window.
+/// `primary` is the primary window entity, used to resolve [`WindowRef::Primary`].
+pub fn target_window(target: &RenderTarget, primary: Option<Entity>) -> Option<Entity> {
+    match target {
+        RenderTarget::Window(WindowRef::Primary) => primary,
+        RenderTarget::Window(WindowRef::Entity(window)) => Some(*window),
+        _ => None,
+    }
+}
+
+/// System parameter which finds the window that a UI node is displayed in. The window is that
+/// of the camera named by the [`TargetCamera`] on the node's root, or the primary window if the
+/// root has no target camera.
+#[derive(SystemParam)]
+pub struct NodeWindow<'w, 's> {
+    parents: Query<'w, 's, &'static Parent>,
+    target_cameras: Query<'w, 's, &'static TargetCamera>,
+    cameras: Query<'w, 's, &'static Camera>,
+    primary: Query<'w, 's, Entity, With<PrimaryWindow>>,
+}
+
+impl<'w, 's> NodeWindow<'w, 's> {
+    /// Returns the window that `node` is displayed in, if any.
+    pub fn window(&self, node: Entity) -> Option<Entity> {
+        let root = self.parents.iter_ancestors(node).last().unwrap_or(node);
+        match self.target_cameras.get(root) {
+            Ok(target_camera) => self.camera_window(target_camera.entity()),
+            Err(_) => self.primary.get_single().ok(),
+        }
+    }
+
+    /// Returns the window that `camera` renders to, if any.
+    pub fn camera_window(&self, camera: Entity) -> Option<Entity> {
+        let camera = self.cameras.get(camera).ok()?;
+        target_window(&camera.target, self.primary.get_single().ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn test_target_window() {
+        let mut world = World::new();
+        let primary = world.spawn_empty().id();
+        let other = world.spawn_empty().id();
+        assert_eq!(
+            target_window(&RenderTarget::Window(WindowRef::Primary), Some(primary)),
+            Some(primary)
+        );
+        assert_eq!(
+            target_window(
+                &RenderTarget::Window(WindowRef::Entity(other)),
+                Some(primary)
+            ),
+            Some(other)
+        );
+        assert_eq!(
+            target_window(&RenderTarget::Window(WindowRef::Primary), None),
+            None
+        );
+        assert_eq!(
+            target_window(&RenderTarget::Image(Handle::default()), Some(primary)),
+            None
+        );
+    }
+
+    #[derive(Resource, Default)]
+    struct Found(Vec<Option<Entity>>);
+
+    #[test]
+    fn test_node_window() {
+        let mut world = World::new();
+        world.init_resource::<Found>();
+        let primary = world.spawn((Window::default(), PrimaryWindow)).id();
+        let palette = world.spawn(Window::default()).id();
+        let camera = world
+            .spawn(Camera {
+                target: RenderTarget::Window(WindowRef::Entity(palette)),
+                ..default()
+            })
+            .id();
+        let main_root = world.spawn_empty().id();
+        let main_child = world.spawn_empty().set_parent(main_root).id();
+        let palette_root = world.spawn(TargetCamera(camera)).id();
+        let palette_child = world.spawn_empty().set_parent(palette_root).id();
+
+        world.run_system_once(move |node_window: NodeWindow, mut found: ResMut<Found>| {
+            found.0 = vec![
+                node_window.window(main_root),
+                node_window.window(main_child),
+                node_window.window(palette_root),
+                node_window.window(palette_child),
+            ];
+        });
+        assert_eq!(
+            world.resource::<Found>().0,
+            vec![Some(primary), Some(primary), Some(palette), Some(palette)]
+        );
+    }
+}